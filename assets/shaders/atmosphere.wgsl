@@ -0,0 +1,51 @@
+#import bevy_pbr::forward_io::VertexOutput
+#import earth::constants::EARTH_RADIUS
+#import "shaders/scattering.wgsl"::{AtmosphereUniform, inscattered_light, ray_sphere, scattering_coords, sun_radiance_scale}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> atmosphere: AtmosphereUniform;
+// precomputed on the cpu, see scattering.rs
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var transmittance_lut: texture_2d<f32>;
+// one sampler for all three tables
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var lut_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var scattering_lut: texture_3d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(5) var multiple_scattering_lut: texture_3d<f32>;
+
+@fragment
+fn fragment(@builtin(front_facing) is_front: bool, mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let camera = atmosphere.camera_position;
+    let sun_dir = normalize(atmosphere.sun_direction);
+    let view_dir = normalize(mesh.world_position.xyz - camera);
+
+    // culling is off so the shell is visible from inside, from outside only the near side is used
+    let camera_outside = length(camera) > atmosphere.atmosphere_radius;
+    if camera_outside && !is_front {
+        discard;
+    }
+
+    // the ground applies its own aerial perspective, the shell only draws the sky
+    if ray_sphere(camera, view_dir, EARTH_RADIUS).x > 0.0 {
+        discard;
+    }
+
+    // the tables integrate from a point inside the shell, so start where the ray enters it
+    let shell = ray_sphere(camera, view_dir, atmosphere.atmosphere_radius);
+    if shell.y <= 0.0 {
+        discard;
+    }
+    let start = camera + view_dir * max(shell.x, 0.0);
+    let radius = length(start);
+    let up = start / radius;
+
+    let coords = scattering_coords(
+        radius,
+        dot(up, view_dir),
+        dot(up, sun_dir),
+        atmosphere.atmosphere_radius,
+    );
+    let single = textureSampleLevel(scattering_lut, lut_sampler, coords, 0.0);
+    let multiple = textureSampleLevel(multiple_scattering_lut, lut_sampler, coords, 0.0);
+    let scattered = inscattered_light(single, multiple.rgb, dot(view_dir, sun_dir), atmosphere);
+
+    // premultiplied: the scattered light is added, whatever is behind is dimmed by the transmittance
+    return vec4<f32>(scattered * sun_radiance_scale(), 1.0 - multiple.a);
+}
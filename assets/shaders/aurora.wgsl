@@ -0,0 +1,127 @@
+#import bevy_pbr::{
+    forward_io::VertexOutput,
+    mesh_view_bindings::{globals, view},
+}
+#import earth::constants::{EARTH_RADIUS, PI}
+#import "shaders/scattering.wgsl"::{ray_sphere, sun_radiance_scale}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> aurora: AuroraUniform;
+
+struct AuroraUniform {
+    // unit vector towards the northern geomagnetic pole
+    pole: vec3<f32>,
+    // geomagnetic latitude of the middle of the ovals, radians
+    oval_latitude: f32,
+    sun_direction: vec3<f32>,
+    oval_half_width: f32,
+    // km from the centre of the earth
+    bottom_radius: f32,
+    top_radius: f32,
+    // emission looking straight up through the middle of the oval, in units of the sun's light
+    intensity: f32,
+    _padding: f32,
+}
+
+const STEPS: u32 = 48u;
+// cosine of the sun's zenith angle over which the aurora fades in, from just below the horizon to nautical twilight
+const DUSK: f32 = -0.03;
+const DARK: f32 = -0.2;
+// radians the oval leans poleward at magnetic noon and equatorward at midnight
+const NIGHT_SHIFT: f32 = 0.06;
+// km the emission falls off over above its sharp lower edge
+const SCALE_HEIGHT: f32 = 60.0;
+// oxygen's green line low down, its red line high up
+const GREEN: vec3<f32> = vec3<f32>(0.15, 1.0, 0.35);
+const RED: vec3<f32> = vec3<f32>(1.0, 0.15, 0.2);
+// curtain folds and the fine rays along them, per turn around the pole
+const FOLDS: f32 = 24.0;
+const RAYS: f32 = 600.0;
+
+fn hash(n: f32) -> f32 {
+    return fract(sin(n * 12.9898) * 43758.5453);
+}
+
+// smooth noise repeating every `period` units, so it has no seam around the pole
+fn periodic_noise(x: f32, period: f32) -> f32 {
+    let cell = floor(x);
+    let t = x - cell;
+    let a = hash(cell - floor(cell / period) * period);
+    let b = hash(cell + 1.0 - floor((cell + 1.0) / period) * period);
+    return mix(a, b, t * t * (3.0 - 2.0 * t));
+}
+
+// light given off per km at a point, in units of the sun's light
+fn emission(position: vec3<f32>, sun_noon: vec3<f32>, east: vec3<f32>, north: vec3<f32>) -> vec3<f32> {
+    let radius = length(position);
+    let up = position / radius;
+    let altitude = radius - EARTH_RADIUS;
+
+    // only in the dark, from the terminator
+    let dark = 1.0 - smoothstep(DARK, DUSK, dot(up, aurora.sun_direction));
+    if dark <= 0.0 {
+        return vec3<f32>(0.0);
+    }
+
+    let pole_alignment = dot(up, aurora.pole);
+    let magnetic_latitude = abs(asin(clamp(pole_alignment, -1.0, 1.0)));
+    let around = up - aurora.pole * pole_alignment;
+    // 1 at magnetic noon, -1 at magnetic midnight
+    let local_time = dot(around, sun_noon) / max(length(around), 1e-4);
+    // fraction of a turn around the geomagnetic pole
+    let turn = atan2(dot(up, east), dot(up, north)) / (2.0 * PI) + 0.5;
+
+    // the curtains fold and drift slowly along the oval
+    let drift = globals.time * 0.02;
+    let fold = (periodic_noise(turn * FOLDS + drift, FOLDS) - 0.5) * aurora.oval_half_width;
+    let center = aurora.oval_latitude - NIGHT_SHIFT * local_time + fold;
+    let offset = (magnetic_latitude - center) / aurora.oval_half_width;
+    let oval = exp(-offset * offset * 4.0);
+    let rays = 0.4 + 0.6 * periodic_noise(turn * RAYS + drift * 10.0, RAYS);
+
+    // sharp lower edge, thinning upwards, normalised so a vertical path adds up to 1
+    let height = altitude - (aurora.bottom_radius - EARTH_RADIUS);
+    let profile = smoothstep(0.0, 10.0, height) * exp(-height / SCALE_HEIGHT) / SCALE_HEIGHT;
+    let color = mix(GREEN, RED, smoothstep(150.0, 250.0, altitude));
+
+    return color * oval * rays * profile * dark * aurora.intensity;
+}
+
+@fragment
+fn fragment(in: VertexOutput, @builtin(front_facing) is_front: bool) -> @location(0) vec4<f32> {
+    let camera = view.world_position;
+    let dir = normalize(in.world_position.xyz - camera);
+
+    // from outside the shell the front faces march through it, from inside only back faces are seen
+    let inside = length(camera) < aurora.top_radius;
+    if !is_front && !inside {
+        return vec4<f32>(0.0);
+    }
+
+    let shell = ray_sphere(camera, dir, aurora.top_radius);
+    var start = max(shell.x, 0.0);
+    var end = shell.y;
+    let ground = ray_sphere(camera, dir, EARTH_RADIUS);
+    if ground.y > 0.0 && ground.x > 0.0 {
+        end = min(end, ground.x);
+    }
+    if end <= start {
+        return vec4<f32>(0.0);
+    }
+
+    // frame around the geomagnetic pole, noon is towards the sun
+    let east = normalize(cross(aurora.pole, vec3<f32>(0.0, 1.0, 0.0)));
+    let north = cross(east, aurora.pole);
+    let sun_around = aurora.sun_direction - aurora.pole * dot(aurora.sun_direction, aurora.pole);
+    let sun_noon = sun_around / max(length(sun_around), 1e-4);
+
+    // offset per pixel, so the steps don't show as bands
+    let step = (end - start) / f32(STEPS);
+    var t = start + step * hash(dot(in.position.xy, vec2<f32>(1.0, 57.0)));
+    var light = vec3<f32>(0.0);
+    for (var i = 0u; i < STEPS; i++) {
+        light += emission(camera + dir * t, sun_noon, east, north) * step;
+        t += step;
+    }
+
+    return vec4<f32>(light * sun_radiance_scale(), 0.0);
+}
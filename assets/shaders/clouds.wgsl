@@ -0,0 +1,40 @@
+#import bevy_pbr::forward_io::VertexOutput
+#import "shaders/globe.wgsl"::{RasterFrames, raster_uv, sample_frames}
+#import "shaders/scattering.wgsl"::{sun_direction, sun_radiance_scale}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var cloud_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var cloud_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var<uniform> cloud_opacity: f32;
+// radians the clouds have drifted east of the surface
+@group(#{MATERIAL_BIND_GROUP}) @binding(4) var<uniform> cloud_drift: f32;
+// time series frames blend from cloud_texture into next_cloud_texture, both sampled with cloud_sampler
+@group(#{MATERIAL_BIND_GROUP}) @binding(5) var next_cloud_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(7) var<uniform> cloud_frames: RasterFrames;
+
+// clouds light up a little past the terminator, they sit above the ground's shadow
+const TERMINATOR_WIDTH: f32 = 0.15;
+const NIGHT_AMBIENT: f32 = 0.01;
+const SUNSET_COLOR: vec3<f32> = vec3<f32>(1.0, 0.55, 0.3);
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let normal = normalize(mesh.world_position.xyz);
+    let sun_dir = sun_direction();
+
+    let uv = raster_uv(normal, cloud_frames.bounds, cloud_drift);
+    let density = sample_frames(
+        cloud_texture,
+        cloud_sampler,
+        next_cloud_texture,
+        uv,
+        cloud_frames,
+    ).r;
+
+    let sun_angle = dot(normal, sun_dir);
+    let day_factor = smoothstep(-TERMINATOR_WIDTH, TERMINATOR_WIDTH, sun_angle);
+    // low sun reddens the cloud tops
+    let sunlight = mix(SUNSET_COLOR, vec3<f32>(1.0), smoothstep(0.0, 0.3, sun_angle));
+    let color = vec3<f32>(NIGHT_AMBIENT) + sunlight * day_factor * max(sun_angle, 0.2);
+
+    return vec4<f32>(color * sun_radiance_scale(), density * cloud_opacity);
+}
@@ -1,24 +1,125 @@
-#import bevy_pbr::forward_io::VertexOutput
+#import bevy_pbr::{
+    forward_io::{FragmentOutput, VertexOutput},
+    mesh_functions::get_tag,
+    mesh_view_bindings::{globals, view},
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
+}
+#import earth::constants::{CLOUD_RADIUS, DISPLACEMENT_SCALE, EARTH_RADIUS, PI}
+#import "shaders/scattering.wgsl"::{AtmosphereUniform, horizon, inscattered_light, ray_sphere, scattering_coords, sun_direction, sun_radiance_scale, transmittance_coords}
+#import "shaders/globe.wgsl"::{RasterFrames, raster_uv, sample_frames}
+#import "shaders/terrain.wgsl"::{heightmap_texel_size, sample_height, sample_height_uv, surface}
+#import "shaders/ocean.wgsl"::{OceanUniform, WATER_REFLECTANCE, cox_munk_variance, fresnel_schlick, slope_variance_to_roughness, water_color, wave_normal}
+
+// the global surface textures all share day_sampler
+@group(#{MATERIAL_BIND_GROUP}) @binding(100) var day_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(101) var day_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(102) var night_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(104) var ocean_mask: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(106) var specular_map: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(108) var normal_map: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(109) var normal_map_sampler: sampler;
+// the same atmosphere and tables as the sky shell, camera and sun come from the view and its lights
+@group(#{MATERIAL_BIND_GROUP}) @binding(111) var<uniform> atmosphere: AtmosphereUniform;
+// the tables share transmittance_sampler
+@group(#{MATERIAL_BIND_GROUP}) @binding(112) var transmittance_lut: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(113) var transmittance_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(114) var scattering_lut: texture_3d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(116) var multiple_scattering_lut: texture_3d<f32>;
+// the cloud layer, for its shadows, both frames sampled with cloud_sampler
+@group(#{MATERIAL_BIND_GROUP}) @binding(118) var cloud_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(119) var cloud_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(122) var next_cloud_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(124) var<uniform> cloud_frames: RasterFrames;
+// ocean shading, bathymetry is a 1x1 placeholder when there is none
+@group(#{MATERIAL_BIND_GROUP}) @binding(125) var<uniform> ocean_uniform: OceanUniform;
+@group(#{MATERIAL_BIND_GROUP}) @binding(126) var bathymetry: texture_2d<f32>;
+// monthly surface textures blend from day_texture into this one, see SurfaceUniform
+@group(#{MATERIAL_BIND_GROUP}) @binding(128) var next_day_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(134) var uv_checker: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(135) var uv_checker_sampler: sampler;
+// tunable look of the surface, see EarthShadingSettings in shading.rs
+@group(#{MATERIAL_BIND_GROUP}) @binding(136) var<uniform> shading: ShadingUniform;
+// terrain analysis views, see TerrainAnalysis in analysis.rs
+@group(#{MATERIAL_BIND_GROUP}) @binding(137) var<uniform> analysis: AnalysisUniform;
+@group(#{MATERIAL_BIND_GROUP}) @binding(138) var analysis_ramp: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(139) var analysis_ramp_sampler: sampler;
+// tiled ground textures for close views, see detail.rs, all repeating with detail_sampler
+@group(#{MATERIAL_BIND_GROUP}) @binding(140) var detail_rock: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(141) var detail_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(142) var detail_grass: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(144) var detail_snow: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(146) var detail_sand: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(148) var<uniform> detail: DetailUniform;
+// land cover class codes and their palette, see LandCover in land_cover.rs
+@group(#{MATERIAL_BIND_GROUP}) @binding(149) var land_cover: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(150) var land_cover_palette: texture_2d<f32>;
+// horizon elevations towards N, NE, E, SE and S, SW, W, NW, see horizon.rs
+@group(#{MATERIAL_BIND_GROUP}) @binding(152) var horizon_north_east: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(153) var horizon_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(154) var horizon_south_west: texture_2d<f32>;
 
-@group(2) @binding(0) var day_texture: texture_2d<f32>;
-@group(2) @binding(1) var day_sampler: sampler;
-@group(2) @binding(2) var night_texture: texture_2d<f32>;
-@group(2) @binding(3) var night_sampler: sampler;
-@group(2) @binding(4) var ocean_mask: texture_2d<f32>;
-@group(2) @binding(5) var ocean_mask_sampler: sampler;
-@group(2) @binding(6) var specular_map: texture_2d<f32>;
-@group(2) @binding(7) var specular_map_sampler: sampler;
-@group(2) @binding(8) var normal_map: texture_2d<f32>;
-@group(2) @binding(9) var normal_map_sampler: sampler;
-@group(2) @binding(10)  var<uniform> sun_uniform: SunUniform;
+// EarthShadingSettings, each value is documented by its constant in config.rs
+// night_ambient and city_lights_strength are in units of the sun's light, see sun_radiance_scale
+struct ShadingUniform {
+    normal_strength: f32,
+    terminator_width: f32,
+    night_ambient: f32,
+    night_desaturation: f32,
+    city_lights_strength: f32,
+    calm_water_roughness: f32,
+    rough_water_roughness: f32,
+    cloud_shadow_strength: f32,
+    terrain_shadow_strength: f32,
+}
+
+struct AnalysisUniform {
+    mode: u32,
+    // values the ends of the colour ramp stand for, in metres or degrees
+    ramp_min: f32,
+    ramp_max: f32,
+    // real km a heightmap value of 1 stands for, displacement_scale exaggerates it
+    max_elevation: f32,
+}
 
-struct SunUniform {
-    sun_uniform: vec3<f32>,
-    // 16-byte alignment
-    _padding: f32,
+struct DetailUniform {
+    // km of ground one repeat of a texture covers
+    tile_size: f32,
+    // camera distances (km) the detail starts fading in at and is fully in at
+    fade_start: f32,
+    fade_end: f32,
+    // share of the day colour replaced when fully in, 0 without detail textures
+    strength: f32,
+    max_elevation: f32,
+    // metres, at the equator
+    snow_line: f32,
+    // degrees
+    rock_slope: f32,
+    desert_latitude: f32,
 }
 
-const PI: f32 = 3.14159265;
+// values of debug_mode
+const DEBUG_OFF: u32 = 0u;
+const DEBUG_NORMALS: u32 = 1u;
+const DEBUG_UV_CHECKER: u32 = 2u;
+const DEBUG_CHUNKS: u32 = 3u;
+// values of analysis.mode
+const ANALYSIS_OFF: u32 = 0u;
+const ANALYSIS_ELEVATION: u32 = 1u;
+const ANALYSIS_SLOPE: u32 = 2u;
+const ANALYSIS_ASPECT: u32 = 3u;
+// slopes flatter than this (degrees) have no meaningful aspect and fade to grey
+const ASPECT_MIN_SLOPE: f32 = 1.0;
+// widths of the transitions between detail textures, in metres, degrees of slope and degrees of latitude
+const SNOW_BLEND: f32 = 300.0;
+const ROCK_BLEND: f32 = 5.0;
+const DESERT_HALF_WIDTH: f32 = 10.0;
+// deserts give way to grass and rock in the mountains
+const DESERT_MAX_ELEVATION: f32 = 1500.0;
+// radians over which the sun sets behind the relief, it isn't a point
+const SUN_SOFTNESS: f32 = 0.01;
+// repeats of the checker around and down the globe
+const UV_CHECKER_TILES: vec2<f32> = vec2<f32>(32.0, 16.0);
 
 // desaturate a color
 fn desaturate(color: vec3<f32>, factor: f32) -> vec3<f32> {
@@ -35,19 +136,19 @@ fn calculate_sphere_tangent_space(world_pos: vec3<f32>, uv: vec2<f32>) -> mat3x3
     let latitude = (0.5 - uv.y) * PI;
 
     // calculate tangent
-    // direction of increasing longitude
+    // direction of increasing longitude, the mesh puts longitude 0 on +z and 90°E on +x
     let tangent = vec3<f32>(
-        -sin(longitude),
-        0.0,
         cos(longitude),
+        0.0,
+        -sin(longitude),
     );
 
     // calculate bitangent
     // direction of increasing latitude
     let bitangent = vec3<f32>(
-        -cos(longitude) * sin(latitude),
+        -sin(latitude) * sin(longitude),
         cos(latitude),
-        -sin(longitude) * cos(latitude)
+        -sin(latitude) * cos(longitude),
     );
 
     let normal = point_on_sphere;
@@ -59,6 +160,11 @@ fn calculate_sphere_tangent_space(world_pos: vec3<f32>, uv: vec2<f32>) -> mat3x3
     );
 }
 
+// the normal and horizon maps are baked at DISPLACEMENT_SCALE, their slopes scale with the relief
+fn relief_exaggeration() -> f32 {
+    return max(surface.displacement_scale, 0.0) / DISPLACEMENT_SCALE;
+}
+
 // sample and decode normal
 fn sample_normal_map_shpere(uv: vec2<f32>, world_pos: vec3<f32>) -> vec3<f32> {
     // get the mesh normal
@@ -67,24 +173,391 @@ fn sample_normal_map_shpere(uv: vec2<f32>, world_pos: vec3<f32>) -> vec3<f32> {
     // sample normal map
     let normal_sample = textureSample(normal_map, normal_map_sampler, uv).rgb;
 
-    let detail_normal = normal_sample * 2.0 - 1.0;
+    let baked = normal_sample * 2.0 - 1.0;
+    let detail_normal = normalize(vec3<f32>(baked.xy * relief_exaggeration(), baked.z));
     let tbn_matrix = calculate_sphere_tangent_space(world_pos, uv);
 
     // transform detail normal to world space
     let world_detail_normal = tbn_matrix * detail_normal;
 
-    // u can control normal strength here
-    let blended_normal = normalize(mesh_normal + world_detail_normal * 0.32);
+    let blended_normal = normalize(mesh_normal + world_detail_normal * shading.normal_strength);
 
     return blended_normal;
 }
 
-// calculate specular value at a given position
-fn calculate_specular(world_normal: vec3<f32>, light_dir: vec3<f32>, view_dir: vec3<f32>, roughness: f32, specular_strength: f32) -> f32 {
-    let halfway = normalize(light_dir + view_dir);
-    let shininess = mix(512.0, 1.0, roughness);
+// transmittance from a point towards the top of the atmosphere, zero below the horizon
+fn transmittance_to_top(position: vec3<f32>, dir: vec3<f32>) -> vec3<f32> {
+    let radius = length(position);
+    let mu = dot(position / radius, dir);
+    if mu < horizon(radius) {
+        return vec3<f32>(0.0);
+    }
+    let coords = transmittance_coords(radius, mu, atmosphere.atmosphere_radius);
+    return textureSampleLevel(transmittance_lut, transmittance_sampler, coords, 0.0).rgb;
+}
+
+// sunlight left after passing through the cloud layer
+// the cloud is looked up where the ray towards the sun crosses the layer, so shadows shift away from the sun
+fn cloud_shadow(position: vec3<f32>, sun_dir: vec3<f32>) -> f32 {
+    let hit = ray_sphere(position, sun_dir, CLOUD_RADIUS);
+    if hit.y <= 0.0 {
+        return 1.0;
+    }
+    let uv = raster_uv(position + sun_dir * hit.y, cloud_frames.bounds, surface.cloud_drift);
+    let density = sample_frames(
+        cloud_texture,
+        cloud_sampler,
+        next_cloud_texture,
+        uv,
+        cloud_frames,
+    ).r;
+    return 1.0 - density * surface.cloud_opacity * shading.cloud_shadow_strength;
+}
+
+// sky light arriving at a point on the ground from a direction, used for reflections
+fn sky_radiance(position: vec3<f32>, dir: vec3<f32>, sun_dir: vec3<f32>) -> vec3<f32> {
+    let radius = length(position);
+    let up = position / radius;
+    // reflections that point into the ground see the horizon instead
+    let mu = max(dot(up, dir), 0.01);
+    let coords = scattering_coords(radius, mu, dot(up, sun_dir), atmosphere.atmosphere_radius);
+    let single = textureSampleLevel(scattering_lut, transmittance_sampler, coords, 0.0);
+    let multiple = textureSampleLevel(multiple_scattering_lut, transmittance_sampler, coords, 0.0);
+    return inscattered_light(single, multiple.rgb, dot(dir, sun_dir), atmosphere);
+}
+
+// sunlight left after the relief around a point, from the horizon map
+// only darkens slopes the sun has set behind, the day/night mask stays on the smooth sphere
+fn terrain_shadow(world_pos: vec3<f32>, uv: vec2<f32>, sun_dir: vec3<f32>) -> f32 {
+    let north_east = textureSample(horizon_north_east, horizon_sampler, uv);
+    let south_west = textureSample(horizon_south_west, horizon_sampler, uv);
+    var horizon = array<f32, 8>(
+        north_east.r, north_east.g, north_east.b, north_east.a,
+        south_west.r, south_west.g, south_west.b, south_west.a,
+    );
+
+    // sun in the local frame, azimuth clockwise from north in eighths of a turn
+    let tbn = calculate_sphere_tangent_space(world_pos, uv);
+    let east = dot(sun_dir, tbn[0]);
+    let north = dot(sun_dir, tbn[1]);
+    let elevation = asin(clamp(dot(sun_dir, tbn[2]), -1.0, 1.0));
+    var azimuth = atan2(east, north) / (PI / 4.0);
+    azimuth = azimuth - floor(azimuth / 8.0) * 8.0;
+
+    let first = u32(azimuth) % 8u;
+    let second = (first + 1u) % 8u;
+    // stored as fractions of 90 degrees, the rise over distance is what scales with the relief
+    let baked = min(mix(horizon[first], horizon[second], fract(azimuth)), 0.999) * PI / 2.0;
+    let horizon_elevation = atan(tan(baked) * relief_exaggeration());
+
+    let lit = smoothstep(horizon_elevation - SUN_SOFTNESS, horizon_elevation + SUN_SOFTNESS, elevation);
+    return 1.0 - (1.0 - lit) * shading.terrain_shadow_strength;
+}
+
+struct OceanSurface {
+    normal: vec3<f32>,
+    color: vec3<f32>,
+    perceptual_roughness: f32,
+}
+
+// water seen at a given pixel size, the specular map scales how rough the wind makes it
+fn ocean_surface(
+    position: vec3<f32>,
+    uv: vec2<f32>,
+    surface_color: vec3<f32>,
+    specular_strength: f32,
+    footprint: f32,
+) -> OceanSurface {
+    let sphere_normal = normalize(position);
+    let wind_speed = ocean_uniform.wind_speed;
+    let waves = wave_normal(position, sphere_normal, globals.time, wind_speed, footprint);
+
+    let roughness = mix(shading.rough_water_roughness, shading.calm_water_roughness, specular_strength);
+    let slope_variance = cox_munk_variance(wind_speed) * roughness + waves.unresolved_variance;
+
+    var result: OceanSurface;
+    result.normal = waves.normal;
+    result.perceptual_roughness = slope_variance_to_roughness(slope_variance);
+    // the day texture already shows the ocean's colour, bathymetry replaces it where available
+    result.color = surface_color;
+    if ocean_uniform.bathymetry_depth > 0.0 {
+        let depth = textureSample(bathymetry, day_sampler, uv).r * ocean_uniform.bathymetry_depth;
+        result.color = water_color(depth);
+    }
+    return result;
+}
+
+struct AerialPerspective {
+    inscattered: vec3<f32>,
+    transmittance: vec3<f32>,
+}
+
+// haze between the camera and a point on the ground
+fn aerial_perspective(position: vec3<f32>, sun_dir: vec3<f32>) -> AerialPerspective {
+    var result: AerialPerspective;
+    result.inscattered = vec3<f32>(0.0);
+    result.transmittance = vec3<f32>(1.0);
+
+    let camera = view.world_position;
+    let view_dir = normalize(position - camera);
+
+    // start where the view ray enters the atmosphere, the tables integrate from there to the ground
+    let shell = ray_sphere(camera, view_dir, atmosphere.atmosphere_radius);
+    if shell.y <= 0.0 {
+        return result;
+    }
+    let start = camera + view_dir * max(shell.x, 0.0);
+    let radius = length(start);
+    let up = start / radius;
+
+    let coords = scattering_coords(
+        radius,
+        dot(up, view_dir),
+        dot(up, sun_dir),
+        atmosphere.atmosphere_radius,
+    );
+    let single = textureSampleLevel(scattering_lut, transmittance_sampler, coords, 0.0);
+    let multiple = textureSampleLevel(multiple_scattering_lut, transmittance_sampler, coords, 0.0);
+    result.inscattered = inscattered_light(single, multiple.rgb, dot(view_dir, sun_dir), atmosphere);
+
+    // a ray ending on the ground is looked up reversed, pointing up from both ends (Bruneton 2017)
+    let from_ground = transmittance_to_top(position, -view_dir);
+    let from_start = transmittance_to_top(start, -view_dir);
+    result.transmittance = min(from_ground / max(from_start, vec3<f32>(1e-6)), vec3<f32>(1.0));
+    return result;
+}
+
+// texture projected along the world axes, weighted by how squarely each faces the surface
+// avoids the stretching and seams a latitude/longitude tiling would have at the poles
+fn triplanar(texture: texture_2d<f32>, texture_sampler: sampler, position: vec3<f32>, normal: vec3<f32>) -> vec3<f32> {
+    var weights = pow(abs(normal), vec3<f32>(4.0));
+    weights = weights / (weights.x + weights.y + weights.z);
+    let x = textureSample(texture, texture_sampler, position.yz).rgb;
+    let y = textureSample(texture, texture_sampler, position.zx).rgb;
+    let z = textureSample(texture, texture_sampler, position.xy).rgb;
+    return x * weights.x + y * weights.y + z * weights.z;
+}
+
+// the day texture with tiled ground textures blended in as the camera gets close
+// grass is the base, sand covers the desert belts, rock the steep slopes and snow whatever is above the snow line
+fn terrain_detail(day_color: vec3<f32>, world_pos: vec3<f32>, uv: vec2<f32>) -> vec3<f32> {
+    // switched off, skip the ground texture samples
+    if detail.strength == 0.0 {
+        return day_color;
+    }
+    let fade = 1.0 - smoothstep(detail.fade_end, detail.fade_start, distance(view.world_position, world_pos));
+
+    let direction = normalize(world_pos);
+    let latitude = degrees(asin(direction.y));
+    let elevation = sample_height(direction) * detail.max_elevation * 1000.0;
+    // slope of the normal map itself, so normal_strength only changes the lighting
+    let map_normal = normalize(textureSample(normal_map, normal_map_sampler, uv).rgb * 2.0 - 1.0);
+    let slope = degrees(acos(clamp(map_normal.z, -1.0, 1.0)));
+
+    let desert = (1.0 - smoothstep(0.0, DESERT_HALF_WIDTH, abs(abs(latitude) - detail.desert_latitude)))
+        * (1.0 - smoothstep(DESERT_MAX_ELEVATION * 0.5, DESERT_MAX_ELEVATION, elevation));
+    let rock = smoothstep(detail.rock_slope - ROCK_BLEND, detail.rock_slope + ROCK_BLEND, slope);
+    // the snow line drops towards the poles, and snow doesn't stay on cliffs
+    let snow_line = detail.snow_line * pow(cos(radians(latitude)), 1.5);
+    let snow = smoothstep(snow_line - SNOW_BLEND, snow_line + SNOW_BLEND, elevation) * (1.0 - rock);
+
+    let tiled = world_pos / detail.tile_size;
+    var ground = triplanar(detail_grass, detail_sampler, tiled, direction);
+    ground = mix(ground, triplanar(detail_sand, detail_sampler, tiled, direction), desert);
+    ground = mix(ground, triplanar(detail_rock, detail_sampler, tiled, direction), rock);
+    ground = mix(ground, triplanar(detail_snow, detail_sampler, tiled, direction), snow);
+    return mix(day_color, ground, fade * detail.strength);
+}
+
+struct LandCoverSurface {
+    color: vec3<f32>,
+    perceptual_roughness: f32,
+    // 0 where the code isn't one of the classes or the land cover is hidden
+    coverage: f32,
+}
+
+// class of the nearest land cover pixel, the codes can't be interpolated
+fn land_cover_surface(uv: vec2<f32>) -> LandCoverSurface {
+    var result: LandCoverSurface;
+    result.coverage = 0.0;
+    if surface.show_land_cover == 0u {
+        return result;
+    }
+
+    let size = vec2<i32>(textureDimensions(land_cover));
+    let pixel = clamp(vec2<i32>(uv * vec2<f32>(size)), vec2<i32>(0), size - 1);
+    let code = i32(round(textureLoad(land_cover, pixel, 0).r * 255.0));
+    let entry = textureLoad(land_cover_palette, vec2<i32>(code, 0), 0);
+    result.color = entry.rgb;
+    result.coverage = entry.a;
+    result.perceptual_roughness = textureLoad(land_cover_palette, vec2<i32>(code, 1), 0).r;
+    return result;
+}
+
+// unlit colour of the diagnostic views
+fn debug_color(in: VertexOutput, relief_normal: vec3<f32>, sun_dir: vec3<f32>) -> vec3<f32> {
+    switch surface.debug_mode {
+        case DEBUG_NORMALS: {
+            return normalize(in.world_normal) * 0.5 + 0.5;
+        }
+        case DEBUG_UV_CHECKER: {
+            return textureSampleLevel(uv_checker, uv_checker_sampler, fract(in.uv * UV_CHECKER_TILES), 0.0).rgb;
+        }
+        case DEBUG_CHUNKS: {
+            // golden ratio hues, so neighbouring chunks never get similar colours
+            let hue = fract(f32(get_tag(in.instance_index)) * 0.618034);
+            return 0.5 + 0.5 * cos(2.0 * PI * (hue + vec3<f32>(0.0, 1.0 / 3.0, 2.0 / 3.0)));
+        }
+        default: {
+            // normal map only, a grey surface under the sun with a little ambient
+            return vec3<f32>(0.8) * max(dot(relief_normal, sun_dir), 0.0) + 0.05;
+        }
+    }
+}
+
+struct TerrainGradient {
+    // degrees
+    slope: f32,
+    // direction the slope faces, degrees clockwise from north
+    aspect: f32,
+}
+
+// slope and aspect of the real terrain, from central differences of the heightmap
+fn terrain_gradient(uv: vec2<f32>, latitude: f32) -> TerrainGradient {
+    let texel = heightmap_texel_size();
+    let east = sample_height_uv(uv + vec2<f32>(texel.x, 0.0));
+    let west = sample_height_uv(uv - vec2<f32>(texel.x, 0.0));
+    let north = sample_height_uv(uv - vec2<f32>(0.0, texel.y));
+    let south = sample_height_uv(uv + vec2<f32>(0.0, texel.y));
+
+    // km between the samples, a texel spans less ground towards the poles
+    let east_distance = 2.0 * texel.x * 2.0 * PI * EARTH_RADIUS * max(cos(latitude), 1e-3);
+    let north_distance = 2.0 * texel.y * PI * EARTH_RADIUS;
+    let gradient = vec2<f32>(
+        (east - west) * analysis.max_elevation / east_distance,
+        (north - south) * analysis.max_elevation / north_distance,
+    );
+
+    var result: TerrainGradient;
+    result.slope = degrees(atan(length(gradient)));
+    // the slope faces downhill
+    result.aspect = degrees(atan2(-gradient.x, -gradient.y));
+    result.aspect = result.aspect - floor(result.aspect / 360.0) * 360.0;
+    return result;
+}
+
+// colour of the terrain analysis views, on the ramp baked in analysis.rs
+fn analysis_color(uv: vec2<f32>, world_pos: vec3<f32>) -> vec3<f32> {
+    let direction = normalize(world_pos);
+    var value: f32;
+    var flat_fade = 1.0;
+    switch analysis.mode {
+        case ANALYSIS_ELEVATION: {
+            value = sample_height(direction) * analysis.max_elevation * 1000.0;
+        }
+        case ANALYSIS_SLOPE: {
+            value = terrain_gradient(uv, asin(direction.y)).slope;
+        }
+        case ANALYSIS_ASPECT, default: {
+            let gradient = terrain_gradient(uv, asin(direction.y));
+            value = gradient.aspect;
+            flat_fade = smoothstep(0.0, ASPECT_MIN_SLOPE, gradient.slope);
+        }
+    }
+
+    let t = clamp((value - analysis.ramp_min) / (analysis.ramp_max - analysis.ramp_min), 0.0, 1.0);
+    // sample texel centres, so the ends of the ramp aren't blended with the clamped edge
+    let width = f32(textureDimensions(analysis_ramp).x);
+    let ramp_uv = vec2<f32>((t * (width - 1.0) + 0.5) / width, 0.5);
+    let color = textureSampleLevel(analysis_ramp, analysis_ramp_sampler, ramp_uv, 0.0).rgb;
+    return mix(vec3<f32>(0.5), color, flat_fade);
+}
+
+// relief lit from the north-west at 45 degrees, the usual hillshade of maps
+fn hillshade(relief_normal: vec3<f32>, world_pos: vec3<f32>, uv: vec2<f32>) -> f32 {
+    let tbn = calculate_sphere_tangent_space(world_pos, uv);
+    let light = normalize(tbn * vec3<f32>(-0.5, 0.5, 0.707));
+    return 0.6 + 0.4 * max(dot(relief_normal, light), 0.0);
+}
+
+@fragment
+fn fragment(in: VertexOutput, @builtin(front_facing) is_front: bool) -> FragmentOutput {
+    let uv = in.uv;
+    let world_pos = in.world_position.xyz;
+    // size of this pixel on the ground (km), before any branching
+    let footprint = length(fwidth(world_pos));
+
+    let sun_dir = sun_direction();
+    let view_dir = normalize(view.world_position - world_pos);
+
+    // the day/night mask uses the smooth sphere normal, relief only shades the day side
+    // otherwise cities on mountain sides light up earlier/later than they should
+    let sphere_normal = normalize(world_pos);
+    let relief_normal = sample_normal_map_shpere(uv, world_pos);
+
+    if surface.debug_mode != DEBUG_OFF {
+        var out: FragmentOutput;
+        out.color = vec4<f32>(debug_color(in, relief_normal, sun_dir), 1.0);
+        return out;
+    }
+
+    // terrain analysis, shaded like a white surface facing the sun so it is exposed like the daylit globe
+    if analysis.mode != ANALYSIS_OFF {
+        let color = analysis_color(uv, world_pos) * hillshade(relief_normal, world_pos, uv);
+        var out: FragmentOutput;
+        out.color = vec4<f32>(color * sun_radiance_scale(), 1.0);
+        return out;
+    }
+    let day_factor = smoothstep(-shading.terminator_width, shading.terminator_width, dot(sphere_normal, sun_dir));
+
+    let day_color = mix(
+        textureSample(day_texture, day_sampler, uv).rgb,
+        textureSample(next_day_texture, day_sampler, uv).rgb,
+        surface.day_blend,
+    );
+    let night_lights = textureSample(night_texture, day_sampler, uv).rgb;
+    let cover = land_cover_surface(uv);
+    let ground_color = mix(terrain_detail(day_color, world_pos, uv), cover.color, cover.coverage);
+
+    // water, mixed in by the ocean mask so coastlines stay soft
+    let ocean = textureSample(ocean_mask, day_sampler, uv).r;
+    let specular_strength = textureSample(specular_map, day_sampler, uv).r;
+    let water = ocean_surface(world_pos, uv, day_color, specular_strength, footprint);
+
+    // describe the surface to bevy, its lights and shadow maps do the direct lighting and sun glint
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+    pbr_input.material.base_color = vec4<f32>(mix(ground_color, water.color, ocean), 1.0);
+    pbr_input.material.perceptual_roughness = mix(
+        mix(pbr_input.material.perceptual_roughness, cover.perceptual_roughness, cover.coverage),
+        water.perceptual_roughness,
+        ocean,
+    );
+    pbr_input.material.reflectance = mix(pbr_input.material.reflectance, vec3<f32>(WATER_REFLECTANCE), ocean);
+    pbr_input.N = normalize(mix(relief_normal, water.normal, ocean));
+    let lit = apply_pbr_lighting(pbr_input).rgb;
+
+    // sunlight reddens as it crosses more air towards the terminator, clouds and the relief shade it
+    let sunlight = transmittance_to_top(world_pos, sun_dir)
+        * cloud_shadow(world_pos, sun_dir)
+        * terrain_shadow(world_pos, uv, sun_dir);
+
+    // night side, a dim desaturated surface under the city lights
+    let night = (desaturate(ground_color, shading.night_desaturation) * shading.night_ambient
+        + night_lights * shading.city_lights_strength)
+        * (1.0 - day_factor);
+
+    // the sky reflected off the water
+    let reflected = reflect(-view_dir, water.normal);
+    let sky = sky_radiance(world_pos, reflected, sun_dir) * fresnel_schlick(dot(water.normal, view_dir)) * ocean;
+
+    let sun_scale = sun_radiance_scale();
+    let shaded = lit * sunlight + (night + sky) * sun_scale;
 
-    // calculate specular component
-    let spec = pow(max(dot(world_normal, halfway), 0.0), shininess);
-    return spec * specular_strength;
+    // distant terrain fades into the haze
+    let haze = aerial_perspective(world_pos, sun_dir);
+    var out: FragmentOutput;
+    out.color = vec4<f32>(shaded * haze.transmittance + haze.inscattered * sun_scale, 1.0);
+    // fog, and tonemapping for cameras without hdr
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+    return out;
 }
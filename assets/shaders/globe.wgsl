@@ -0,0 +1,52 @@
+// Mapping between directions from the earth's center and the equirectangular textures
+// the scene puts latitude 0, longitude 0 on +z, 90°E on +x and the north pole on +y
+
+#import earth::constants::PI
+
+// a raster sequence's extent and position between its two frames, see sequence.rs
+struct RasterFrames {
+    // west, south, east, north in radians
+    bounds: vec4<f32>,
+    blend: f32,
+    // 16-byte alignment
+    _padding: vec3<f32>,
+}
+
+fn direction_to_uv(direction: vec3<f32>) -> vec2<f32> {
+    let dir = normalize(direction);
+    let longitude = atan2(dir.x, dir.z);
+    let latitude = asin(clamp(dir.y, -1.0, 1.0));
+    return vec2<f32>(longitude / (2.0 * PI) + 0.5, 0.5 - latitude / PI);
+}
+
+// uv inside a raster covering `bounds`, outside 0..1 where the raster doesn't reach
+// the raster is shifted east by the drift angle (radians)
+fn raster_uv(direction: vec3<f32>, bounds: vec4<f32>, drift: f32) -> vec2<f32> {
+    let dir = normalize(direction);
+    let longitude = atan2(dir.x, dir.z) - drift;
+    let latitude = asin(clamp(dir.y, -1.0, 1.0));
+
+    // wrap the longitude into the turn starting at the west edge
+    let east_of_west = longitude - bounds.x - floor((longitude - bounds.x) / (2.0 * PI)) * 2.0 * PI;
+    return vec2<f32>(
+        east_of_west / (bounds.z - bounds.x),
+        (bounds.w - latitude) / (bounds.w - bounds.y),
+    );
+}
+
+// blends the two frames around the clock, zero outside the raster
+// sampled without mips, the uv wraps at the raster's edge and would break derivatives
+fn sample_frames(
+    current: texture_2d<f32>,
+    frame_sampler: sampler,
+    next: texture_2d<f32>,
+    uv: vec2<f32>,
+    frames: RasterFrames,
+) -> vec4<f32> {
+    if any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0)) {
+        return vec4<f32>(0.0);
+    }
+    let a = textureSampleLevel(current, frame_sampler, uv, 0.0);
+    let b = textureSampleLevel(next, frame_sampler, uv, 0.0);
+    return mix(a, b, frames.blend);
+}
@@ -0,0 +1,100 @@
+// Ocean surface model: a filtered wave spectrum, Fresnel reflectance and glint roughness
+// units are km and seconds like the rest of the scene
+
+#import earth::constants::PI
+
+// km/s²
+const GRAVITY: f32 = 0.00981;
+// reflectance of water at normal incidence
+const WATER_F0: f32 = 0.02;
+// the same for bevy's StandardMaterial, which maps reflectance r to 0.16 r²
+const WATER_REFLECTANCE: f32 = 0.35;
+
+// wind and bathymetry settings, see OceanUniform in materials.rs
+struct OceanUniform {
+    // m/s, 10 m above the surface
+    wind_speed: f32,
+    // km a bathymetry value of 1 stands for, 0 when there is no bathymetry
+    bathymetry_depth: f32,
+    // 16-byte alignment
+    _padding: vec2<f32>,
+}
+
+struct WaveSample {
+    normal: vec3<f32>,
+    // slope variance of the waves too small to resolve at this distance
+    unresolved_variance: f32,
+}
+
+// one train of the tiled spectrum: world space direction and wavelength (km)
+// directions are spread over 3d so every point on the sphere has waves crossing it
+const WAVE_COUNT: u32 = 8u;
+const WAVE_DIRECTIONS = array<vec3<f32>, 8>(
+    vec3<f32>(0.80, 0.36, 0.48),
+    vec3<f32>(-0.43, 0.86, 0.27),
+    vec3<f32>(0.12, -0.55, 0.83),
+    vec3<f32>(-0.91, -0.18, 0.37),
+    vec3<f32>(0.57, 0.66, -0.49),
+    vec3<f32>(-0.24, 0.31, -0.92),
+    vec3<f32>(0.69, -0.72, -0.06),
+    vec3<f32>(-0.35, -0.79, -0.50),
+);
+const WAVE_LENGTHS = array<f32, 8>(0.8, 0.53, 0.35, 0.23, 0.15, 0.1, 0.066, 0.044);
+
+// steepness (amplitude * wavenumber) of the waves at a given wind speed
+// a flat Phillips-like spectrum, every band carries about the same slope
+fn wave_steepness(wind_speed: f32) -> f32 {
+    return 0.04 * clamp(wind_speed / 7.0, 0.2, 2.0);
+}
+
+// Cox-Munk mean square slope of the capillary waves below the spectrum
+fn cox_munk_variance(wind_speed: f32) -> f32 {
+    return 0.003 + 0.00512 * wind_speed;
+}
+
+// perturbs the sphere normal with the wave spectrum
+// footprint is the size of a pixel on the surface (km), waves shorter than a few pixels
+// are dropped from the normal and returned as extra roughness instead, so they don't shimmer
+fn wave_normal(position: vec3<f32>, normal: vec3<f32>, time: f32, wind_speed: f32, footprint: f32) -> WaveSample {
+    let steepness = wave_steepness(wind_speed);
+    var gradient = vec3<f32>(0.0);
+    var unresolved = 0.0;
+
+    for (var i = 0u; i < WAVE_COUNT; i++) {
+        let wavelength = WAVE_LENGTHS[i];
+        let direction = WAVE_DIRECTIONS[i];
+        let wavenumber = 2.0 * PI / wavelength;
+        // deep water dispersion
+        let frequency = sqrt(GRAVITY * wavenumber);
+        let phase = dot(direction, position) * wavenumber - frequency * time;
+
+        let resolved = smoothstep(2.0, 4.0, wavelength / max(footprint, 1e-6));
+        gradient += direction * steepness * cos(phase) * resolved;
+        // a sine of slope amplitude s has variance s² / 2
+        unresolved += 0.5 * steepness * steepness * (1.0 - resolved);
+    }
+
+    // only the part of the gradient along the surface tilts the normal
+    let tangential = gradient - normal * dot(gradient, normal);
+    var result: WaveSample;
+    result.normal = normalize(normal - tangential);
+    result.unresolved_variance = unresolved;
+    return result;
+}
+
+fn fresnel_schlick(cos_theta: f32) -> f32 {
+    return WATER_F0 + (1.0 - WATER_F0) * pow(1.0 - clamp(cos_theta, 0.0, 1.0), 5.0);
+}
+
+// bevy's specular lobe is GGX, whose alpha roughly matches the root mean square slope
+fn slope_variance_to_roughness(slope_variance: f32) -> f32 {
+    return sqrt(sqrt(slope_variance));
+}
+
+// colour of the water column, from turquoise shallows to deep blue
+fn water_color(depth: f32) -> vec3<f32> {
+    let shallow = vec3<f32>(0.03, 0.16, 0.17);
+    let deep = vec3<f32>(0.004, 0.016, 0.05);
+    // km over which the shallow colour fades
+    return mix(deep, shallow, exp(-depth / 0.05));
+}
@@ -0,0 +1,112 @@
+// Atmospheric scattering helpers shared by the atmosphere shell and the earth surface
+// the lookup table parameterizations must match scattering.rs
+
+#import bevy_pbr::mesh_view_bindings::{lights, view}
+#import earth::constants::{EARTH_RADIUS, MIE_ANISOTROPY, PI}
+
+struct AtmosphereUniform {
+    sun_direction: vec3<f32>,
+    camera_position: vec3<f32>,
+    // per meter, at sea level
+    rayleigh_coeff: vec3<f32>,
+    mie_coeff: f32,
+    atmosphere_radius: f32,
+    // 16-byte alignment
+    _padding: f32,
+}
+
+// distances along the ray to the near and far intersections with a sphere at the origin
+// returns a negative far distance if the ray misses
+fn ray_sphere(origin: vec3<f32>, dir: vec3<f32>, radius: f32) -> vec2<f32> {
+    let b = dot(origin, dir);
+    let c = dot(origin, origin) - radius * radius;
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return vec2<f32>(1.0, -1.0);
+    }
+    let root = sqrt(discriminant);
+    return vec2<f32>(-b - root, -b + root);
+}
+
+fn rayleigh_phase(cos_theta: f32) -> f32 {
+    return 3.0 / (16.0 * PI) * (1.0 + cos_theta * cos_theta);
+}
+
+// Cornette-Shanks approximation of the mie phase function
+fn mie_phase(cos_theta: f32) -> f32 {
+    let g = MIE_ANISOTROPY;
+    let g2 = g * g;
+    let numerator = 3.0 * (1.0 - g2) * (1.0 + cos_theta * cos_theta);
+    let denominator = 8.0 * PI * (2.0 + g2) * pow(1.0 + g2 - 2.0 * g * cos_theta, 1.5);
+    return numerator / denominator;
+}
+
+fn height_to_unit(radius: f32, atmosphere_radius: f32) -> f32 {
+    return sqrt(clamp((radius - EARTH_RADIUS) / (atmosphere_radius - EARTH_RADIUS), 0.0, 1.0));
+}
+
+// cosine of the view zenith angle at which a ray grazes the ground
+fn horizon(radius: f32) -> f32 {
+    let ratio = EARTH_RADIUS / max(radius, EARTH_RADIUS);
+    return -sqrt(max(1.0 - ratio * ratio, 0.0));
+}
+
+// half the texture on each side of the horizon
+fn view_to_unit(radius: f32, mu: f32) -> f32 {
+    let h = horizon(radius);
+    if mu > h {
+        return 0.5 + 0.5 * pow(max((mu - h) / (1.0 - h), 0.0), 0.2);
+    }
+    return 0.5 - 0.5 * pow(max((h - mu) / (1.0 + h), 0.0), 0.2);
+}
+
+// most of the texture is spent around sunrise and sunset
+fn sun_to_unit(mu_s: f32) -> f32 {
+    return clamp((1.0 - exp(-2.8 * mu_s - 0.8)) / (1.0 - exp(-3.6)), 0.0, 1.0);
+}
+
+// coordinates in the transmittance table, only meaningful above the horizon
+fn transmittance_coords(radius: f32, mu: f32, atmosphere_radius: f32) -> vec2<f32> {
+    let h = horizon(radius);
+    let view = sqrt(clamp((mu - h) / (1.0 - h), 0.0, 1.0));
+    return vec2<f32>(view, height_to_unit(radius, atmosphere_radius));
+}
+
+// coordinates in the single and multiple scattering tables
+fn scattering_coords(radius: f32, mu: f32, mu_s: f32, atmosphere_radius: f32) -> vec3<f32> {
+    return vec3<f32>(view_to_unit(radius, mu), sun_to_unit(mu_s), height_to_unit(radius, atmosphere_radius));
+}
+
+// light scattered towards the viewer, from single and multiple scattering table samples
+// nu is the cosine of the angle between the view ray and the sun
+// the tables are integrated for a sun irradiance of 1, a white surface facing that sun reflects 1 / pi,
+// so the result is scaled by pi to be in the units of sun_radiance_scale
+fn inscattered_light(single: vec4<f32>, multiple: vec3<f32>, nu: f32, atmosphere: AtmosphereUniform) -> vec3<f32> {
+    // only mie's red channel is stored, the others follow rayleigh's (Bruneton 2008)
+    let rayleigh = single.rgb;
+    let mie = rayleigh * single.a / max(rayleigh.r, 1e-9)
+        * (atmosphere.rayleigh_coeff.r / atmosphere.rayleigh_coeff);
+
+    return PI * (
+        rayleigh * rayleigh_phase(nu)
+        + mie * mie_phase(nu)
+        + multiple
+    );
+}
+
+// radiance in units of the sun's light, where a white surface facing the sun is 1, to bevy's units
+// bevy's lighting reflects illuminance / pi off such a surface, and scales everything by the exposure
+fn sun_radiance_scale() -> vec3<f32> {
+    if lights.n_directional_lights == 0u {
+        return vec3<f32>(view.exposure);
+    }
+    return lights.directional_lights[0].color.rgb * view.exposure / PI;
+}
+
+// towards the sun, the scene's only directional light
+fn sun_direction() -> vec3<f32> {
+    if lights.n_directional_lights == 0u {
+        return vec3<f32>(0.0, 1.0, 0.0);
+    }
+    return lights.directional_lights[0].direction_to_light;
+}
@@ -0,0 +1,58 @@
+// The heightmap the globe is displaced by, shared by the vertex shader and the terrain analysis views
+
+#import earth::constants::PI
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(131) var heightmap: texture_2d<f32>;
+// per-frame values of the surface, SurfaceUniform in materials.rs
+@group(#{MATERIAL_BIND_GROUP}) @binding(132) var<uniform> surface: SurfaceUniform;
+
+struct SurfaceUniform {
+    // radians the clouds have drifted east of the surface
+    cloud_drift: f32,
+    cloud_opacity: f32,
+    // monthly textures blend from day_texture into next_day_texture
+    day_blend: f32,
+    // km of height a heightmap value of 1 stands for
+    displacement_scale: f32,
+    // see DebugRenderMode in debug.rs
+    debug_mode: u32,
+    show_land_cover: u32,
+    _padding: vec2<f32>,
+}
+
+// height (0-1) in the direction of a point, bilinear between texel centres and wrapping in longitude
+// must stay in step with `sample_height` in mesh.rs, which places picking and satellites on the terrain
+fn sample_height(direction: vec3<f32>) -> f32 {
+    let longitude = atan2(direction.x, direction.z);
+    let latitude = asin(clamp(direction.y, -1.0, 1.0));
+    let u = longitude / (2.0 * PI) + 0.5;
+    let v = 0.5 - latitude / PI;
+    return sample_height_uv(vec2<f32>(u, v));
+}
+
+fn sample_height_uv(uv: vec2<f32>) -> f32 {
+    let size = vec2<i32>(textureDimensions(heightmap));
+    let texel = vec2<f32>(uv.x * f32(size.x) - 0.5, uv.y * f32(size.y) - 0.5);
+    let base = floor(texel);
+    let t = texel - base;
+    let x0 = i32(base.x);
+    let y0 = i32(base.y);
+
+    let h00 = height_texel(x0, y0, size);
+    let h10 = height_texel(x0 + 1, y0, size);
+    let h01 = height_texel(x0, y0 + 1, size);
+    let h11 = height_texel(x0 + 1, y0 + 1, size);
+    let top = h00 + (h10 - h00) * t.x;
+    let bottom = h01 + (h11 - h01) * t.x;
+    return top + (bottom - top) * t.y;
+}
+
+fn height_texel(x: i32, y: i32, size: vec2<i32>) -> f32 {
+    let wrapped = vec2<i32>(((x % size.x) + size.x) % size.x, clamp(y, 0, size.y - 1));
+    return textureLoad(heightmap, wrapped, 0).r;
+}
+
+// size of one heightmap texel, in uv
+fn heightmap_texel_size() -> vec2<f32> {
+    return 1.0 / vec2<f32>(textureDimensions(heightmap));
+}
@@ -1,30 +1,234 @@
 // Earth measurement (in km)
+// also available to the shaders, see plugins/earth/constants.rs
 pub const EARTH_RADIUS: f32 = 6378.0;
 pub const ATMOSPHERE_RADIUS: f32 = 7000.0;
 pub const CLOUD_RADIUS: f32 = 6478.0;
-// maximum terrain height
+// km the terrain is raised at a heightmap value of 1, exaggerated for display
+// starting value of EarthShadingSettings::displacement_scale, which picking follows too
+// the normal and horizon maps are baked at this value, earth.wgsl rescales them to the current one
 pub const DISPLACEMENT_SCALE: f32 = 80.0;
 
 // Atospheric scattering parameters
 // based on values from https://www.scratchapixel.com/lessons/procedural-generation-virtual-worlds/simulating-sky/simulating-colors-of-the-sky.html
 pub const RAYLEIGH_COEFF: [f32; 3] = [5.8e-6, 13.5e-6, 33.1e-6];  // RGB wavelengths
-pub const MIE_COEFF: f32 = 210.0e-5;
-pub const SUN_INTENSITY: f32 = 10.0;
+pub const MIE_COEFF: f32 = 21.0e-6; // per meter, at sea level
+// precomputed scattering tables, recomputed whenever the parameters above change
+pub const ATMOSPHERE_LUT_CACHE_PATH: &str = "assets/cache/atmosphere_luts.bin";
+// mie scattering is strongly forward, towards the sun, only used when drawing
+pub const MIE_ANISOTROPY: f32 = 0.76;
+
+// Camera and sun
+// lux, sunlight above the atmosphere, the scattering tables take care of what the air absorbs
+pub const SUN_ILLUMINANCE: f32 = 130_000.0;
+// exposure for a sunlit scene, auto exposure corrects from here
+pub const CAMERA_EV100: f32 = 15.0;
+// stops of scene brightness auto exposure meters over, darker pixels such as empty space are ignored
+pub const AUTO_EXPOSURE_RANGE: (f32, f32) = (-6.0, 6.0);
+// share of the image bloom scatters, so city lights and the sun glint glow
+pub const BLOOM_INTENSITY: f32 = 0.15;
 
 // Rotation speeds
 // radians, dont touch
 pub const EARTH_ROTATION_SPEED: f32 = 0.00005;
 
+// Clouds
+pub const CLOUD_OPACITY: f32 = 0.9;
+// radians per day the cloud layer drifts east relative to the surface
+pub const CLOUD_DRIFT_SPEED: f64 = 0.3;
+// optional time series of cloud frames, replaces the static texture and its drift when present
+// see plugins/earth/sequence.rs for the manifest format
+pub const CLOUD_FRAMES_MANIFEST: &str = "assets/data/clouds.txt";
+// frames kept loaded ahead of the clock
+pub const CLOUD_FRAMES_PRELOAD: usize = 3;
+
+// Surface lighting
+// the globe is lit by bevy's pbr pipeline, water roughness comes from the wind and specular map
+pub const LAND_ROUGHNESS: f32 = 0.9;
+// km, the sun's shadow cascades reach this far from the camera
+pub const SHADOW_MAX_DISTANCE: f32 = 40_000.0;
+pub const SHADOW_FIRST_CASCADE_FAR_BOUND: f32 = 2_000.0;
+// km, scene units are large so the default bias is far too small
+pub const SHADOW_DEPTH_BIAS: f32 = 1.0;
+
+// Surface shading
+// starting values of EarthShadingSettings, which can be changed while running
+// how far the normal map tilts the sphere normal
+pub const NORMAL_STRENGTH: f32 = 0.32;
+// half width of the day/night transition, in cosine of the sun angle
+pub const TERMINATOR_WIDTH: f32 = 0.1;
+// light left on the night side and the city lights, in units of the sun's light
+pub const NIGHT_AMBIENT: f32 = 0.02;
+pub const CITY_LIGHTS_STRENGTH: f32 = 1.5;
+// 0 keeps the day colours on the night side, 1 makes them grey
+pub const NIGHT_DESATURATION: f32 = 0.8;
+// range the specular map scales the wind's water roughness over, brighter values are calmer
+pub const CALM_WATER_ROUGHNESS: f32 = 0.5;
+pub const ROUGH_WATER_ROUGHNESS: f32 = 1.5;
+// fraction of the sunlight the thickest clouds block
+pub const CLOUD_SHADOW_STRENGTH: f32 = 0.6;
+// fraction of the sunlight blocked where the relief hides the sun, see the horizon map below
+pub const TERRAIN_SHADOW_STRENGTH: f32 = 0.85;
+
+// Ocean
+// m/s, sets the size of the waves and how wide the sun glint spreads
+pub const OCEAN_WIND_SPEED: f32 = 7.0;
+// optional bathymetry, greyscale from sea level (0) to BATHYMETRY_MAX_DEPTH (1)
+// without it the day texture's ocean colour is used
+pub const EARTH_BATHYMETRY_TEXTURE: Option<&str> = None;
+pub const BATHYMETRY_MAX_DEPTH: f32 = 11.0;
+
+// Terrain shadows
+// horizon map the relief's shadows are looked up in, baked from the height map in the background at startup
+pub const HORIZON_MAP_SIZE: (u32, u32) = (1024, 512);
+// km searched for the horizon in each direction, and the samples along it
+pub const HORIZON_DISTANCE: f32 = 300.0;
+pub const HORIZON_STEPS: u32 = 16;
+
+// Globe mesh
+// each cube face is split into 2x2 chunks of this many vertices per side
+pub const EARTH_MESH_RESOLUTION: u32 = 128;
+
 // Normal map generation config
 // change this if you want the program to generate a new normal map every time it compiles
+// the map is generated and saved whenever the file is missing
 pub const USE_SAVED_NORMAL_MAP: bool = true;
-pub const SAVED_NORMAL_MAP_PATH: &str = "textures/normal.png";
+// versioned with the generator, maps from an older one are encoded differently
+pub const SAVED_NORMAL_MAP_PATH: &str = "textures/normal_v2.png";
 
 // Asset paths
 pub const EARTH_DIFFUSE_TEXTURE: &str = "textures/diffuse.tif";
+// optional monthly day textures, `{month}` is replaced by 01 to 12
+// blended through the year by the simulation clock, the single texture above is shown until they load
+pub const EARTH_MONTHLY_DIFFUSE_TEXTURES: Option<&str> = None;
 pub const EARTH_NIGHT_TEXTURE: &str = "textures/night.tif";
 pub const EARTH_CLOUDS_TEXTURE: &str = "textures/clouds.tif";
 pub const EARTH_OCEAN_MASK_TEXTURE: &str = "textures/ocean_mask.png";
 pub const EARTH_SPECULAR_TEXTURE: &str = "textures/specular.tif";
 
-pub const EARTH_DISPLACEMENT_TEXTURE: &str = "textures/topography.png";
\ No newline at end of file
+pub const EARTH_DISPLACEMENT_TEXTURE: &str = "textures/topography.png";
+
+// Terrain analysis
+// real elevation (km) of a heightmap value of 1, DISPLACEMENT_SCALE exaggerates it for display
+pub const TERRAIN_MAX_ELEVATION: f32 = 8.848;
+// hypsometric tint, elevation in metres and sRGB colour, above the last stop stays its colour
+pub const ELEVATION_RAMP: &[(f32, [u8; 3])] = &[
+    (0.0, [38, 115, 77]),
+    (300.0, [112, 163, 92]),
+    (1000.0, [222, 214, 143]),
+    (2000.0, [191, 140, 82]),
+    (3500.0, [140, 110, 90]),
+    (5000.0, [245, 245, 245]),
+];
+// slope in degrees and sRGB colour
+pub const SLOPE_RAMP: &[(f32, [u8; 3])] = &[
+    (0.0, [26, 150, 65]),
+    (5.0, [166, 217, 106]),
+    (15.0, [254, 224, 139]),
+    (30.0, [244, 109, 67]),
+    (45.0, [165, 0, 38]),
+];
+
+// Terrain detail
+// tiled rock, grass, snow and sand textures blended over the day texture close to the ground
+// None keeps the day texture alone
+pub const TERRAIN_DETAIL_TEXTURES: Option<[&str; 4]> = None;
+// km of ground one repeat of a detail texture covers
+pub const DETAIL_TILE_SIZE: f32 = 2.0;
+// camera distance (km) the detail starts fading in at, and is fully in at
+pub const DETAIL_FADE_DISTANCE: (f32, f32) = (300.0, 30.0);
+// share of the day texture's colour the detail replaces when fully in
+pub const DETAIL_STRENGTH: f32 = 0.6;
+// metres, snow line at the equator, it drops to sea level at the poles
+pub const SNOW_LINE: f32 = 4500.0;
+// degrees, normal map slopes steeper than this show rock
+pub const ROCK_SLOPE: f32 = 25.0;
+// degrees, the desert belts either side of the equator are centred on this latitude
+pub const DESERT_LATITUDE: f32 = 25.0;
+
+// Land cover
+// optional categorical raster, a class code per pixel in the red channel, e.g. ESA WorldCover or MODIS
+// shown instead of the day texture with F5
+pub const EARTH_LAND_COVER_TEXTURE: Option<&str> = None;
+// class code, name, sRGB colour and perceptual roughness, ESA WorldCover's classes and legend colours
+// codes not listed keep the day texture
+pub const LAND_COVER_CLASSES: &[(u8, &str, [u8; 3], f32)] = &[
+    (10, "Tree cover", [0, 100, 0], 0.9),
+    (20, "Shrubland", [255, 187, 34], 0.9),
+    (30, "Grassland", [255, 255, 76], 0.85),
+    (40, "Cropland", [240, 150, 255], 0.85),
+    (50, "Built-up", [250, 0, 0], 0.6),
+    (60, "Bare / sparse vegetation", [180, 180, 180], 0.95),
+    (70, "Snow and ice", [240, 240, 240], 0.3),
+    (80, "Permanent water bodies", [0, 100, 200], 0.1),
+    (90, "Herbaceous wetland", [0, 150, 160], 0.5),
+    (95, "Mangroves", [0, 207, 117], 0.7),
+    (100, "Moss and lichen", [250, 230, 160], 0.9),
+];
+
+// Aurora
+// geomagnetic Kp index, 0 (quiet) to 9 (extreme storm), used when there is no series
+pub const AURORA_KP: f32 = 3.0;
+// optional Kp time series, replaces the value above, see plugins/aurora/kp.rs for the format
+pub const AURORA_KP_SERIES: &str = "assets/data/kp.txt";
+// northern geomagnetic pole of the dipole field (degrees), the southern one is opposite
+pub const GEOMAGNETIC_NORTH_POLE: (f32, f32) = (80.8, -72.7);
+// km above the surface the emission starts at and fades out below
+pub const AURORA_ALTITUDES: (f32, f32) = (90.0, 350.0);
+// brightness at Kp 9 looking straight up through the oval, in units of the sun's light
+pub const AURORA_INTENSITY: f32 = 0.5;
+
+// Satellites
+// two-line element sets, e.g. downloaded from https://celestrak.org
+pub const SATELLITE_TLE_PATH: &str = "assets/data/satellites.tle";
+pub const SATELLITE_MARKER_RADIUS: f32 = 60.0;
+// how many orbits of ground track to draw behind and ahead of each satellite
+pub const GROUND_TRACK_ORBITS: f64 = 1.0;
+// minutes between track samples, and between track recalculations
+pub const TRACK_STEP: f64 = 0.5;
+pub const TRACK_REFRESH_INTERVAL: f64 = 1.0;
+
+// Ground stations
+// name, latitude, longitude (degrees), altitude (km)
+pub const GROUND_STATIONS: &[(&str, f32, f32, f32)] = &[
+    ("Svalbard", 78.23, 15.39, 0.5),
+    ("Kiruna", 67.86, 20.96, 0.4),
+];
+// degrees above the horizon
+pub const STATION_MIN_ELEVATION: f32 = 10.0;
+// days of passes to predict ahead of the clock
+pub const PASS_PREDICTION_WINDOW: f64 = 1.0;
+// minutes between coarse visibility checks, must be shorter than the shortest pass
+pub const PASS_SEARCH_STEP: f64 = 0.5;
+// altitude the visibility cones are drawn up to (km)
+pub const STATION_CONE_ALTITUDE: f32 = 1000.0;
+
+// Sensors
+// swath widths (km) of nadir-pointing pushbroom sensors, by satellite name as it appears in the TLE file
+pub const SENSOR_SWATHS: &[(&str, f32)] = &[
+    ("SENTINEL-2A", 290.0),
+    ("SENTINEL-2B", 290.0),
+    ("LANDSAT 8", 185.0),
+    ("LANDSAT 9", 185.0),
+];
+// half angles (degrees from nadir) of nadir-pointing conical sensors, by satellite name
+pub const SENSOR_HALF_ANGLES: &[(&str, f32)] = &[("ISS (ZARYA)", 30.0)];
+// seconds of strip drawn behind a swath sensor
+pub const SWATH_TRAIL: f32 = 60.0;
+// days of coverage accumulated from the start of the simulation
+pub const COVERAGE_WINDOW: f64 = 1.0;
+// cells in longitude and latitude
+pub const COVERAGE_RASTER_SIZE: (usize, usize) = (720, 360);
+// minutes between footprint samples when accumulating coverage
+pub const COVERAGE_STEP: f64 = 0.5;
+
+// Solar system view
+// km, the camera's far plane has to reach past the outer planets
+pub const CAMERA_FAR_PLANE: f32 = 1.0e10;
+// distance (AU) the heliocentric camera keeps from the sun
+pub const SOLAR_SYSTEM_CAMERA_DISTANCE: f64 = 4.0;
+// seconds to zoom between the globe and the heliocentric view
+pub const VIEW_TRANSITION_TIME: f32 = 3.0;
+// radians, bodies are enlarged in the heliocentric view so they never shrink below this
+pub const MIN_BODY_ANGULAR_SIZE: f32 = 0.004;
+pub const SHOW_MOON: bool = true;
+pub const SHOW_PLANETS: bool = true;
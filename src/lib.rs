@@ -0,0 +1,15 @@
+// The globe's plugins as a library, so their calculations can be used outside the app
+// e.g. `earth::daylight(&LatLon::from_degrees(52.5, 13.4), julian_date)`
+
+pub mod config;
+pub mod plugins;
+#[cfg(test)]
+mod shader_validation;
+
+pub use plugins::earth::uv::LatLon;
+pub use plugins::sun::daylight::{Daylight, SunEvents, daylight};
+
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct Sun;
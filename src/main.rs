@@ -1,54 +1,42 @@
 use bevy::{
-    asset::RenderAssetUsages,
-    color::palettes::basic::SILVER,
-    prelude::*,
-    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    light::CascadeShadowConfigBuilder, picking::mesh_picking::MeshPickingPlugin, prelude::*,
+    render::view::Hdr,
 };
-use std::f32::consts::PI;
 
-pub mod config;
-
-mod plugins;
-use plugins::earth::EarthPlugin;
-
-#[derive(Component)]
-pub struct Sun;
+use earth::plugins::aurora::AuroraPlugin;
+use earth::plugins::camera::CameraSettingsPlugin;
+use earth::plugins::clock::ClockPlugin;
+use earth::plugins::earth::EarthPlugin;
+use earth::plugins::satellites::SatellitePlugin;
+use earth::plugins::solar_system::SolarSystemPlugin;
+use earth::plugins::stations::StationPlugin;
+use earth::plugins::sun::SunPlugin;
+use earth::plugins::ui::UiPlugin;
+use earth::{Sun, config};
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugins((
+            MeshPickingPlugin,
+            CameraSettingsPlugin,
+            ClockPlugin,
+            SunPlugin,
+            EarthPlugin,
+            AuroraPlugin,
+            SatellitePlugin,
+            StationPlugin,
+            SolarSystemPlugin,
+            UiPlugin,
+        ))
         .add_systems(Startup, setup)
-        .add_systems(Update, rotate)
         .run();
 }
 
-#[derive(Component)]
-struct Earth;
-
 // const SHAPE_X_EXTENT: f32 = 14.0;
 // const Z_EXTENT: f32 = 5.0;
 
-fn setup(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut images: ResMut<Assets<Image>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) {
-    let earth_material = materials.add(StandardMaterial {
-        base_color_texture: Some(images.add(uv_debug_texture())),
-        ..default()
-    });
-
-    let shape = meshes.add(Sphere::new(4.0).mesh().uv(32, 18));
-
-    commands.spawn((
-        Mesh3d(shape),
-        MeshMaterial3d(earth_material.clone()),
-        Transform::default()
-            .with_scale(Vec3::new(1., 1., 1.05))
-            .with_rotation(Quat::from_rotation_x(-PI / 1.5859)),
-        Earth,
-    ));
+fn setup(mut commands: Commands) {
     // commands.spawn((
     //     Mesh3d(shape),
     //     MeshMaterial3d(earth_material.clone()),
@@ -56,53 +44,34 @@ fn setup(
     //     Earth,
     // ));
 
+    // oriented by the sun plugin, lights the globe through bevy's pbr pipeline
+    // its illuminance comes from CameraSettings
     commands.spawn((
-        PointLight {
+        DirectionalLight {
             shadows_enabled: true,
-            intensity: 10_000_000.,
-            range: 100.0,
-            shadow_depth_bias: 0.2,
+            shadow_depth_bias: config::SHADOW_DEPTH_BIAS,
             ..default()
         },
-        Transform::from_xyz(0.0, 16.0, 8.0),
+        CascadeShadowConfigBuilder {
+            first_cascade_far_bound: config::SHADOW_FIRST_CASCADE_FAR_BOUND,
+            maximum_distance: config::SHADOW_MAX_DISTANCE,
+            ..default()
+        }
+        .build(),
+        Transform::default(),
+        Sun,
     ));
 
+    // scene units are km, so the far plane has to reach past the planets in the solar system view
+    // hdr so the pbr surface and the custom atmosphere materials are tonemapped together
+    // exposure, tonemapping and bloom come from CameraSettings
     commands.spawn((
         Camera3d::default(),
-        Transform::from_xyz(0.0, 7., 14.0).looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+        Hdr,
+        Projection::from(PerspectiveProjection {
+            far: config::CAMERA_FAR_PLANE,
+            ..default()
+        }),
+        Transform::from_xyz(0.0, 0.0, 4.0 * config::EARTH_RADIUS).looking_at(Vec3::ZERO, Vec3::Y),
     ));
 }
-
-fn uv_debug_texture() -> Image {
-    const TEXTURE_SIZE: usize = 8;
-
-    let mut palette: [u8; 32] = [
-        255, 102, 159, 255, 255, 159, 102, 255, 236, 255, 102, 255, 121, 255, 102, 255, 102, 255,
-        198, 255, 102, 198, 255, 255, 121, 102, 255, 255, 236, 102, 255, 255,
-    ];
-
-    let mut texture_data = [0; TEXTURE_SIZE * TEXTURE_SIZE * 4];
-    for y in 0..TEXTURE_SIZE {
-        let offset = TEXTURE_SIZE * y * 4;
-        texture_data[offset..(offset + TEXTURE_SIZE * 4)].copy_from_slice(&palette);
-        palette.rotate_right(4);
-    }
-
-    Image::new_fill(
-        Extent3d {
-            width: TEXTURE_SIZE as u32,
-            height: TEXTURE_SIZE as u32,
-            depth_or_array_layers: 1,
-        },
-        TextureDimension::D2,
-        &texture_data,
-        TextureFormat::Rgba8UnormSrgb,
-        RenderAssetUsages::RENDER_WORLD,
-    )
-}
-
-fn rotate(mut query: Query<&mut Transform, With<Earth>>, time: Res<Time>) {
-    for mut transform in &mut query {
-        transform.rotate_y(time.delta_secs() / 2.0);
-    }
-}
@@ -0,0 +1,158 @@
+use std::fmt;
+
+use crate::plugins::clock::series::{bracket, series_lines};
+
+// Time series of the geomagnetic Kp index
+// one value per line, `#` starts a comment:
+//   <ISO 8601 UTC timestamp> <Kp>        e.g. 2024-05-10T18:00:00Z 8.67
+// Kp may also be written in thirds, as in 5-, 5o and 5+
+// three-hourly values are stamped with the start of their interval
+
+pub const MAX_KP: f32 = 9.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KpSeriesError {
+    // line number
+    InvalidTimestamp(usize),
+    InvalidKp(usize),
+}
+
+impl fmt::Display for KpSeriesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KpSeriesError::InvalidTimestamp(line) => {
+                write!(f, "line {line} has an invalid timestamp")
+            }
+            KpSeriesError::InvalidKp(line) => write!(f, "line {line} has no Kp between 0 and 9"),
+        }
+    }
+}
+
+impl std::error::Error for KpSeriesError {}
+
+#[derive(Clone, Debug, Default)]
+pub struct KpSeries {
+    // julian date (UTC) and Kp, in time order
+    samples: Vec<(f64, f32)>,
+}
+
+impl KpSeries {
+    /// Parses a series, lines that fail are skipped and reported
+    pub fn parse(text: &str) -> (Self, Vec<KpSeriesError>) {
+        let mut samples = Vec::new();
+        let mut errors = Vec::new();
+
+        for mut line in series_lines(text) {
+            let Some(time) = line.time() else {
+                errors.push(KpSeriesError::InvalidTimestamp(line.number));
+                continue;
+            };
+            let Some(kp) = line.fields.next().and_then(parse_kp) else {
+                errors.push(KpSeriesError::InvalidKp(line.number));
+                continue;
+            };
+            samples.push((time, kp));
+        }
+
+        samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+        (KpSeries { samples }, errors)
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Kp at a time, interpolated between samples so the aurora doesn't jump every three hours
+    /// outside the series the first or last value is held
+    pub fn kp_at(&self, julian_date: f64) -> Option<f32> {
+        let (before, after, t) = bracket(&self.samples, julian_date, |&(time, _)| time)?;
+        let (from, to) = (self.samples[before].1, self.samples[after].1);
+        Some(from + (to - from) * t as f32)
+    }
+}
+
+// decimal, or a whole number followed by -, o or + for thirds
+fn parse_kp(text: &str) -> Option<f32> {
+    let third = match text.chars().last()? {
+        '-' => Some(-1.0 / 3.0),
+        'o' => Some(0.0),
+        '+' => Some(1.0 / 3.0),
+        _ => None,
+    };
+    let kp = match third {
+        Some(third) => text[..text.len() - 1].parse::<f32>().ok()? + third,
+        None => text.parse::<f32>().ok()?,
+    };
+    (0.0..=MAX_KP).contains(&kp).then_some(kp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::clock::julian::CalendarDate;
+
+    #[test]
+    fn parses_thirds() {
+        let cases = [
+            ("5-", 5.0 - 1.0 / 3.0),
+            ("5o", 5.0),
+            ("5+", 5.0 + 1.0 / 3.0),
+            ("0o", 0.0),
+            ("9-", 9.0 - 1.0 / 3.0),
+            ("8.67", 8.67),
+        ];
+        for (text, expected) in cases {
+            let kp = parse_kp(text).unwrap();
+            assert!((kp - expected).abs() < 1e-6, "{text:?} gave {kp}");
+        }
+    }
+
+    #[test]
+    fn rejects_values_outside_the_scale() {
+        for text in ["9+", "0-", "-1", "9.5", "x", "+", ""] {
+            assert_eq!(parse_kp(text), None, "{text:?}");
+        }
+
+        let text = "\
+# storm
+2024-05-10T12:00:00Z 5o
+2024-05-10T15:00:00Z 9+
+2024-05-10T18 8
+2024-05-10T21:00:00Z
+2024-05-11T00:00:00Z 7- # fading
+";
+        let (series, errors) = KpSeries::parse(text);
+        assert_eq!(series.len(), 2);
+        assert_eq!(
+            errors,
+            vec![
+                KpSeriesError::InvalidKp(3),
+                KpSeriesError::InvalidTimestamp(4),
+                KpSeriesError::InvalidKp(5),
+            ]
+        );
+    }
+
+    #[test]
+    fn interpolates_and_holds_the_ends() {
+        let (series, errors) = KpSeries::parse("2024-05-10T03:00:00Z 6\n2024-05-10T00:00:00Z 3\n");
+        assert!(errors.is_empty());
+        let start = CalendarDate::parse_iso8601("2024-05-10T00:00:00Z")
+            .unwrap()
+            .to_julian_date();
+        let hour = 1.0 / 24.0;
+
+        // samples are sorted by time whatever the order of the file
+        assert_eq!(series.kp_at(start), Some(3.0));
+        assert!((series.kp_at(start + hour).unwrap() - 4.0).abs() < 1e-4);
+        assert_eq!(series.kp_at(start + 3.0 * hour), Some(6.0));
+        assert_eq!(series.kp_at(start - 1.0), Some(3.0));
+        assert_eq!(series.kp_at(start + 1.0), Some(6.0));
+
+        assert_eq!(KpSeries::default().kp_at(start), None);
+    }
+}
@@ -0,0 +1,248 @@
+use bevy::{
+    light::NotShadowCaster,
+    pbr::{MaterialPipeline, MaterialPipelineKey},
+    prelude::*,
+    render::render_resource::*,
+    shader::ShaderRef,
+};
+
+pub mod kp;
+
+use crate::Sun;
+use crate::config::*;
+use crate::plugins::clock::SimulationClock;
+use crate::plugins::earth::uv::LatLon;
+use kp::{KpSeries, MAX_KP};
+
+// Auroral ovals around the geomagnetic poles, ray marched through a shell above the globe
+// they grow towards the equator and brighten with the Kp index and only glow where the sky is dark
+// F6 shows or hides them, [ and ] set the Kp by hand
+
+const TOGGLE_KEY: KeyCode = KeyCode::F6;
+const LOWER_KP_KEY: KeyCode = KeyCode::BracketLeft;
+const RAISE_KP_KEY: KeyCode = KeyCode::BracketRight;
+// Kp change per key press
+const KP_STEP: f32 = 1.0;
+
+pub struct AuroraPlugin;
+
+impl Plugin for AuroraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<AuroraMaterial>::default())
+            .init_resource::<Aurora>()
+            .add_systems(Startup, spawn_aurora)
+            .add_systems(Update, (control_aurora, update_aurora).chain());
+    }
+}
+
+/// Where the Kp index comes from
+#[derive(Clone, Debug)]
+pub enum KpSource {
+    Fixed(f32),
+    // follows the simulation clock
+    Series(KpSeries),
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct Aurora {
+    pub visible: bool,
+    pub source: KpSource,
+    // last value taken from the source
+    pub kp: f32,
+}
+
+impl Default for Aurora {
+    fn default() -> Self {
+        Aurora {
+            visible: true,
+            source: load_kp_series().map_or(KpSource::Fixed(AURORA_KP), KpSource::Series),
+            kp: AURORA_KP,
+        }
+    }
+}
+
+impl Aurora {
+    /// Geomagnetic latitude of the middle of the oval and its half width, both in degrees
+    /// the equatorward edge moves about 2 degrees per Kp, from 66 degrees when quiet
+    pub fn oval(kp: f32) -> (f32, f32) {
+        let half_width = 2.0 + 0.5 * kp;
+        let edge = 66.0 - 2.0 * kp;
+        (edge + half_width, half_width)
+    }
+}
+
+/// Reads the optional Kp series, the fixed AURORA_KP is used without one
+fn load_kp_series() -> Option<KpSeries> {
+    let text = std::fs::read_to_string(AURORA_KP_SERIES).ok()?;
+    let (series, errors) = KpSeries::parse(&text);
+    for error in errors {
+        warn!("skipping Kp value in {AURORA_KP_SERIES}: {error}");
+    }
+    if series.is_empty() {
+        warn!("{AURORA_KP_SERIES} has no Kp values, using {AURORA_KP}");
+        return None;
+    }
+    info!("aurora follows {} Kp values", series.len());
+    Some(series)
+}
+
+#[derive(ShaderType, Copy, Clone, Debug, Reflect)]
+#[repr(C)]
+pub struct AuroraUniform {
+    // unit vector towards the northern geomagnetic pole
+    pub pole: Vec3,
+    // geomagnetic latitude of the middle of the ovals, radians
+    pub oval_latitude: f32,
+    pub sun_direction: Vec3,
+    // radians
+    pub oval_half_width: f32,
+    // km from the centre of the earth
+    pub bottom_radius: f32,
+    pub top_radius: f32,
+    // emission looking straight up through the middle of the oval, in units of the sun's light
+    pub intensity: f32,
+    pub _padding: f32,
+}
+
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct AuroraMaterial {
+    #[uniform(0)]
+    pub aurora: AuroraUniform,
+}
+
+impl Material for AuroraMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/aurora.wgsl".into()
+    }
+
+    // emission only, it never hides what is behind
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Add
+    }
+
+    // the shell is seen from the inside too, the shader marches from whichever side faces the camera
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &bevy::mesh::MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        descriptor.primitive.cull_mode = None;
+        Ok(())
+    }
+}
+
+/// The shell the aurora is drawn on
+#[derive(Component)]
+pub struct AuroraShell;
+
+fn spawn_aurora(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<AuroraMaterial>>,
+) {
+    let (bottom, top) = AURORA_ALTITUDES;
+    let (latitude, longitude) = GEOMAGNETIC_NORTH_POLE;
+    let material = materials.add(AuroraMaterial {
+        aurora: AuroraUniform {
+            pole: LatLon::from_degrees(latitude, longitude).to_unit_vector(),
+            oval_latitude: 0.0,
+            sun_direction: Vec3::Y,
+            oval_half_width: 0.0,
+            bottom_radius: EARTH_RADIUS + bottom,
+            top_radius: EARTH_RADIUS + top,
+            intensity: 0.0,
+            _padding: 0.0,
+        },
+    });
+
+    // the scene is earth-fixed, so the shell stays at the origin with the globe
+    commands.spawn((
+        Mesh3d(meshes.add(Sphere::new(EARTH_RADIUS + top).mesh().ico(6).unwrap())),
+        MeshMaterial3d(material),
+        Transform::default(),
+        Pickable::IGNORE,
+        NotShadowCaster,
+        AuroraShell,
+    ));
+}
+
+fn control_aurora(keys: Res<ButtonInput<KeyCode>>, mut aurora: ResMut<Aurora>) {
+    if keys.just_pressed(TOGGLE_KEY) {
+        aurora.visible = !aurora.visible;
+        info!("aurora: {}", if aurora.visible { "on" } else { "off" });
+    }
+
+    // setting the Kp by hand leaves the series
+    let step = match (
+        keys.just_pressed(LOWER_KP_KEY),
+        keys.just_pressed(RAISE_KP_KEY),
+    ) {
+        (true, false) => -KP_STEP,
+        (false, true) => KP_STEP,
+        _ => return,
+    };
+    let kp = (aurora.kp + step).clamp(0.0, MAX_KP);
+    aurora.source = KpSource::Fixed(kp);
+    info!("Kp {kp:.1}");
+}
+
+/// Follows the Kp and the sun
+fn update_aurora(
+    mut aurora: ResMut<Aurora>,
+    clock: Res<SimulationClock>,
+    mut materials: ResMut<Assets<AuroraMaterial>>,
+    mut shells: Query<(&MeshMaterial3d<AuroraMaterial>, &mut Visibility), With<AuroraShell>>,
+    suns: Query<&GlobalTransform, With<Sun>>,
+) {
+    let kp = match &aurora.source {
+        KpSource::Fixed(kp) => *kp,
+        KpSource::Series(series) => series.kp_at(clock.julian_date).unwrap_or(AURORA_KP),
+    };
+    // only touched when it moves, so change detection stays quiet
+    if aurora.kp != kp {
+        aurora.kp = kp;
+    }
+
+    let Ok(sun) = suns.single() else {
+        return;
+    };
+    let (oval_latitude, oval_half_width) = Aurora::oval(kp);
+
+    for (handle, mut visibility) in &mut shells {
+        visibility.set_if_neq(if aurora.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+        let Some(material) = materials.get_mut(handle) else {
+            continue;
+        };
+        let uniform = &mut material.aurora;
+        // the light shines along its -z, so +z points back at the sun
+        uniform.sun_direction = sun.back().into();
+        uniform.oval_latitude = oval_latitude.to_radians();
+        uniform.oval_half_width = oval_half_width.to_radians();
+        // a little even on quiet nights
+        uniform.intensity = AURORA_INTENSITY * (0.15 + 0.85 * kp / MAX_KP);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ovals_grow_equatorward_with_kp() {
+        let mut previous = Aurora::oval(0.0);
+        assert_eq!(previous.0 - previous.1, 66.0);
+        for step in 1..=9 {
+            let (middle, half_width) = Aurora::oval(step as f32);
+            let (previous_middle, previous_half_width) = previous;
+            assert!(middle - half_width < previous_middle - previous_half_width);
+            assert!(middle < previous_middle);
+            assert!(half_width > previous_half_width);
+            previous = (middle, half_width);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use bevy::{
+    camera::Exposure,
+    core_pipeline::tonemapping::Tonemapping,
+    post_process::{
+        auto_exposure::{AutoExposure, AutoExposurePlugin},
+        bloom::Bloom,
+    },
+    prelude::*,
+};
+
+use crate::Sun;
+use crate::config::*;
+
+// Exposure, tonemapping and bloom of the main camera, and the sun's illuminance
+// the sun is physically based, so the camera exposes for daylight like a real one would
+// and the night side and the atmosphere come out in the same units, see sun_radiance_scale
+
+pub struct CameraSettingsPlugin;
+
+impl Plugin for CameraSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        // not part of the default plugins, it needs compute shaders and warns without them
+        app.add_plugins(AutoExposurePlugin)
+            .init_resource::<CameraSettings>()
+            .add_systems(
+                Update,
+                apply_camera_settings.run_if(resource_changed::<CameraSettings>),
+            );
+    }
+}
+
+/// How the scene is lit and exposed, applied to the cameras and the sun whenever it changes
+#[derive(Resource, Clone)]
+pub struct CameraSettings {
+    // lux
+    pub sun_illuminance: f32,
+    pub ev100: f32,
+    // corrects the exposure from ev100 by metering the image, None keeps it fixed
+    pub auto_exposure: Option<AutoExposure>,
+    pub tonemapping: Tonemapping,
+    // None turns bloom off
+    pub bloom: Option<Bloom>,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        let (darkest, brightest) = AUTO_EXPOSURE_RANGE;
+        CameraSettings {
+            sun_illuminance: SUN_ILLUMINANCE,
+            ev100: CAMERA_EV100,
+            auto_exposure: Some(AutoExposure {
+                range: darkest..=brightest,
+                ..default()
+            }),
+            // AgX rolls the sun glint and city lights off into white without skewing their hue,
+            // and keeps the deep blacks of space
+            tonemapping: Tonemapping::AgX,
+            bloom: Some(Bloom {
+                intensity: BLOOM_INTENSITY,
+                ..Bloom::NATURAL
+            }),
+        }
+    }
+}
+
+fn apply_camera_settings(
+    mut commands: Commands,
+    settings: Res<CameraSettings>,
+    cameras: Query<Entity, With<Camera3d>>,
+    mut suns: Query<&mut DirectionalLight, With<Sun>>,
+) {
+    for mut light in &mut suns {
+        light.illuminance = settings.sun_illuminance;
+    }
+
+    for camera in &cameras {
+        let mut camera = commands.entity(camera);
+        camera.insert((
+            Exposure {
+                ev100: settings.ev100,
+            },
+            settings.tonemapping,
+        ));
+
+        match &settings.auto_exposure {
+            Some(auto_exposure) => camera.insert(auto_exposure.clone()),
+            None => camera.remove::<AutoExposure>(),
+        };
+        match &settings.bloom {
+            Some(bloom) => camera.insert(bloom.clone()),
+            None => camera.remove::<Bloom>(),
+        };
+    }
+}
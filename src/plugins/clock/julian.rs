@@ -0,0 +1,224 @@
+// Julian date conversions
+// based on Jean Meeus, Astronomical Algorithms (2nd ed.), chapter 7
+
+/// Julian date of the J2000.0 epoch (2000-01-01 12:00 TT)
+pub const J2000: f64 = 2451545.0;
+
+/// Julian date of the unix epoch (1970-01-01 00:00 UTC)
+pub const UNIX_EPOCH: f64 = 2440587.5;
+
+pub const SECONDS_PER_DAY: f64 = 86400.0;
+
+/// A gregorian calendar date and UTC time of day
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CalendarDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: f64,
+}
+
+impl CalendarDate {
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        CalendarDate {
+            year,
+            month,
+            day,
+            hour: 0,
+            minute: 0,
+            second: 0.0,
+        }
+    }
+
+    pub fn with_time(mut self, hour: u32, minute: u32, second: f64) -> Self {
+        self.hour = hour;
+        self.minute = minute;
+        self.second = second;
+        self
+    }
+
+    /// Parses an ISO 8601 UTC timestamp such as `2024-05-01T06:00:00Z`
+    /// the time, its seconds and the trailing `Z` are optional
+    pub fn parse_iso8601(text: &str) -> Option<Self> {
+        let text = text.strip_suffix('Z').unwrap_or(text);
+        let (date, time) = text.split_once('T').unwrap_or((text, "00:00"));
+
+        let mut date = date.splitn(3, '-');
+        let year = date.next()?.parse::<i32>().ok()?;
+        let month = date.next()?.parse::<u32>().ok()?;
+        let day = date.next()?.parse::<u32>().ok()?;
+
+        let mut time = time.splitn(3, ':');
+        let hour = time.next()?.parse::<u32>().ok()?;
+        let minute = time.next()?.parse::<u32>().ok()?;
+        let second = match time.next() {
+            Some(second) => second.parse::<f64>().ok()?,
+            None => 0.0,
+        };
+
+        let valid = (1..=12).contains(&month)
+            && (1..=31).contains(&day)
+            && hour < 24
+            && minute < 60
+            && (0.0..61.0).contains(&second);
+        valid.then(|| CalendarDate::new(year, month, day).with_time(hour, minute, second))
+    }
+
+    pub fn to_julian_date(self) -> f64 {
+        let (mut year, mut month) = (self.year as f64, self.month as f64);
+        if month <= 2.0 {
+            year -= 1.0;
+            month += 12.0;
+        }
+
+        // gregorian calendar correction
+        let a = (year / 100.0).floor();
+        let b = 2.0 - a + (a / 4.0).floor();
+
+        let day_fraction =
+            (self.hour as f64 + self.minute as f64 / 60.0 + self.second / 3600.0) / 24.0;
+
+        (365.25 * (year + 4716.0)).floor()
+            + (30.6001 * (month + 1.0)).floor()
+            + self.day as f64
+            + day_fraction
+            + b
+            - 1524.5
+    }
+
+    pub fn from_julian_date(julian_date: f64) -> Self {
+        // split into whole days and milliseconds, rounding first so that
+        // floating point error can't produce times like 12:59:60
+        const MILLIS_PER_DAY: i64 = 86_400_000;
+        let millis = ((julian_date + 0.5) * MILLIS_PER_DAY as f64).round() as i64;
+        let z = millis.div_euclid(MILLIS_PER_DAY) as f64;
+        let millis_of_day = millis.rem_euclid(MILLIS_PER_DAY);
+
+        let a = if z < 2299161.0 {
+            z
+        } else {
+            let alpha = ((z - 1867216.25) / 36524.25).floor();
+            z + 1.0 + alpha - (alpha / 4.0).floor()
+        };
+
+        let b = a + 1524.0;
+        let c = ((b - 122.1) / 365.25).floor();
+        let d = (365.25 * c).floor();
+        let e = ((b - d) / 30.6001).floor();
+
+        let day = (b - d - (30.6001 * e).floor()) as u32;
+        let month = if e < 14.0 { e - 1.0 } else { e - 13.0 } as u32;
+        let year = if month > 2 { c - 4716.0 } else { c - 4715.0 } as i32;
+
+        let hour = millis_of_day / 3_600_000;
+        let minute = millis_of_day % 3_600_000 / 60_000;
+        let second = (millis_of_day % 60_000) as f64 / 1000.0;
+
+        CalendarDate {
+            year,
+            month,
+            day,
+            hour: hour as u32,
+            minute: minute as u32,
+            second,
+        }
+    }
+}
+
+/// Julian date of midnight (0h UTC) at the start of the day containing `julian_date`
+pub fn start_of_day(julian_date: f64) -> f64 {
+    (julian_date - 0.5).floor() + 0.5
+}
+
+/// Julian date of the current system time
+pub fn now() -> f64 {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs_f64())
+        .unwrap_or(0.0);
+
+    UNIX_EPOCH + seconds / SECONDS_PER_DAY
+}
+
+/// Julian centuries since J2000, used by most ephemeris formulas
+pub fn centuries_since_j2000(julian_date: f64) -> f64 {
+    (julian_date - J2000) / 36525.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn j2000_is_noon_on_the_first_of_january_2000() {
+        let date = CalendarDate::new(2000, 1, 1).with_time(12, 0, 0.0);
+        assert_eq!(date.to_julian_date(), J2000);
+        assert_eq!(CalendarDate::from_julian_date(J2000), date);
+    }
+
+    #[test]
+    fn calendar_dates_round_trip() {
+        let dates = [
+            CalendarDate::new(1957, 10, 4).with_time(19, 28, 34.0),
+            CalendarDate::new(1970, 1, 1),
+            CalendarDate::new(2000, 2, 29).with_time(23, 59, 59.5),
+            CalendarDate::new(2024, 12, 31).with_time(6, 30, 0.0),
+        ];
+        for date in dates {
+            assert_eq!(CalendarDate::from_julian_date(date.to_julian_date()), date);
+        }
+        assert_eq!(CalendarDate::new(1970, 1, 1).to_julian_date(), UNIX_EPOCH);
+    }
+
+    #[test]
+    fn start_of_day_is_the_previous_midnight() {
+        let noon = CalendarDate::new(2024, 6, 21).with_time(12, 0, 0.0);
+        let midnight = CalendarDate::new(2024, 6, 21).to_julian_date();
+        assert_eq!(start_of_day(noon.to_julian_date()), midnight);
+        assert_eq!(start_of_day(midnight), midnight);
+    }
+
+    #[test]
+    fn parses_iso8601_timestamps() {
+        let expected = CalendarDate::new(2024, 5, 1).with_time(6, 0, 0.0);
+        assert_eq!(
+            CalendarDate::parse_iso8601("2024-05-01T06:00:00Z"),
+            Some(expected)
+        );
+        assert_eq!(
+            CalendarDate::parse_iso8601("2024-05-01T06:00:00"),
+            Some(expected)
+        );
+        assert_eq!(
+            CalendarDate::parse_iso8601("2024-05-01T06:00"),
+            Some(expected)
+        );
+        assert_eq!(
+            CalendarDate::parse_iso8601("2024-05-01"),
+            Some(CalendarDate::new(2024, 5, 1))
+        );
+        assert_eq!(
+            CalendarDate::parse_iso8601("2024-05-01T06:00:30.5Z"),
+            Some(CalendarDate::new(2024, 5, 1).with_time(6, 0, 30.5))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_timestamps() {
+        for text in [
+            "",
+            "2024-05",
+            "2024-13-01T00:00:00Z",
+            "2024-05-01T24:00:00Z",
+            "2024-05-01T06:60Z",
+            "2024-05-01T06",
+            "2024-05-01T06:00:00+02:00",
+            "2024/05/01T06:00:00Z",
+            "yesterday",
+        ] {
+            assert_eq!(CalendarDate::parse_iso8601(text), None, "{text:?}");
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+pub mod julian;
+pub mod series;
+
+use julian::SECONDS_PER_DAY;
+
+pub struct ClockPlugin;
+
+impl Plugin for ClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimulationClock>()
+            .add_systems(First, advance_clock);
+    }
+}
+
+/// Simulated UTC time, decoupled from wall clock time
+/// everything that depends on the date (sun, satellites, ...) should read this
+#[derive(Resource, Debug, Clone)]
+pub struct SimulationClock {
+    // UTC julian date
+    pub julian_date: f64,
+    // simulated seconds per real second
+    pub time_scale: f64,
+    pub paused: bool,
+}
+
+impl Default for SimulationClock {
+    fn default() -> Self {
+        SimulationClock::new(julian::now())
+    }
+}
+
+impl SimulationClock {
+    pub fn new(julian_date: f64) -> Self {
+        SimulationClock {
+            julian_date,
+            time_scale: 1.0,
+            paused: false,
+        }
+    }
+
+    pub fn calendar_date(&self) -> julian::CalendarDate {
+        julian::CalendarDate::from_julian_date(self.julian_date)
+    }
+}
+
+fn advance_clock(mut clock: ResMut<SimulationClock>, time: Res<Time>) {
+    if clock.paused {
+        return;
+    }
+
+    let elapsed_days = time.delta_secs_f64() * clock.time_scale / SECONDS_PER_DAY;
+    clock.julian_date += elapsed_days;
+}
@@ -0,0 +1,87 @@
+use std::str::SplitWhitespace;
+
+use super::julian::CalendarDate;
+
+// Text files of timestamped values, such as the cloud frame manifest and the Kp series
+// one entry per line, `#` starts a comment, entries start with an ISO 8601 UTC timestamp
+
+/// A line of a series file with something on it once the comment is removed
+pub struct SeriesLine<'a> {
+    // counted from 1, for error messages
+    pub number: usize,
+    pub first: &'a str,
+    // the fields after the first
+    pub fields: SplitWhitespace<'a>,
+}
+
+impl SeriesLine<'_> {
+    /// Julian date (UTC) of the first field, None if it isn't a timestamp
+    pub fn time(&self) -> Option<f64> {
+        CalendarDate::parse_iso8601(self.first).map(|date| date.to_julian_date())
+    }
+}
+
+/// The lines of a series file, blank and comment-only lines skipped
+pub fn series_lines(text: &str) -> impl Iterator<Item = SeriesLine<'_>> {
+    text.lines().enumerate().filter_map(|(index, line)| {
+        let mut fields = line.split('#').next().unwrap_or("").split_whitespace();
+        let first = fields.next()?;
+        Some(SeriesLine {
+            number: index + 1,
+            first,
+            fields,
+        })
+    })
+}
+
+/// Indices of the items at or before and after `x`, sorted by `key`, and how far between them it is
+/// outside the items the first or last is held, None if there are none
+pub fn bracket<T>(items: &[T], x: f64, key: impl Fn(&T) -> f64) -> Option<(usize, usize, f64)> {
+    let last = items.len().checked_sub(1)?;
+    let after = items.partition_point(|item| key(item) <= x);
+
+    Some(match after {
+        0 => (0, 0, 0.0),
+        _ if after > last => (last, last, 0.0),
+        _ => {
+            let (start, end) = (key(&items[after - 1]), key(&items[after]));
+            (after - 1, after, (x - start) / (end - start))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_lines_and_strips_comments() {
+        let text = "# header\n\n2024-05-01T06:00:00Z a b # note\n   # indented\nbounds 1 2\n";
+        let lines: Vec<_> = series_lines(text)
+            .map(|line| (line.number, line.first, line.fields.collect::<Vec<_>>()))
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                (3, "2024-05-01T06:00:00Z", vec!["a", "b"]),
+                (5, "bounds", vec!["1", "2"]),
+            ]
+        );
+        assert!(series_lines(text).next().unwrap().time().is_some());
+        assert_eq!(series_lines(text).nth(1).unwrap().time(), None);
+    }
+
+    #[test]
+    fn brackets_and_holds_the_ends() {
+        let keys = [1.0, 2.0, 4.0];
+        let bracket = |x| bracket(&keys, x, |&key| key);
+
+        assert_eq!(bracket(0.0), Some((0, 0, 0.0)));
+        assert_eq!(bracket(1.0), Some((0, 1, 0.0)));
+        assert_eq!(bracket(3.0), Some((1, 2, 0.5)));
+        assert_eq!(bracket(4.0), Some((2, 2, 0.0)));
+        assert_eq!(bracket(9.0), Some((2, 2, 0.0)));
+        assert_eq!(super::bracket(&[], 1.0, |&key: &f64| key), None);
+    }
+}
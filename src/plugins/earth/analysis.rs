@@ -0,0 +1,349 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::EarthData;
+use crate::config::*;
+use crate::plugins::clock::series::bracket;
+use crate::plugins::earth::materials::{AnalysisUniform, EarthMaterial};
+
+// Terrain analysis views: the globe coloured by elevation, slope or aspect of the heightmap
+// the displacement uses, cycled with F4
+// the active colour ramp is baked into one image, shared by the material and the legend
+
+const CYCLE_MODE_KEY: KeyCode = KeyCode::F4;
+// texels of the baked ramp
+const RAMP_WIDTH: u32 = 256;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnalysisMode {
+    // the photographic globe
+    #[default]
+    Off,
+    // hypsometric tint
+    Elevation,
+    Slope,
+    // compass direction the slopes face
+    Aspect,
+}
+
+impl AnalysisMode {
+    pub fn next(self) -> Self {
+        match self {
+            AnalysisMode::Off => AnalysisMode::Elevation,
+            AnalysisMode::Elevation => AnalysisMode::Slope,
+            AnalysisMode::Slope => AnalysisMode::Aspect,
+            AnalysisMode::Aspect => AnalysisMode::Off,
+        }
+    }
+
+    /// Value of `analysis.mode` in earth.wgsl
+    pub fn shader_mode(self) -> u32 {
+        match self {
+            AnalysisMode::Off => 0,
+            AnalysisMode::Elevation => 1,
+            AnalysisMode::Slope => 2,
+            AnalysisMode::Aspect => 3,
+        }
+    }
+
+    /// Legend title
+    pub fn label(self) -> &'static str {
+        match self {
+            AnalysisMode::Off => "",
+            AnalysisMode::Elevation => "Elevation (m)",
+            AnalysisMode::Slope => "Slope (°)",
+            AnalysisMode::Aspect => "Aspect",
+        }
+    }
+}
+
+/// Colours at increasing values, linear in sRGB between them
+#[derive(Clone, Debug)]
+pub struct ColorRamp {
+    // at least two, strictly increasing, so the ramp always spans a range
+    stops: Vec<(f32, Color)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorRampError {
+    TooFewStops,
+    // index of the stop that is not above the one before it
+    NotIncreasing(usize),
+}
+
+impl fmt::Display for ColorRampError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorRampError::TooFewStops => write!(f, "a colour ramp needs at least two stops"),
+            ColorRampError::NotIncreasing(index) => {
+                write!(f, "stop {index} is not above the one before it")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ColorRampError {}
+
+impl ColorRamp {
+    pub fn new(stops: Vec<(f32, Color)>) -> Result<Self, ColorRampError> {
+        if stops.len() < 2 {
+            return Err(ColorRampError::TooFewStops);
+        }
+        if let Some(index) = (1..stops.len())
+            .find(|&i| stops[i].0.partial_cmp(&stops[i - 1].0) != Some(Ordering::Greater))
+        {
+            return Err(ColorRampError::NotIncreasing(index));
+        }
+        Ok(ColorRamp { stops })
+    }
+
+    pub fn from_srgb(stops: &[(f32, [u8; 3])]) -> Result<Self, ColorRampError> {
+        ColorRamp::new(
+            stops
+                .iter()
+                .map(|&(value, [r, g, b])| (value, Color::srgb_u8(r, g, b)))
+                .collect(),
+        )
+    }
+
+    /// Hue wheel over the compass, north is red at both ends
+    pub fn compass() -> Self {
+        ColorRamp {
+            stops: (0..=8)
+                .map(|step| {
+                    let degrees = step as f32 * 45.0;
+                    (degrees, Color::hsl(degrees, 0.75, 0.55))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    /// Values of the first and last stop
+    pub fn range(&self) -> (f32, f32) {
+        (self.stops[0].0, self.stops[self.stops.len() - 1].0)
+    }
+
+    /// Colour at `value`, the end stops hold their colours beyond the range
+    pub fn sample(&self, value: f32) -> Color {
+        let (before, after, t) = bracket(&self.stops, value as f64, |&(stop, _)| stop as f64)
+            .expect("a ramp has at least two stops");
+        let (from, to) = (self.stops[before].1, self.stops[after].1);
+        Srgba::from(from).mix(&Srgba::from(to), t as f32).into()
+    }
+
+    /// RAMP_WIDTH x 1 image from the first to the last stop
+    pub fn to_image(&self) -> Image {
+        let (min, max) = self.range();
+        let data: Vec<u8> = (0..RAMP_WIDTH)
+            .flat_map(|x| {
+                let value = min + (max - min) * x as f32 / (RAMP_WIDTH - 1) as f32;
+                Srgba::from(self.sample(value)).to_u8_array()
+            })
+            .collect();
+
+        Image::new(
+            Extent3d {
+                width: RAMP_WIDTH,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            // kept in the main world so it can be rebaked in place
+            RenderAssetUsages::default(),
+        )
+    }
+}
+
+/// Which analysis view is shown and how it is coloured
+#[derive(Resource, Clone, Debug)]
+pub struct TerrainAnalysis {
+    pub mode: AnalysisMode,
+    // real km a heightmap value of 1 stands for
+    pub max_elevation: f32,
+    // metres
+    pub elevation_ramp: ColorRamp,
+    // degrees
+    pub slope_ramp: ColorRamp,
+    // degrees clockwise from north
+    pub aspect_ramp: ColorRamp,
+}
+
+impl Default for TerrainAnalysis {
+    fn default() -> Self {
+        TerrainAnalysis {
+            mode: AnalysisMode::Off,
+            max_elevation: TERRAIN_MAX_ELEVATION,
+            elevation_ramp: ColorRamp::from_srgb(ELEVATION_RAMP).expect("invalid ELEVATION_RAMP"),
+            slope_ramp: ColorRamp::from_srgb(SLOPE_RAMP).expect("invalid SLOPE_RAMP"),
+            aspect_ramp: ColorRamp::compass(),
+        }
+    }
+}
+
+impl TerrainAnalysis {
+    /// Ramp of the current mode
+    pub fn ramp(&self) -> Option<&ColorRamp> {
+        match self.mode {
+            AnalysisMode::Off => None,
+            AnalysisMode::Elevation => Some(&self.elevation_ramp),
+            AnalysisMode::Slope => Some(&self.slope_ramp),
+            AnalysisMode::Aspect => Some(&self.aspect_ramp),
+        }
+    }
+
+    /// Legend labels, each at its position along the ramp (0-1)
+    pub fn legend_labels(&self) -> Vec<(f32, String)> {
+        const COMPASS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+        let Some(ramp) = self.ramp() else {
+            return Vec::new();
+        };
+        let (min, max) = ramp.range();
+
+        ramp.stops()
+            .iter()
+            .map(|&(value, _)| {
+                let text = match self.mode {
+                    AnalysisMode::Aspect => {
+                        COMPASS[(value / 45.0).round() as usize % COMPASS.len()].to_string()
+                    }
+                    _ => format!("{value:.0}"),
+                };
+                ((value - min) / (max - min), text)
+            })
+            .collect()
+    }
+
+    pub fn to_uniform(&self) -> AnalysisUniform {
+        let (ramp_min, ramp_max) = self.ramp().map_or((0.0, 1.0), ColorRamp::range);
+        AnalysisUniform {
+            mode: self.mode.shader_mode(),
+            ramp_min,
+            ramp_max,
+            max_elevation: self.max_elevation,
+        }
+    }
+}
+
+/// The baked ramp of the current mode
+#[derive(Resource)]
+pub struct AnalysisRamp(pub Handle<Image>);
+
+impl FromWorld for AnalysisRamp {
+    fn from_world(world: &mut World) -> Self {
+        let ramp = world
+            .resource::<TerrainAnalysis>()
+            .elevation_ramp
+            .to_image();
+        AnalysisRamp(world.resource_mut::<Assets<Image>>().add(ramp))
+    }
+}
+
+pub(super) fn cycle_analysis_mode(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut analysis: ResMut<TerrainAnalysis>,
+) {
+    if keys.just_pressed(CYCLE_MODE_KEY) {
+        analysis.mode = analysis.mode.next();
+        info!("terrain analysis: {:?}", analysis.mode);
+    }
+}
+
+/// Rebakes the ramp when the settings change and keeps the material's uniform current
+pub(super) fn apply_terrain_analysis(
+    analysis: Res<TerrainAnalysis>,
+    ramp: Res<AnalysisRamp>,
+    data: Res<EarthData>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<EarthMaterial>>,
+) {
+    if !analysis.is_changed() {
+        return;
+    }
+    if let Some(colors) = analysis.ramp()
+        && let Some(image) = images.get_mut(&ramp.0)
+    {
+        *image = colors.to_image();
+    }
+
+    let Some(handle) = &data.earth_material else {
+        return;
+    };
+    let Some(material) = materials.get_mut(handle) else {
+        return;
+    };
+    material.extension.analysis = analysis.to_uniform();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grey_ramp() -> ColorRamp {
+        ColorRamp::from_srgb(&[
+            (0.0, [0, 0, 0]),
+            (10.0, [100, 100, 100]),
+            (20.0, [200, 200, 200]),
+        ])
+        .unwrap()
+    }
+
+    fn red(color: Color) -> u8 {
+        Srgba::from(color).to_u8_array()[0]
+    }
+
+    #[test]
+    fn samples_hold_the_ends_and_interpolate_between_stops() {
+        let ramp = grey_ramp();
+
+        assert_eq!(red(ramp.sample(-5.0)), 0);
+        assert_eq!(red(ramp.sample(0.0)), 0);
+        assert_eq!(red(ramp.sample(5.0)), 50);
+        assert_eq!(red(ramp.sample(10.0)), 100);
+        assert_eq!(red(ramp.sample(15.0)), 150);
+        assert_eq!(red(ramp.sample(20.0)), 200);
+        assert_eq!(red(ramp.sample(1000.0)), 200);
+    }
+
+    #[test]
+    fn rejects_ramps_without_a_range() {
+        assert_eq!(
+            ColorRamp::from_srgb(&[]).unwrap_err(),
+            ColorRampError::TooFewStops
+        );
+        assert_eq!(
+            ColorRamp::from_srgb(&[(5.0, [0, 0, 0])]).unwrap_err(),
+            ColorRampError::TooFewStops
+        );
+        assert_eq!(
+            ColorRamp::from_srgb(&[(0.0, [0, 0, 0]), (5.0, [0, 0, 0]), (5.0, [9, 9, 9])])
+                .unwrap_err(),
+            ColorRampError::NotIncreasing(2)
+        );
+        assert_eq!(
+            ColorRamp::from_srgb(&[(0.0, [0, 0, 0]), (f32::NAN, [0, 0, 0])]).unwrap_err(),
+            ColorRampError::NotIncreasing(1)
+        );
+    }
+
+    #[test]
+    fn configured_ramps_are_valid() {
+        let analysis = TerrainAnalysis {
+            mode: AnalysisMode::Elevation,
+            ..default()
+        };
+        let labels = analysis.legend_labels();
+        assert_eq!(labels.first().unwrap().0, 0.0);
+        assert_eq!(labels.last().unwrap().0, 1.0);
+    }
+}
@@ -0,0 +1,57 @@
+use bevy::{asset::uuid_handle, prelude::*};
+use std::f32::consts::PI;
+
+use crate::config::*;
+
+// WGSL import module generated from config.rs, so the shaders can't drift from the cpu side
+// shaders use it with `#import earth::constants::{EARTH_RADIUS, ...}`
+
+pub const CONSTANTS_SHADER: Handle<Shader> = uuid_handle!("9a724698-d135-40a6-9e65-75376e9f6b11");
+pub const CONSTANTS_IMPORT_PATH: &str = "earth::constants";
+
+/// Source of the module
+pub fn constants_wgsl() -> String {
+    let [red, green, blue] = RAYLEIGH_COEFF;
+    let constants = [
+        ("PI", "f32", float(PI)),
+        // km
+        ("EARTH_RADIUS", "f32", float(EARTH_RADIUS)),
+        ("ATMOSPHERE_RADIUS", "f32", float(ATMOSPHERE_RADIUS)),
+        ("CLOUD_RADIUS", "f32", float(CLOUD_RADIUS)),
+        ("DISPLACEMENT_SCALE", "f32", float(DISPLACEMENT_SCALE)),
+        // per meter, at sea level
+        (
+            "RAYLEIGH_COEFF",
+            "vec3<f32>",
+            format!(
+                "vec3<f32>({}, {}, {})",
+                float(red),
+                float(green),
+                float(blue)
+            ),
+        ),
+        ("MIE_COEFF", "f32", float(MIE_COEFF)),
+        ("MIE_ANISOTROPY", "f32", float(MIE_ANISOTROPY)),
+    ];
+
+    let mut source = format!(
+        "// generated by constants.rs from config.rs, don't edit\n#define_import_path {CONSTANTS_IMPORT_PATH}\n\n"
+    );
+    for (name, kind, value) in constants {
+        source.push_str(&format!("const {name}: {kind} = {value};\n"));
+    }
+    source
+}
+
+// debug formatting always keeps a decimal point or exponent, so WGSL reads the value as a float
+fn float(value: f32) -> String {
+    format!("{value:?}")
+}
+
+/// Registers the module before any material compiles its shaders
+pub(super) fn add_constants_shader(mut shaders: ResMut<Assets<Shader>>) {
+    let shader = Shader::from_wgsl(constants_wgsl(), file!());
+    if let Err(error) = shaders.insert(&CONSTANTS_SHADER, shader) {
+        warn!("could not add the shader constants: {error}");
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    pbr::wireframe::Wireframe,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use super::{EarthChunk, EarthData};
+use crate::plugins::earth::materials::EarthMaterial;
+
+// Diagnostic views of the globe, for chasing seams, chunk edges and normal map problems
+// cycled with F3, the current mode is logged
+
+const CYCLE_MODE_KEY: KeyCode = KeyCode::F3;
+
+/// How the globe is drawn
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DebugRenderMode {
+    #[default]
+    Off,
+    // the usual shading with the mesh edges on top
+    // the lines are drawn from the cpu-baked meshes, so they also show if the gpu displacement drifts
+    Wireframe,
+    // geometric world normal as colour
+    Normals,
+    // checker texture on the mesh uvs, shows stretching and uv seams
+    UvChecker,
+    // every chunk in its own colour
+    Chunks,
+    // grey surface lit only through the normal map
+    NormalMap,
+}
+
+impl DebugRenderMode {
+    pub fn next(self) -> Self {
+        match self {
+            DebugRenderMode::Off => DebugRenderMode::Wireframe,
+            DebugRenderMode::Wireframe => DebugRenderMode::Normals,
+            DebugRenderMode::Normals => DebugRenderMode::UvChecker,
+            DebugRenderMode::UvChecker => DebugRenderMode::Chunks,
+            DebugRenderMode::Chunks => DebugRenderMode::NormalMap,
+            DebugRenderMode::NormalMap => DebugRenderMode::Off,
+        }
+    }
+
+    /// Value of `debug_mode` in earth.wgsl
+    pub fn shader_mode(self) -> u32 {
+        match self {
+            DebugRenderMode::Off | DebugRenderMode::Wireframe => 0,
+            DebugRenderMode::Normals => 1,
+            DebugRenderMode::UvChecker => 2,
+            DebugRenderMode::Chunks => 3,
+            DebugRenderMode::NormalMap => 4,
+        }
+    }
+}
+
+pub(super) fn cycle_debug_render_mode(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut mode: ResMut<DebugRenderMode>,
+) {
+    if keys.just_pressed(CYCLE_MODE_KEY) {
+        *mode = mode.next();
+        info!("debug render mode: {:?}", *mode);
+    }
+}
+
+/// Puts the globe's material and chunks into the current mode when it changes,
+/// or when the globe has just been built
+pub(super) fn apply_debug_render_mode(
+    mut commands: Commands,
+    mode: Res<DebugRenderMode>,
+    data: Res<EarthData>,
+    mut materials: ResMut<Assets<EarthMaterial>>,
+    chunks: Query<(Entity, Has<Wireframe>), With<EarthChunk>>,
+    built: Query<(), Added<EarthChunk>>,
+) {
+    if !mode.is_changed() && built.is_empty() {
+        return;
+    }
+    let Some(handle) = &data.earth_material else {
+        return;
+    };
+    let Some(material) = materials.get_mut(handle) else {
+        return;
+    };
+    material.extension.surface.debug_mode = mode.shader_mode();
+
+    let wireframe = *mode == DebugRenderMode::Wireframe;
+    for (chunk, has_wireframe) in &chunks {
+        if wireframe && !has_wireframe {
+            commands.entity(chunk).insert(Wireframe);
+        } else if !wireframe && has_wireframe {
+            commands.entity(chunk).remove::<Wireframe>();
+        }
+    }
+}
+
+/// 8x8 colour checker for the UvChecker mode
+pub fn uv_debug_texture() -> Image {
+    const TEXTURE_SIZE: usize = 8;
+
+    let mut palette: [u8; 32] = [
+        255, 102, 159, 255, 255, 159, 102, 255, 236, 255, 102, 255, 121, 255, 102, 255, 102, 255,
+        198, 255, 102, 198, 255, 255, 121, 102, 255, 255, 236, 102, 255, 255,
+    ];
+
+    let mut texture_data = [0; TEXTURE_SIZE * TEXTURE_SIZE * 4];
+    for y in 0..TEXTURE_SIZE {
+        let offset = TEXTURE_SIZE * y * 4;
+        texture_data[offset..(offset + TEXTURE_SIZE * 4)].copy_from_slice(&palette);
+        palette.rotate_right(4);
+    }
+
+    Image::new_fill(
+        Extent3d {
+            width: TEXTURE_SIZE as u32,
+            height: TEXTURE_SIZE as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &texture_data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
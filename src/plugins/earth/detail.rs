@@ -0,0 +1,66 @@
+use bevy::{
+    image::{ImageAddressMode, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor},
+    prelude::*,
+};
+
+use crate::config::*;
+use crate::plugins::earth::materials::DetailUniform;
+
+// Tiled ground textures that take over from the blurry global day texture close to the surface
+// earth.wgsl picks between them by elevation, normal map slope and latitude
+
+/// The four ground textures, in the order of TERRAIN_DETAIL_TEXTURES
+pub struct DetailTextures {
+    pub rock: Handle<Image>,
+    pub grass: Handle<Image>,
+    pub snow: Handle<Image>,
+    pub sand: Handle<Image>,
+    pub uniform: DetailUniform,
+}
+
+impl DetailTextures {
+    /// Loads the textures from the config, or binds placeholders and turns the detail off
+    pub fn load(asset_server: &AssetServer, images: &mut Assets<Image>) -> Self {
+        let (handles, strength) = match TERRAIN_DETAIL_TEXTURES {
+            Some(paths) => (
+                paths.map(|path| load_tiled(asset_server, path)),
+                DETAIL_STRENGTH,
+            ),
+            None => {
+                let placeholder = images.add(Image::default());
+                ([(); 4].map(|_| placeholder.clone()), 0.0)
+            }
+        };
+        let [rock, grass, snow, sand] = handles;
+        let (fade_start, fade_end) = DETAIL_FADE_DISTANCE;
+
+        DetailTextures {
+            rock,
+            grass,
+            snow,
+            sand,
+            uniform: DetailUniform {
+                tile_size: DETAIL_TILE_SIZE,
+                fade_start,
+                fade_end,
+                strength,
+                max_elevation: TERRAIN_MAX_ELEVATION,
+                snow_line: SNOW_LINE,
+                rock_slope: ROCK_SLOPE,
+                desert_latitude: DESERT_LATITUDE,
+            },
+        }
+    }
+}
+
+// the textures repeat across the globe, so their sampler has to wrap
+// earth.wgsl samples all four with the rock texture's
+fn load_tiled(asset_server: &AssetServer, path: &'static str) -> Handle<Image> {
+    asset_server.load_with_settings(path, |settings: &mut ImageLoaderSettings| {
+        settings.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+            address_mode_u: ImageAddressMode::Repeat,
+            address_mode_v: ImageAddressMode::Repeat,
+            ..ImageSamplerDescriptor::linear()
+        });
+    })
+}
@@ -0,0 +1,42 @@
+use bevy::math::DVec3;
+use bevy::prelude::*;
+
+use crate::plugins::sun::ephemeris::greenwich_mean_sidereal_time;
+
+// Reference frame conversions
+// TEME: true equator, mean equinox, the inertial frame SGP4 outputs (z = north pole)
+// ECEF: earth-centered earth-fixed (x = greenwich meridian, z = north pole)
+// scene: the earth-fixed frame used by `LatLon` (x = 90°E, y = north pole, z = greenwich)
+// polar motion and the difference between GMST and GAST are ignored (a few tens of meters)
+
+/// Rotates a TEME vector into ECEF
+pub fn teme_to_ecef(teme: DVec3, julian_date: f64) -> DVec3 {
+    let (sin_gmst, cos_gmst) = greenwich_mean_sidereal_time(julian_date).sin_cos();
+    DVec3::new(
+        cos_gmst * teme.x + sin_gmst * teme.y,
+        -sin_gmst * teme.x + cos_gmst * teme.y,
+        teme.z,
+    )
+}
+
+/// Earth's rotation rate relative to the stars (rad/s)
+const EARTH_ROTATION_RATE: f64 = 7.292115e-5;
+
+/// Rotates a TEME position and velocity into ECEF, the velocity becomes relative to the ground
+pub fn teme_state_to_ecef(position: DVec3, velocity: DVec3, julian_date: f64) -> (DVec3, DVec3) {
+    let position = teme_to_ecef(position, julian_date);
+    // the earth-fixed frame turns under the orbit
+    let velocity =
+        teme_to_ecef(velocity, julian_date) - DVec3::Z.cross(position) * EARTH_ROTATION_RATE;
+    (position, velocity)
+}
+
+/// Swizzles ECEF axes into the scene's earth-fixed axes
+pub fn ecef_to_scene(ecef: DVec3) -> Vec3 {
+    Vec3::new(ecef.y as f32, ecef.z as f32, ecef.x as f32)
+}
+
+/// TEME position at `julian_date` to a scene position
+pub fn teme_to_scene(teme: DVec3, julian_date: f64) -> Vec3 {
+    ecef_to_scene(teme_to_ecef(teme, julian_date))
+}
@@ -0,0 +1,160 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future::poll_once},
+};
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI, TAU};
+
+use super::EarthData;
+use super::mesh::Heights;
+use crate::config::{DISPLACEMENT_SCALE, EARTH_RADIUS, HORIZON_DISTANCE, HORIZON_STEPS};
+
+// Horizon map for terrain self-shadowing
+// for every texel, how high the surrounding relief rises above it towards eight compass directions,
+// earth.wgsl shades a slope only while the sun is above that horizon
+// baked at the starting exaggeration DISPLACEMENT_SCALE, as the normal map is,
+// earth.wgsl rescales the slopes to the current one so shadows match what is drawn
+// the curvature of the earth is ignored, HORIZON_DISTANCE keeps it small next to the relief
+// baking takes a while, so it runs on the async compute pool and the globe starts out unshadowed
+
+/// The horizon map being baked in the background, and the images it fills in when done
+pub struct HorizonTask {
+    task: Task<[Image; 2]>,
+    pub images: [Handle<Image>; 2],
+}
+
+impl HorizonTask {
+    /// Starts baking the horizon map of a height map, its images cast no shadows until then
+    pub fn spawn(height_map: Image, size: (u32, u32), images: &mut Assets<Image>) -> Self {
+        let task = AsyncComputeTaskPool::get()
+            .spawn(async move { generate_horizon_map(&height_map, size) });
+        HorizonTask {
+            task,
+            images: [(); 2].map(|_| images.add(horizon_image(vec![0; 4], 1, 1))),
+        }
+    }
+}
+
+/// Swaps the baked horizon map in for the placeholders once its task is done
+pub(super) fn finish_horizon_map(mut data: ResMut<EarthData>, mut images: ResMut<Assets<Image>>) {
+    let Some(horizon) = data.horizon_task.as_mut() else {
+        return;
+    };
+    let Some(maps) = block_on(poll_once(&mut horizon.task)) else {
+        return;
+    };
+    for (handle, map) in horizon.images.iter().zip(maps) {
+        if let Some(image) = images.get_mut(handle) {
+            *image = map;
+        }
+    }
+    data.horizon_task = None;
+}
+
+/// The horizon elevation towards N, NE, E, SE and towards S, SW, W, NW
+/// each as a fraction of 90 degrees in one channel
+pub fn generate_horizon_map(height_map: &Image, (width, height): (u32, u32)) -> [Image; 2] {
+    let (width, height) = (width as usize, height as usize);
+    let mut data = [vec![0u8; width * height * 4], vec![0u8; width * height * 4]];
+
+    let Some(heights) = Heights::new(height_map) else {
+        return data.map(|data| horizon_image(data, width, height));
+    };
+    let (source_width, source_height) = (heights.width, heights.height);
+
+    // km of ground one texel of the height map spans along a meridian
+    let texel_north = PI * EARTH_RADIUS / source_height as f32;
+
+    for y in 0..height {
+        let v = (y as f32 + 0.5) / height as f32;
+        let latitude = (0.5 - v) * PI;
+        // poleward the parallels shrink, so a km spans more texels east to west
+        let texel_east = (TAU * EARTH_RADIUS * latitude.cos() / source_width as f32).max(1e-3);
+
+        for x in 0..width {
+            let u = (x as f32 + 0.5) / width as f32;
+            let source = Vec2::new(
+                u * source_width as f32 - 0.5,
+                v * source_height as f32 - 0.5,
+            );
+            let base = heights.sample(source);
+
+            for direction in 0..8 {
+                // clockwise from north
+                let azimuth = direction as f32 * FRAC_PI_4;
+                let (east, north) = azimuth.sin_cos();
+
+                // steps closer together near the texel, where the horizon rises fastest
+                let mut steepest = 0.0f32;
+                for step in 1..=HORIZON_STEPS {
+                    let fraction = step as f32 / HORIZON_STEPS as f32;
+                    let distance = HORIZON_DISTANCE * fraction * fraction;
+                    // image rows run southwards
+                    let offset = Vec2::new(
+                        distance * east / texel_east,
+                        -distance * north / texel_north,
+                    );
+                    let rise = (heights.sample(source + offset) - base) * DISPLACEMENT_SCALE;
+                    steepest = steepest.max(rise / distance);
+                }
+
+                let elevation = steepest.atan() / FRAC_PI_2;
+                let pixel_index = (y * width + x) * 4 + direction % 4;
+                data[direction / 4][pixel_index] = (elevation * 255.0).round() as u8;
+            }
+        }
+    }
+
+    data.map(|data| horizon_image(data, width, height))
+}
+
+fn horizon_image(data: Vec<u8>, width: usize, height: usize) -> Image {
+    Image::new(
+        Extent3d {
+            width: width as u32,
+            height: height as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ridges_cast_a_horizon_towards_them() {
+        // flat ground with a ridge along the meridian of column 32
+        let (width, height) = (64, 32);
+        let data = (0..width * height)
+            .flat_map(|index| {
+                let red = if index % width == 32 { 255 } else { 0 };
+                [red, 0, 0, 255]
+            })
+            .collect();
+        let height_map = horizon_image(data, width, height);
+        let [north_east, south_west] =
+            generate_horizon_map(&height_map, (width as u32, height as u32));
+        let elevation = |image: &Image, x: usize, channel: usize| {
+            image.data.as_ref().unwrap()[(16 * width + x) * 4 + channel]
+        };
+
+        // just west of the ridge, east is channel 2 of the first map and west of the second
+        assert!(elevation(&north_east, 31, 2) > 0);
+        assert_eq!(elevation(&south_west, 31, 2), 0);
+        // and the other way round just east of it
+        assert!(elevation(&south_west, 33, 2) > 0);
+        assert_eq!(elevation(&north_east, 33, 2), 0);
+
+        // too far away to see it in any direction
+        for channel in 0..4 {
+            assert_eq!(elevation(&north_east, 10, channel), 0);
+            assert_eq!(elevation(&south_west, 10, channel), 0);
+        }
+    }
+}
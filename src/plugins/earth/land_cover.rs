@@ -0,0 +1,351 @@
+use std::fmt;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    image::ImageLoaderSettings,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use super::EarthData;
+use crate::config::*;
+use crate::plugins::earth::materials::EarthMaterial;
+use crate::plugins::earth::uv::LatLon;
+
+// Thematic globe coloured by a categorical land cover raster instead of the day texture
+// toggled with F5, the classes' colours and roughness are baked into a palette indexed by class code
+
+const TOGGLE_KEY: KeyCode = KeyCode::F5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LandCoverError {
+    UnsupportedFormat(TextureFormat),
+    // the first value above 255 in a 16-bit raster
+    CodeOutOfRange(u16),
+}
+
+impl fmt::Display for LandCoverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LandCoverError::UnsupportedFormat(format) => {
+                write!(f, "{format:?} is not an 8 or 16-bit integer format")
+            }
+            LandCoverError::CodeOutOfRange(code) => write!(f, "class code {code} is above 255"),
+        }
+    }
+}
+
+impl std::error::Error for LandCoverError {}
+
+#[derive(Clone, Debug)]
+pub struct LandCoverClass {
+    pub code: u8,
+    pub name: String,
+    pub color: Color,
+    pub perceptual_roughness: f32,
+}
+
+/// The land cover raster, its classes and whether the globe shows it
+#[derive(Resource)]
+pub struct LandCover {
+    pub visible: bool,
+    pub classes: Vec<LandCoverClass>,
+    // None without EARTH_LAND_COVER_TEXTURE
+    pub raster: Option<Handle<Image>>,
+    // 256x2, row 0 is the linear colour with alpha 1 for listed codes, row 1 the roughness
+    pub palette: Handle<Image>,
+}
+
+impl FromWorld for LandCover {
+    fn from_world(world: &mut World) -> Self {
+        let classes: Vec<LandCoverClass> = LAND_COVER_CLASSES
+            .iter()
+            .map(
+                |&(code, name, [r, g, b], perceptual_roughness)| LandCoverClass {
+                    code,
+                    name: name.to_string(),
+                    color: Color::srgb_u8(r, g, b),
+                    perceptual_roughness,
+                },
+            )
+            .collect();
+
+        // class codes are data, read texel by texel, so no srgb and no filtering
+        // check_land_cover_raster converts the raster to one byte per pixel once it loads
+        let raster = EARTH_LAND_COVER_TEXTURE.map(|path| {
+            world
+                .resource::<AssetServer>()
+                .load_with_settings(path, |settings: &mut ImageLoaderSettings| {
+                    settings.is_srgb = false
+                })
+        });
+        let palette = world
+            .resource_mut::<Assets<Image>>()
+            .add(palette_image(&classes));
+
+        LandCover {
+            visible: false,
+            classes,
+            raster,
+            palette,
+        }
+    }
+}
+
+impl LandCover {
+    pub fn class(&self, code: u8) -> Option<&LandCoverClass> {
+        self.classes.iter().find(|class| class.code == code)
+    }
+
+    /// Class of the raster pixel containing a location, None until the raster has loaded
+    /// or where its code isn't one of the classes
+    pub fn land_cover_at(
+        &self,
+        images: &Assets<Image>,
+        location: &LatLon,
+    ) -> Option<&LandCoverClass> {
+        let image = images.get(self.raster.as_ref()?)?;
+        let (u, v) = location.to_uv();
+        let x = ((u * image.width() as f32) as u32).min(image.width() - 1);
+        let y = ((v * image.height() as f32) as u32).min(image.height() - 1);
+        let code = *image.pixel_bytes(UVec3::new(x, y, 0))?.first()?;
+        self.class(code)
+    }
+}
+
+/// The class codes of a raster as an R8Unorm image, from the first channel of 8 or 16-bit integer formats
+pub fn class_codes(image: &Image) -> Result<Image, LandCoverError> {
+    let format = image.texture_descriptor.format;
+    let data = image.data.as_deref().unwrap_or_default();
+    let codes = match format {
+        TextureFormat::R8Unorm
+        | TextureFormat::R8Uint
+        | TextureFormat::Rg8Unorm
+        | TextureFormat::Rg8Uint
+        | TextureFormat::Rgba8Unorm
+        | TextureFormat::Rgba8UnormSrgb
+        | TextureFormat::Rgba8Uint => {
+            let stride = format.block_copy_size(None).unwrap_or(1) as usize;
+            data.chunks_exact(stride).map(|pixel| pixel[0]).collect()
+        }
+        // codes saved at 16 bits keep their values, they aren't scaled up to 65535
+        TextureFormat::R16Unorm
+        | TextureFormat::R16Uint
+        | TextureFormat::Rg16Unorm
+        | TextureFormat::Rg16Uint
+        | TextureFormat::Rgba16Unorm
+        | TextureFormat::Rgba16Uint => {
+            let stride = format.block_copy_size(None).unwrap_or(2) as usize;
+            data.chunks_exact(stride)
+                .map(|pixel| {
+                    let code = u16::from_le_bytes([pixel[0], pixel[1]]);
+                    u8::try_from(code).map_err(|_| LandCoverError::CodeOutOfRange(code))
+                })
+                .collect::<Result<Vec<u8>, _>>()?
+        }
+        format => return Err(LandCoverError::UnsupportedFormat(format)),
+    };
+
+    Ok(Image::new(
+        image.texture_descriptor.size,
+        TextureDimension::D2,
+        codes,
+        TextureFormat::R8Unorm,
+        RenderAssetUsages::default(),
+    ))
+}
+
+fn palette_image(classes: &[LandCoverClass]) -> Image {
+    let mut data = vec![0; 256 * 2 * 4];
+    for class in classes {
+        let colour = 4 * class.code as usize;
+        let roughness = 4 * (256 + class.code as usize);
+        let [r, g, b, _] = LinearRgba::from(class.color).to_u8_array();
+        data[colour..colour + 4].copy_from_slice(&[r, g, b, 255]);
+        data[roughness] = (class.perceptual_roughness.clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+
+    Image::new(
+        Extent3d {
+            width: 256,
+            height: 2,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        // the roughness row must not be srgb decoded, so the colours are stored linear
+        TextureFormat::Rgba8Unorm,
+        // kept in the main world so it can be rebaked in place
+        RenderAssetUsages::default(),
+    )
+}
+
+/// Converts the raster to one byte per pixel when it loads, or drops it when it can't be
+/// so the shader and `land_cover_at` both read codes from the first byte
+pub(super) fn check_land_cover_raster(
+    mut events: MessageReader<AssetEvent<Image>>,
+    mut land_cover: ResMut<LandCover>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Some(raster) = land_cover.raster.clone() else {
+        return;
+    };
+    for event in events.read() {
+        if !event.is_loaded_with_dependencies(&raster) {
+            continue;
+        }
+        let Some(image) = images.get_mut(&raster) else {
+            continue;
+        };
+        if image.texture_descriptor.format == TextureFormat::R8Unorm {
+            continue;
+        }
+        match class_codes(image) {
+            Ok(codes) => *image = codes,
+            Err(error) => {
+                warn!("ignoring the land cover raster: {error}");
+                // the material keeps the handle, a placeholder lists no class
+                *image = Image::default();
+                land_cover.raster = None;
+                land_cover.visible = false;
+                return;
+            }
+        }
+    }
+}
+
+pub(super) fn toggle_land_cover(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut land_cover: ResMut<LandCover>,
+) {
+    if !keys.just_pressed(TOGGLE_KEY) {
+        return;
+    }
+    if land_cover.raster.is_none() {
+        warn!("no land cover raster, set EARTH_LAND_COVER_TEXTURE in config.rs");
+        return;
+    }
+    land_cover.visible = !land_cover.visible;
+    info!(
+        "land cover: {}",
+        if land_cover.visible { "on" } else { "off" }
+    );
+}
+
+/// Rebakes the palette when the classes change and shows or hides the land cover
+pub(super) fn apply_land_cover(
+    land_cover: Res<LandCover>,
+    data: Res<EarthData>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<EarthMaterial>>,
+) {
+    if !land_cover.is_changed() {
+        return;
+    }
+    if let Some(palette) = images.get_mut(&land_cover.palette) {
+        *palette = palette_image(&land_cover.classes);
+    }
+
+    let Some(handle) = &data.earth_material else {
+        return;
+    };
+    let Some(material) = materials.get_mut(handle) else {
+        return;
+    };
+    material.extension.surface.show_land_cover = land_cover.visible as u32;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn land_cover(raster: Handle<Image>) -> LandCover {
+        LandCover {
+            visible: true,
+            classes: vec![LandCoverClass {
+                code: 10,
+                name: "Tree cover".to_string(),
+                color: Color::BLACK,
+                perceptual_roughness: 1.0,
+            }],
+            raster: Some(raster),
+            palette: Handle::default(),
+        }
+    }
+
+    fn raster(width: u32, height: u32, format: TextureFormat, data: Vec<u8>) -> Image {
+        Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            format,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn looks_up_the_class_under_a_location() {
+        // west half is tree cover, the east half an unlisted code
+        let mut images = Assets::<Image>::default();
+        let handle = images.add(raster(
+            4,
+            2,
+            TextureFormat::R8Unorm,
+            vec![10, 10, 99, 99, 10, 10, 99, 99],
+        ));
+        let land_cover = land_cover(handle);
+
+        let west = LatLon::from_degrees(30.0, -100.0);
+        let class = land_cover.land_cover_at(&images, &west).unwrap();
+        assert_eq!(class.name, "Tree cover");
+
+        let east = LatLon::from_degrees(-30.0, 100.0);
+        assert!(land_cover.land_cover_at(&images, &east).is_none());
+
+        // u = 1 at the antimeridian stays on the last column
+        let seam = LatLon::from_degrees(0.0, 180.0);
+        assert_eq!(seam.to_uv().0, 1.0);
+        assert!(land_cover.land_cover_at(&images, &seam).is_none());
+        let west_edge = LatLon::from_degrees(0.0, -180.0);
+        assert!(
+            land_cover
+                .land_cover_at(&images, &west_edge)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn converts_rasters_to_one_byte_codes() {
+        let rgba = raster(
+            2,
+            1,
+            TextureFormat::Rgba8Unorm,
+            vec![10, 0, 0, 255, 20, 0, 0, 255],
+        );
+        let codes = class_codes(&rgba).unwrap();
+        assert_eq!(codes.texture_descriptor.format, TextureFormat::R8Unorm);
+        assert_eq!(codes.data.unwrap(), vec![10, 20]);
+
+        let wide = raster(2, 1, TextureFormat::R16Uint, vec![10, 0, 80, 0]);
+        assert_eq!(class_codes(&wide).unwrap().data.unwrap(), vec![10, 80]);
+    }
+
+    #[test]
+    fn rejects_rasters_that_are_not_codes() {
+        let wide = raster(2, 1, TextureFormat::R16Unorm, vec![10, 0, 0, 1]);
+        assert_eq!(
+            class_codes(&wide).unwrap_err(),
+            LandCoverError::CodeOutOfRange(256)
+        );
+
+        let float = raster(1, 1, TextureFormat::R32Float, vec![0; 4]);
+        assert_eq!(
+            class_codes(&float).unwrap_err(),
+            LandCoverError::UnsupportedFormat(TextureFormat::R32Float)
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::{
+    pbr::{ExtendedMaterial, MaterialExtension},
     prelude::*,
     render::render_resource::*,
     reflect::TypePath,
@@ -6,54 +7,218 @@ use bevy::{
     shader::ShaderRef,
 };
 
-#[derive(ShaderType, Clone, Copy, Debug)]
-#[repr(C)]
-pub struct SunUniform {
-    pub direction: Vec3,
-    pub _padding: f32,
-}
+use super::sequence::GeoBounds;
 
 // atmosphere uniform data
-#[derive(ShaderType, Copy, Clone, Debug)]
+#[derive(ShaderType, Copy, Clone, Debug, Reflect)]
 #[repr(C)]
 pub struct AtmosphereUniform {
     pub sun_direction: Vec3,
     pub camera_position: Vec3,
     pub rayleigh_coeff: Vec3,
     pub mie_coeff: f32,
-    pub sun_intensity: f32,
     pub atomosphere_radius: f32,
     pub _padding: f32,
 }
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct EarthMaterial {
-    #[texture(0)]
-    #[sampler(1)]
+// ocean wind and bathymetry
+#[derive(ShaderType, Copy, Clone, Debug, Reflect)]
+#[repr(C)]
+pub struct OceanUniform {
+    // m/s
+    pub wind_speed: f32,
+    // km a bathymetry value of 1 stands for, 0 when there is no bathymetry
+    pub bathymetry_depth: f32,
+    pub _padding: Vec2,
+}
+
+// look of the surface, synced from EarthShadingSettings
+#[derive(ShaderType, Copy, Clone, Debug, Reflect)]
+#[repr(C)]
+pub struct ShadingUniform {
+    pub normal_strength: f32,
+    pub terminator_width: f32,
+    pub night_ambient: f32,
+    pub night_desaturation: f32,
+    pub city_lights_strength: f32,
+    pub calm_water_roughness: f32,
+    pub rough_water_roughness: f32,
+    pub cloud_shadow_strength: f32,
+    pub terrain_shadow_strength: f32,
+}
+
+// which terrain analysis view is shown and the values its colour ramp spans
+#[derive(ShaderType, Copy, Clone, Debug, Reflect)]
+#[repr(C)]
+pub struct AnalysisUniform {
+    pub mode: u32,
+    pub ramp_min: f32,
+    pub ramp_max: f32,
+    // real km a heightmap value of 1 stands for
+    pub max_elevation: f32,
+}
+
+// how the tiled detail textures are faded in and chosen, see detail.rs
+#[derive(ShaderType, Copy, Clone, Debug, Reflect)]
+#[repr(C)]
+pub struct DetailUniform {
+    // km
+    pub tile_size: f32,
+    pub fade_start: f32,
+    pub fade_end: f32,
+    // 0 when there are no detail textures
+    pub strength: f32,
+    // real km a heightmap value of 1 stands for
+    pub max_elevation: f32,
+    // metres, at the equator
+    pub snow_line: f32,
+    // degrees
+    pub rock_slope: f32,
+    pub desert_latitude: f32,
+}
+
+// per-frame values of the surface, packed into one uniform
+#[derive(ShaderType, Copy, Clone, Debug, Reflect)]
+#[repr(C)]
+pub struct SurfaceUniform {
+    // the cloud layer's drift and opacity, for cloud shadows
+    pub cloud_drift: f32,
+    pub cloud_opacity: f32,
+    // monthly textures are blended from `day_texture` into `next_day_texture`
+    pub day_blend: f32,
+    // km of terrain per unit of height, see EarthShadingSettings
+    pub displacement_scale: f32,
+    // one of the diagnostic views in earth.wgsl, see debug.rs, 0 draws the globe normally
+    pub debug_mode: u32,
+    // 1 replaces the day texture with the land cover
+    pub show_land_cover: u32,
+    pub _padding: Vec2,
+}
+
+// where a raster sequence sits on the globe and how far between its two frames the clock is
+#[derive(ShaderType, Copy, Clone, Debug, Reflect)]
+#[repr(C)]
+pub struct RasterFramesUniform {
+    // west, south, east, north in radians
+    pub bounds: Vec4,
+    pub blend: f32,
+    pub _padding: Vec3,
+}
+
+impl Default for RasterFramesUniform {
+    // a single global image
+    fn default() -> Self {
+        RasterFramesUniform {
+            bounds: GeoBounds::GLOBAL.to_vec4(),
+            blend: 0.0,
+            _padding: Vec3::ZERO,
+        }
+    }
+}
+
+/// The globe's material: Bevy's PBR pipeline lights the surface, the extension supplies
+/// the day/night textures, ocean and relief and adds the atmosphere on top
+pub type EarthMaterial = ExtendedMaterial<StandardMaterial, EarthExtension>;
+
+// bindings start at 100, below that belongs to the StandardMaterial
+// textures that are sampled alike share one sampler, to stay within 16 samplers per stage
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+pub struct EarthExtension {
+    // its sampler is shared by every global surface texture: night, ocean mask, specular,
+    // bathymetry and the next month's day texture
+    #[texture(100)]
+    #[sampler(101)]
     pub day_texture: Handle<Image>,
-    #[texture(2)]
-    #[sampler(3)]
+    #[texture(102)]
     pub night_texture: Handle<Image>,
-    #[texture(4)]
-    #[sampler(5)]
+    #[texture(104)]
     pub ocean_mask: Handle<Image>,
-    #[texture(6)]
-    #[sampler(7)]
+    #[texture(106)]
     pub specular_map: Handle<Image>,
-    #[texture(8)]
-    #[sampler(9)]
+    #[texture(108)]
+    #[sampler(109)]
     pub normal_map: Handle<Image>,
-    #[uniform(10)]
-    pub sun_uniform: SunUniform,
+    // the atmosphere shell's coefficients for aerial perspective, camera and sun go unused
+    #[uniform(111)]
+    pub atmosphere_uniform: AtmosphereUniform,
+    // the tables share the transmittance table's sampler
+    #[texture(112)]
+    #[sampler(113)]
+    pub transmittance_lut: Handle<Image>,
+    #[texture(114, dimension = "3d")]
+    pub scattering_lut: Handle<Image>,
+    #[texture(116, dimension = "3d")]
+    pub multiple_scattering_lut: Handle<Image>,
+    // the cloud layer's frames, for cloud shadows, sharing one sampler
+    #[texture(118)]
+    #[sampler(119)]
+    pub cloud_texture: Handle<Image>,
+    #[texture(122)]
+    pub next_cloud_texture: Handle<Image>,
+    #[uniform(124)]
+    pub cloud_frames: RasterFramesUniform,
+    #[uniform(125)]
+    pub ocean_uniform: OceanUniform,
+    #[texture(126)]
+    pub bathymetry: Handle<Image>,
+    #[texture(128)]
+    pub next_day_texture: Handle<Image>,
+    // the raw heightmap, read texel by texel so the vertex shader matches mesh::sample_height
+    #[texture(131)]
+    pub heightmap: Handle<Image>,
+    // read by the vertex shader too, for the displacement
+    #[uniform(132)]
+    pub surface: SurfaceUniform,
+    #[texture(134)]
+    #[sampler(135)]
+    pub uv_checker: Handle<Image>,
+    #[uniform(136)]
+    pub shading: ShadingUniform,
+    // elevation, slope and aspect views, see analysis.rs
+    #[uniform(137)]
+    pub analysis: AnalysisUniform,
+    #[texture(138)]
+    #[sampler(139)]
+    pub analysis_ramp: Handle<Image>,
+    // tiled ground textures for close views, 1x1 placeholders when there are none
+    // all four repeat, so they share the first one's sampler
+    #[texture(140)]
+    #[sampler(141)]
+    pub detail_rock: Handle<Image>,
+    #[texture(142)]
+    pub detail_grass: Handle<Image>,
+    #[texture(144)]
+    pub detail_snow: Handle<Image>,
+    #[texture(146)]
+    pub detail_sand: Handle<Image>,
+    #[uniform(148)]
+    pub detail: DetailUniform,
+    // class codes, read texel by texel, and the palette they index, see land_cover.rs
+    #[texture(149)]
+    pub land_cover: Handle<Image>,
+    #[texture(150)]
+    pub land_cover_palette: Handle<Image>,
+    // how high the relief rises towards each compass direction, for terrain shadows, see horizon.rs
+    #[texture(152)]
+    #[sampler(153)]
+    pub horizon_north_east: Handle<Image>,
+    #[texture(154)]
+    pub horizon_south_west: Handle<Image>,
 }
 
-impl Material for EarthMaterial {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/earth.wgsl".into()
+impl MaterialExtension for EarthExtension {
+    // the terrain is displaced on the gpu, in the prepass too so shadows see it
+    fn vertex_shader() -> ShaderRef {
+        "shaders/earth_vertex.wgsl".into()
     }
 
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Opaque
+    fn prepass_vertex_shader() -> ShaderRef {
+        "shaders/earth_vertex.wgsl".into()
+    }
+
+    // forward only, the atmosphere and night side are added after bevy's lighting
+    fn fragment_shader() -> ShaderRef {
+        "shaders/earth.wgsl".into()
     }
 }
 
@@ -61,6 +226,14 @@ impl Material for EarthMaterial {
 pub struct AtmosphereMaterial {
     #[uniform(0)]
     pub atmosphere_uniform: AtmosphereUniform,
+    // precomputed from the uniform, see scattering.rs, sharing one sampler
+    #[texture(1)]
+    #[sampler(2)]
+    pub transmittance_lut: Handle<Image>,
+    #[texture(3, dimension = "3d")]
+    pub scattering_lut: Handle<Image>,
+    #[texture(5, dimension = "3d")]
+    pub multiple_scattering_lut: Handle<Image>,
 }
 
 impl Material for AtmosphereMaterial {
@@ -68,16 +241,17 @@ impl Material for AtmosphereMaterial {
         "shaders/atmosphere.wgsl".into()
     }
 
+    // scattered light is added on top, the transmittance dims what is behind
     fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+        AlphaMode::Premultiplied
     }
 
     fn specialize(
-            pipeline: &bevy::pbr::MaterialPipeline,
-            descriptor: &mut RenderPipelineDescriptor,
-            layout: &bevy::mesh::MeshVertexBufferLayoutRef,
-            key: bevy::pbr::MaterialPipelineKey<Self>,
-        ) -> Result<(), SpecializedMeshPipelineError> {
+        _pipeline: &bevy::pbr::MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &bevy::mesh::MeshVertexBufferLayoutRef,
+        _key: bevy::pbr::MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
         descriptor.primitive.cull_mode = None;
         Ok(())
     }
@@ -88,16 +262,22 @@ pub struct CloudMaterial {
     #[texture(0)]
     #[sampler(1)]
     pub cloud_texture: Handle<Image>,
-    #[uniform(2)]
-    pub sun_uniform: SunUniform,
     // runtime adjustment
     #[uniform(3)]
     pub cloud_opacity: f32,
+    // radians east of the surface, advanced with the simulation clock
+    #[uniform(4)]
+    pub cloud_drift: f32,
+    // time series frames are blended from `cloud_texture` into this one, with its sampler
+    #[texture(5)]
+    pub next_cloud_texture: Handle<Image>,
+    #[uniform(7)]
+    pub cloud_frames: RasterFramesUniform,
 }
 
 impl Material for CloudMaterial {
     fn fragment_shader() -> ShaderRef {
-        "shaders/cloud.wgsl".into()
+        "shaders/clouds.wgsl".into()
     }
 
     fn alpha_mode(&self) -> AlphaMode {
@@ -1,20 +1,24 @@
 use bevy::{
     asset::RenderAssetUsages,
+    camera::primitives::Aabb,
     mesh::{Indices, PrimitiveTopology},
     prelude::*,
 };
+use std::f32::consts::PI;
 
-use crate::config::{DISPLACEMENT_SCALE, EARTH_RADIUS};
+use crate::config::EARTH_RADIUS;
 use crate::plugins::earth::uv::LatLon;
 
 /// Generates a spherical mesh face by projecting a flat grid onto a sphere
 /// Based on Sebastin Lague and Grayson Head's implementation
+/// the heights are raised by `displacement_scale` km at a heightmap value of 1
 pub fn generate_face(
     normal: Vec3,
     resolution: u32,
     x_offset: f32,
     y_offset: f32,
     displacement: Option<&Image>,
+    displacement_scale: f32,
 ) -> Mesh {
     // this creates two perpendicular axes on the cube face
     let axis_a = Vec3::new(normal.y, normal.z, normal.x);
@@ -66,9 +70,9 @@ pub fn generate_face(
                 u = 0.0;
             }
 
-            // sample displacement
+            // sample displacement, by direction like the vertex shader so both agree
             let displacement = if let Some(disp_map) = displacement {
-                sample_displacement(disp_map, u, v) * DISPLACEMENT_SCALE
+                sample_height(disp_map, point_on_unit_sphere.normalize()) * displacement_scale
             } else {
                 0.0
             };
@@ -102,6 +106,7 @@ pub fn generate_face(
 
     // the problem with recalculating normals is that it messes up the day/night shader
     // i.e. cities on mountain sides becomes bright earlier/later than they should
+    // the relief's own shadows come from the horizon map instead, see horizon.rs
 
     // build bevy mesh
     let mut mesh = Mesh::new(
@@ -138,7 +143,7 @@ fn cube_point_to_sphere_point(p: Vec3) -> Vec3 {
 
 /// Recalculate normals based on actual mesh geometry
 #[allow(dead_code)]
-fn recalculate_normals(normals: &mut Vec<Vec3>, vertices: &[Vec3], indices: &[u32]) {
+fn recalculate_normals(normals: &mut [Vec3], vertices: &[Vec3], indices: &[u32]) {
     // reset normals
     normals.fill(Vec3::ZERO);
 
@@ -159,8 +164,8 @@ fn recalculate_normals(normals: &mut Vec<Vec3>, vertices: &[Vec3], indices: &[u3
 
             // check for degenrate triangle
             let face_normal_length = face_normal.length();
-            if face_normal_length < 1e-6 {
-                face_normal = face_normal / face_normal_length;
+            if face_normal_length > 1e-6 {
+                face_normal /= face_normal_length;
 
                 // add face normal to each vertex normal
                 normals[i0] += face_normal;
@@ -174,7 +179,7 @@ fn recalculate_normals(normals: &mut Vec<Vec3>, vertices: &[Vec3], indices: &[u3
     for normal in normals.iter_mut() {
         let length = normal.length();
         if length > 1e-6 {
-            *normal = *normal / length;
+            *normal /= length;
         } else {
             // fallback for isolated vertices
             *normal = Vec3::Y;
@@ -182,28 +187,219 @@ fn recalculate_normals(normals: &mut Vec<Vec3>, vertices: &[Vec3], indices: &[u3
     }
 }
 
-/// Sample displacement value from image at UV coordinates
-fn sample_displacement(image: &Image, u: f32, v: f32) -> f32 {
-    // clamp UV coordinates
-    let u = u.clamp(0.0, 1.0);
-    let v = v.clamp(0.0, 1.0);
+/// Bounds of a chunk for any terrain height between sea level and `displacement_scale`
+/// the vertex shader displaces the terrain itself, so culling must not rely on the baked relief
+pub fn chunk_bounds(mesh: &Mesh, displacement_scale: f32) -> Option<Aabb> {
+    let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+    let top = EARTH_RADIUS + displacement_scale.max(0.0);
+    Aabb::enclosing(positions.iter().flat_map(|&position| {
+        let direction = Vec3::from(position).normalize();
+        [direction * EARTH_RADIUS, direction * top]
+    }))
+}
 
-    let width = image.texture_descriptor.size.width as usize;
-    let height = image.texture_descriptor.size.height as usize;
+/// Height (0-1) of the heightmap in the direction of a point
+/// mirrors `sample_height` in terrain.wgsl, so picking lands on the terrain the gpu draws
+pub fn sample_height(image: &Image, direction: Vec3) -> f32 {
+    Heights::new(image).map_or(0.0, |heights| heights.at(direction))
+}
 
-    // UV to pixel coordinates
-    let x = (u * (width - 1) as f32).round() as usize;
-    let y = (v * (height - 1) as f32).round() as usize;
+// km between height samples along a picking ray
+const PICK_STEP: f32 = 0.5;
+
+/// Where a ray first meets the terrain the vertex shader draws, in the earth-fixed frame
+/// marches through the relief's shell, None if the ray passes over it
+pub fn raymarch_terrain(
+    image: &Image,
+    displacement_scale: f32,
+    origin: Vec3,
+    direction: Vec3,
+) -> Option<Vec3> {
+    let heights = Heights::new(image)?;
+    let direction = direction.normalize();
+    let above = |t: f32| {
+        let point = origin + direction * t;
+        point.length() - EARTH_RADIUS - heights.at(point) * displacement_scale
+    };
+
+    // the stretch of the ray inside the highest the terrain can reach
+    let top = EARTH_RADIUS + displacement_scale.max(0.0);
+    let b = origin.dot(direction);
+    let discriminant = b * b - (origin.length_squared() - top * top);
+    if discriminant < 0.0 {
+        return None;
+    }
+    let (near, far) = (-b - discriminant.sqrt(), -b + discriminant.sqrt());
+    if far < 0.0 {
+        return None;
+    }
 
-    // get pixel data slice
-    if let Some(data) = image.data.as_ref() {
-        let pixel_index = (y * width + x) * 4;  // 4 bytes per pixel (RGBA)
+    let mut previous = near.max(0.0);
+    if above(previous) <= 0.0 {
+        return Some(origin + direction * previous);
+    }
+    while previous < far {
+        let t = (previous + PICK_STEP).min(far);
+        if above(t) <= 0.0 {
+            // narrow the crossing down to well under a step
+            let (mut outside, mut inside) = (previous, t);
+            for _ in 0..16 {
+                let middle = 0.5 * (outside + inside);
+                if above(middle) <= 0.0 {
+                    inside = middle;
+                } else {
+                    outside = middle;
+                }
+            }
+            return Some(origin + direction * inside);
+        }
+        previous = t;
+    }
+    None
+}
 
-        if pixel_index + 3 < data.len() {
-            // just used red channel
-            return data[pixel_index] as f32 / 255.0;
+/// Red channel of a heightmap, read on the cpu the way terrain.wgsl reads it on the gpu
+pub struct Heights<'a> {
+    data: &'a [u8],
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<'a> Heights<'a> {
+    /// None when the image has no data on the cpu
+    pub fn new(image: &'a Image) -> Option<Self> {
+        Some(Heights {
+            data: image.data.as_deref()?,
+            width: image.texture_descriptor.size.width as usize,
+            height: image.texture_descriptor.size.height as usize,
+        })
+    }
+
+    /// Height (0-1) in the direction of a point
+    pub fn at(&self, direction: Vec3) -> f32 {
+        // same expressions as the shader rather than LatLon::to_uv, which goes through degrees
+        let coords = LatLon::from(direction);
+        let u = coords.longitude / (2.0 * PI) + 0.5;
+        let v = 0.5 - coords.latitude / PI;
+
+        self.sample(Vec2::new(
+            u * self.width as f32 - 0.5,
+            v * self.height as f32 - 0.5,
+        ))
+    }
+
+    /// Height (0-1) at texel coordinates, bilinear between texel centres,
+    /// wrapping in longitude and clamped at the poles
+    pub fn sample(&self, texel: Vec2) -> f32 {
+        let base = texel.floor();
+        let t = texel - base;
+        let (x0, y0) = (base.x as i64, base.y as i64);
+
+        let h00 = self.texel(x0, y0);
+        let h10 = self.texel(x0 + 1, y0);
+        let h01 = self.texel(x0, y0 + 1);
+        let h11 = self.texel(x0 + 1, y0 + 1);
+        let top = h00 + (h10 - h00) * t.x;
+        let bottom = h01 + (h11 - h01) * t.x;
+        top + (bottom - top) * t.y
+    }
+
+    fn texel(&self, x: i64, y: i64) -> f32 {
+        let x = x.rem_euclid(self.width as i64) as usize;
+        let y = y.clamp(0, self.height as i64 - 1) as usize;
+        // 4 bytes per pixel (RGBA), just used red channel
+        let pixel_index = (y * self.width + x) * 4;
+        self.data
+            .get(pixel_index)
+            .map_or(0.0, |&red| red as f32 / 255.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+    /// A 4x2 heightmap with one value per texel in the red channel
+    fn heightmap(reds: [u8; 8]) -> Image {
+        let data = reds.iter().flat_map(|&red| [red, 0, 0, 255]).collect();
+        Image::new(
+            Extent3d {
+                width: 4,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::MAIN_WORLD,
+        )
+    }
+
+    /// Direction to the texture coordinate (u, v), the inverse of the mapping in `sample_height`
+    fn direction(u: f32, v: f32) -> Vec3 {
+        let longitude = (u - 0.5) * 2.0 * PI;
+        let latitude = (0.5 - v) * PI;
+        LatLon {
+            latitude,
+            longitude,
         }
+        .to_unit_vector()
+    }
+
+    #[test]
+    fn interpolates_between_texel_centres() {
+        let image = heightmap([0, 255, 0, 0, 0, 255, 0, 0]);
+
+        // the centre of texel (1, 0) and halfway to its neighbour
+        assert!((sample_height(&image, direction(0.375, 0.25)) - 1.0).abs() < 1e-3);
+        assert!((sample_height(&image, direction(0.5, 0.25)) - 0.5).abs() < 1e-3);
+        assert!((sample_height(&image, direction(0.3125, 0.25)) - 0.75).abs() < 1e-3);
+    }
+
+    #[test]
+    fn wraps_at_the_seam() {
+        let image = heightmap([255, 0, 0, 0, 255, 0, 0, 0]);
+
+        // halfway between the last and the first column, from either side of the antimeridian
+        let east = sample_height(&image, direction(0.9999, 0.25));
+        let west = sample_height(&image, direction(0.0001, 0.25));
+        assert!((east - 0.5).abs() < 1e-2, "{east}");
+        assert!((west - 0.5).abs() < 1e-2, "{west}");
     }
-    
-    0.0  // default if not available, or out of bounds
-}
\ No newline at end of file
+
+    #[test]
+    fn clamps_at_the_poles() {
+        let image = heightmap([255, 255, 255, 255, 0, 0, 0, 0]);
+
+        // above the first row's centres there is nothing to blend with
+        assert!((sample_height(&image, Vec3::Y) - 1.0).abs() < 1e-3);
+        assert!(sample_height(&image, Vec3::NEG_Y).abs() < 1e-3);
+        assert!((sample_height(&image, direction(0.1, 0.5)) - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn picking_rays_stop_at_the_raised_terrain() {
+        let image = heightmap([255; 8]);
+        let origin = Vec3::X * (EARTH_RADIUS + 1000.0);
+
+        let hit = raymarch_terrain(&image, 10.0, origin, Vec3::NEG_X).unwrap();
+        assert!((hit.length() - (EARTH_RADIUS + 10.0)).abs() < 1e-2, "{hit}");
+        let flat = raymarch_terrain(&image, 0.0, origin, Vec3::NEG_X).unwrap();
+        assert!((flat.length() - EARTH_RADIUS).abs() < 1e-2, "{flat}");
+        // passing over the terrain, and pointing away from it
+        let grazing = origin + Vec3::Y * (EARTH_RADIUS + 20.0);
+        assert_eq!(raymarch_terrain(&image, 10.0, grazing, Vec3::NEG_X), None);
+        assert_eq!(raymarch_terrain(&image, 10.0, origin, Vec3::X), None);
+    }
+
+    #[test]
+    fn chunk_bounds_cover_the_displacement() {
+        let mesh = generate_face(Vec3::X, 8, 0.0, 0.0, None, 0.0);
+        let bounds = chunk_bounds(&mesh, 80.0).unwrap();
+        let flat = chunk_bounds(&mesh, 0.0).unwrap();
+
+        assert!(bounds.max().max_element() >= EARTH_RADIUS + 79.9);
+        assert!(bounds.min().cmple(flat.min()).all() && bounds.max().cmpge(flat.max()).all());
+    }
+}
@@ -1,21 +1,90 @@
-use bevy::prelude::*;
+use bevy::{
+    asset::LoadState,
+    camera::primitives::Aabb,
+    image::ImageLoaderSettings,
+    light::NotShadowCaster,
+    mesh::MeshTag,
+    pbr::{OpaqueRendererMethod, wireframe::WireframePlugin},
+    prelude::*,
+};
+use std::path::Path;
 
+pub mod analysis;
+pub mod constants;
+pub mod debug;
+pub mod detail;
+pub mod frames;
+pub mod horizon;
+pub mod land_cover;
 pub mod materials;
 pub mod mesh;
 pub mod normal;
+pub mod scattering;
+pub mod seasons;
+pub mod sequence;
+pub mod shading;
 pub mod uv;
 
-use crate::{Sun, config::*};
-use materials::{EarthMaterial, SunUniform};
-use mesh::generate_face;
+use crate::{
+    Sun,
+    config::*,
+    plugins::clock::{SimulationClock, julian::J2000},
+};
+use analysis::{AnalysisRamp, TerrainAnalysis, apply_terrain_analysis, cycle_analysis_mode};
+use constants::add_constants_shader;
+use debug::{DebugRenderMode, apply_debug_render_mode, cycle_debug_render_mode};
+use detail::DetailTextures;
+use horizon::{HorizonTask, finish_horizon_map};
+use land_cover::{LandCover, apply_land_cover, check_land_cover_raster, toggle_land_cover};
+use materials::{
+    AtmosphereMaterial, AtmosphereUniform, CloudMaterial, EarthExtension, EarthMaterial,
+    OceanUniform, RasterFramesUniform, SurfaceUniform,
+};
+use mesh::{chunk_bounds, generate_face, raymarch_terrain};
 use normal::{generate_normal_map, save_image_as_png};
+use scattering::{AtmosphereLutTask, finish_atmosphere_luts};
+use seasons::MonthlyTextures;
+use sequence::RasterSequence;
+use shading::{EarthShadingSettings, sync_shading_settings};
 
 pub struct EarthPlugin;
 
 impl Plugin for EarthPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(MaterialPlugin::<EarthMaterial>::default())
-            .add_systems(Startup, setup);
+        app.add_plugins((
+            MaterialPlugin::<EarthMaterial>::default(),
+            MaterialPlugin::<AtmosphereMaterial>::default(),
+            MaterialPlugin::<CloudMaterial>::default(),
+            // only draws where the gpu supports line polygons, it warns otherwise
+            WireframePlugin::default(),
+        ))
+        .init_resource::<DebugRenderMode>()
+        .init_resource::<EarthShadingSettings>()
+        .init_resource::<TerrainAnalysis>()
+        .init_resource::<AnalysisRamp>()
+        .init_resource::<LandCover>()
+        .add_systems(Startup, (add_constants_shader, setup))
+        .add_systems(
+            Update,
+            (
+                build_earth,
+                finish_horizon_map,
+                finish_atmosphere_luts,
+                update_atmosphere,
+                update_clouds,
+                update_seasons,
+                sync_shading_settings,
+                update_chunk_bounds,
+                cycle_debug_render_mode,
+                apply_debug_render_mode,
+                cycle_analysis_mode,
+                apply_terrain_analysis,
+                check_land_cover_raster,
+                toggle_land_cover,
+                apply_land_cover,
+            )
+                .chain(),
+        );
     }
 }
 
@@ -23,6 +92,32 @@ impl Plugin for EarthPlugin {
 #[derive(Component)]
 pub struct Earth;
 
+/// One of the 24 mesh chunks the globe is built from, children of the `Earth` entity
+#[derive(Component)]
+pub struct EarthChunk;
+
+/// The heightmap the vertex shader raises the globe's flat chunks with,
+/// so clicks can land on the terrain it draws
+#[derive(Resource)]
+pub struct Relief {
+    heightmap: Handle<Image>,
+}
+
+impl Relief {
+    /// Where a ray in the earth-fixed frame first meets the terrain drawn at `displacement_scale`
+    /// None until the heightmap has loaded, or if the ray passes over the terrain
+    pub fn hit(
+        &self,
+        images: &Assets<Image>,
+        displacement_scale: f32,
+        origin: Vec3,
+        direction: Vec3,
+    ) -> Option<Vec3> {
+        let heightmap = images.get(&self.heightmap)?;
+        raymarch_terrain(heightmap, displacement_scale, origin, direction)
+    }
+}
+
 /// holds everything needed for earth generation including normal map
 #[derive(Resource)]
 struct EarthData {
@@ -30,24 +125,416 @@ struct EarthData {
     normal_map_handle: Option<Handle<Image>>, // generated normal map
     earth_entity: Entity,
     earth_material: Option<Handle<EarthMaterial>>, // created after normal map generation
+    atmosphere_material: Handle<AtmosphereMaterial>,
+    cloud_material: Handle<CloudMaterial>,
+    // replaces the static cloud texture when a manifest is present
+    cloud_sequence: Option<RasterSequence>,
+    // replaces the single day texture when configured
+    monthly_textures: Option<MonthlyTextures>,
+    // until the horizon map has been baked
+    horizon_task: Option<HorizonTask>,
+    // until the atmosphere lookup tables are loaded or computed
+    lut_task: Option<AtmosphereLutTask>,
 }
 
-fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>, asset_server: Res<AssetServer>) {
-    // sun direction
-    let sun_direction = Vec3::new(1.0, 1.0, 1.0).normalize();
-
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut images: ResMut<Assets<Image>>,
+    mut atmosphere_materials: ResMut<Assets<AtmosphereMaterial>>,
+    mut cloud_materials: ResMut<Assets<CloudMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
     // load textures
-    let displacement_handle = asset_server.load(EARTH_DISPLACEMENT_TEXTURE);
+    // heights and normals are data, not colour, so they must not be read as srgb
+    let displacement_handle = asset_server.load_with_settings(
+        EARTH_DISPLACEMENT_TEXTURE,
+        |settings: &mut ImageLoaderSettings| settings.is_srgb = false,
+    );
+    // a missing map is generated and saved by build_earth
+    let saved_normal_map = Path::new("assets").join(SAVED_NORMAL_MAP_PATH);
+    let normal_map_handle = (USE_SAVED_NORMAL_MAP && saved_normal_map.exists()).then(|| {
+        asset_server.load_with_settings(
+            SAVED_NORMAL_MAP_PATH,
+            |settings: &mut ImageLoaderSettings| settings.is_srgb = false,
+        )
+    });
 
     // create earth entity
+    // the scene is earth-fixed, the sun moves around the globe as the clock runs
     let earth_entity = commands
-        .spawn((Earth, Transform::default(), GlobalTransform::default()))
+        .spawn((Earth, Transform::default(), Visibility::default()))
         .id();
 
+    // atmosphere shell, camera and sun are filled in every frame
+    let atmosphere_uniform = AtmosphereUniform {
+        sun_direction: Vec3::Y,
+        camera_position: Vec3::ZERO,
+        rayleigh_coeff: Vec3::from_array(RAYLEIGH_COEFF),
+        mie_coeff: MIE_COEFF,
+        atomosphere_radius: ATMOSPHERE_RADIUS,
+        _padding: 0.0,
+    };
+    let luts = AtmosphereLutTask::spawn(
+        (&atmosphere_uniform).into(),
+        ATMOSPHERE_LUT_CACHE_PATH,
+        &mut images,
+    );
+    let atmosphere_material = atmosphere_materials.add(AtmosphereMaterial {
+        atmosphere_uniform,
+        transmittance_lut: luts.transmittance.clone(),
+        scattering_lut: luts.scattering.clone(),
+        multiple_scattering_lut: luts.scattering_multiple.clone(),
+    });
+    commands.spawn((
+        Mesh3d(meshes.add(Sphere::new(ATMOSPHERE_RADIUS).mesh().ico(6).unwrap())),
+        MeshMaterial3d(atmosphere_material.clone()),
+        Transform::default(),
+        // clicks have to reach the globe underneath
+        Pickable::IGNORE,
+        NotShadowCaster,
+        ChildOf(earth_entity),
+    ));
+
+    // cloud layer, drifts over the surface with the simulation clock
+    // cloud cover is used as opacity, so it is read as linear data
+    let cloud_texture: Handle<Image> = asset_server.load_with_settings(
+        EARTH_CLOUDS_TEXTURE,
+        |settings: &mut ImageLoaderSettings| settings.is_srgb = false,
+    );
+    let cloud_material = cloud_materials.add(CloudMaterial {
+        cloud_texture: cloud_texture.clone(),
+        cloud_opacity: CLOUD_OPACITY,
+        cloud_drift: 0.0,
+        next_cloud_texture: cloud_texture,
+        cloud_frames: RasterFramesUniform::default(),
+    });
+    commands.spawn((
+        Mesh3d(meshes.add(Sphere::new(CLOUD_RADIUS).mesh().ico(6).unwrap())),
+        MeshMaterial3d(cloud_material.clone()),
+        Transform::default(),
+        Pickable::IGNORE,
+        // the earth shader draws cloud shadows itself
+        NotShadowCaster,
+        ChildOf(earth_entity),
+    ));
+
+    commands.insert_resource(Relief {
+        heightmap: displacement_handle.clone(),
+    });
     commands.insert_resource(EarthData {
         displacement_handle,
-        normal_map_handle: None,
+        normal_map_handle,
         earth_entity,
         earth_material: None,
+        atmosphere_material,
+        cloud_material,
+        cloud_sequence: load_cloud_sequence(),
+        monthly_textures: EARTH_MONTHLY_DIFFUSE_TEXTURES.map(MonthlyTextures::from_pattern),
+        horizon_task: None,
+        lut_task: Some(luts),
+    });
+}
+
+/// Reads the optional cloud frame manifest, the static texture is kept until its frames load
+fn load_cloud_sequence() -> Option<RasterSequence> {
+    let manifest = std::fs::read_to_string(CLOUD_FRAMES_MANIFEST).ok()?;
+    let (sequence, errors) = RasterSequence::parse(&manifest, CLOUD_FRAMES_PRELOAD, false);
+    for error in errors {
+        warn!("skipping cloud frame in {CLOUD_FRAMES_MANIFEST}: {error}");
+    }
+    if sequence.is_empty() {
+        warn!("{CLOUD_FRAMES_MANIFEST} has no frames, using {EARTH_CLOUDS_TEXTURE}");
+        return None;
+    }
+    info!("animating clouds from {} frames", sequence.frames().len());
+    Some(sequence)
+}
+
+/// Builds the globe meshes and material once the displacement map has loaded
+#[allow(clippy::too_many_arguments)]
+fn build_earth(
+    mut commands: Commands,
+    mut data: ResMut<EarthData>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<EarthMaterial>>,
+    atmosphere_materials: Res<Assets<AtmosphereMaterial>>,
+    cloud_materials: Res<Assets<CloudMaterial>>,
+    shading: Res<EarthShadingSettings>,
+    analysis: Res<TerrainAnalysis>,
+    analysis_ramp: Res<AnalysisRamp>,
+    land_cover: Res<LandCover>,
+    asset_server: Res<AssetServer>,
+) {
+    if data.earth_material.is_some() {
+        return;
+    }
+    // the surface uses the same scattering tables as the sky
+    let Some(atmosphere) = atmosphere_materials.get(&data.atmosphere_material).cloned() else {
+        return;
+    };
+    let Some(clouds) = cloud_materials.get(&data.cloud_material).cloned() else {
+        return;
+    };
+    // a saved normal map that can't be read is generated again
+    if let Some(handle) = &data.normal_map_handle {
+        match asset_server.load_state(handle) {
+            LoadState::Loaded => {}
+            LoadState::Failed(error) => {
+                warn!("could not load {SAVED_NORMAL_MAP_PATH}, generating it: {error}");
+                data.normal_map_handle = None;
+            }
+            _ => return,
+        }
+    }
+    // owned, the horizon map is baked from it in the background
+    let Some(displacement) = images.get(&data.displacement_handle).cloned() else {
+        return;
+    };
+
+    let normal_map = match data.normal_map_handle.clone() {
+        Some(handle) => handle,
+        None => {
+            let normal_map = generate_normal_map(&displacement);
+            save_image_as_png(&normal_map, &format!("assets/{SAVED_NORMAL_MAP_PATH}"));
+            let handle = images.add(normal_map);
+            data.normal_map_handle = Some(handle.clone());
+            handle
+        }
+    };
+
+    // the shader skips bathymetry when its depth is zero, but it still needs something bound
+    let (bathymetry, bathymetry_depth) = match EARTH_BATHYMETRY_TEXTURE {
+        Some(path) => (
+            asset_server.load_with_settings(path, |settings: &mut ImageLoaderSettings| {
+                settings.is_srgb = false
+            }),
+            BATHYMETRY_MAX_DEPTH,
+        ),
+        None => (images.add(Image::default()), 0.0),
+    };
+
+    let detail = DetailTextures::load(&asset_server, &mut images);
+
+    // the relief's shadows, from the same heights as the normal map
+    let horizon = HorizonTask::spawn(displacement, HORIZON_MAP_SIZE, &mut images);
+    let [horizon_north_east, horizon_south_west] = horizon.images.clone();
+    data.horizon_task = Some(horizon);
+
+    let day_texture: Handle<Image> = asset_server.load(EARTH_DIFFUSE_TEXTURE);
+    let earth_material = materials.add(EarthMaterial {
+        // bevy's lighting only sees this surface description, the extension fills it in per pixel
+        base: StandardMaterial {
+            perceptual_roughness: shading.land_roughness,
+            opaque_render_method: OpaqueRendererMethod::Forward,
+            ..default()
+        },
+        extension: EarthExtension {
+            day_texture: day_texture.clone(),
+            night_texture: asset_server.load(EARTH_NIGHT_TEXTURE),
+            ocean_mask: asset_server.load(EARTH_OCEAN_MASK_TEXTURE),
+            specular_map: asset_server.load(EARTH_SPECULAR_TEXTURE),
+            normal_map,
+            atmosphere_uniform: atmosphere.atmosphere_uniform,
+            transmittance_lut: atmosphere.transmittance_lut,
+            scattering_lut: atmosphere.scattering_lut,
+            multiple_scattering_lut: atmosphere.multiple_scattering_lut,
+            cloud_texture: clouds.cloud_texture,
+            next_cloud_texture: clouds.next_cloud_texture,
+            cloud_frames: clouds.cloud_frames,
+            ocean_uniform: OceanUniform {
+                wind_speed: OCEAN_WIND_SPEED,
+                bathymetry_depth,
+                _padding: Vec2::ZERO,
+            },
+            bathymetry,
+            next_day_texture: day_texture,
+            heightmap: data.displacement_handle.clone(),
+            surface: SurfaceUniform {
+                cloud_drift: clouds.cloud_drift,
+                cloud_opacity: clouds.cloud_opacity,
+                day_blend: 0.0,
+                displacement_scale: shading.displacement_scale,
+                debug_mode: 0,
+                show_land_cover: land_cover.visible as u32,
+                _padding: Vec2::ZERO,
+            },
+            uv_checker: images.add(debug::uv_debug_texture()),
+            shading: shading.to_uniform(),
+            analysis: analysis.to_uniform(),
+            analysis_ramp: analysis_ramp.0.clone(),
+            detail_rock: detail.rock,
+            detail_grass: detail.grass,
+            detail_snow: detail.snow,
+            detail_sand: detail.sand,
+            detail: detail.uniform,
+            // the shader finds no listed class in the placeholder and keeps the day texture
+            land_cover: land_cover
+                .raster
+                .clone()
+                .unwrap_or_else(|| images.add(Image::default())),
+            land_cover_palette: land_cover.palette.clone(),
+            horizon_north_east,
+            horizon_south_west,
+        },
     });
+
+    // the tag numbers the chunks for the debug chunk colouring
+    for index in 0..CHUNK_COUNT {
+        let mesh = generate_chunk(index);
+        let bounds = chunk_bounds(&mesh, shading.displacement_scale).unwrap_or_default();
+        commands.spawn((
+            EarthChunk,
+            MeshTag(index as u32),
+            bounds,
+            Mesh3d(meshes.add(mesh)),
+            MeshMaterial3d(earth_material.clone()),
+            Transform::default(),
+            ChildOf(data.earth_entity),
+        ));
+    }
+
+    data.earth_material = Some(earth_material);
+}
+
+// each cube face is split into 2x2 chunks, so no chunk spans too much longitude
+const CHUNK_COUNT: usize = 24;
+
+/// Flat mesh of one chunk, numbered face by face
+/// the vertex shader displaces the terrain, picking follows it through `Relief`
+fn generate_chunk(index: usize) -> Mesh {
+    let faces = [
+        Vec3::X,
+        Vec3::NEG_X,
+        Vec3::Y,
+        Vec3::NEG_Y,
+        Vec3::Z,
+        Vec3::NEG_Z,
+    ];
+    let offsets = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(0.0, 1.0),
+        Vec2::new(1.0, 1.0),
+    ];
+    let (face, offset) = (faces[index / 4], offsets[index % 4]);
+    generate_face(face, EARTH_MESH_RESOLUTION, offset.x, offset.y, None, 0.0)
+}
+
+/// Refits the chunks' bounds when the relief's exaggeration changes,
+/// the meshes stay flat so nothing is rebuilt
+fn update_chunk_bounds(
+    shading: Res<EarthShadingSettings>,
+    meshes: Res<Assets<Mesh>>,
+    mut chunks: Query<(&Mesh3d, &mut Aabb), With<EarthChunk>>,
+) {
+    if !shading.is_changed() {
+        return;
+    }
+    for (mesh, mut bounds) in &mut chunks {
+        if let Some(new_bounds) = meshes
+            .get(&mesh.0)
+            .and_then(|mesh| chunk_bounds(mesh, shading.displacement_scale))
+        {
+            *bounds = new_bounds;
+        }
+    }
+}
+
+/// Keeps the atmosphere shell's view of the camera and sun current,
+/// the surface reads both from the view and its lights
+fn update_atmosphere(
+    data: Res<EarthData>,
+    mut materials: ResMut<Assets<AtmosphereMaterial>>,
+    cameras: Query<&GlobalTransform, With<Camera3d>>,
+    suns: Query<&GlobalTransform, With<Sun>>,
+) {
+    let (Ok(camera), Ok(sun)) = (cameras.single(), suns.single()) else {
+        return;
+    };
+    let Some(material) = materials.get_mut(&data.atmosphere_material) else {
+        return;
+    };
+
+    let uniform = &mut material.atmosphere_uniform;
+    uniform.camera_position = camera.translation();
+    uniform.sun_direction = sun.back().into();
+}
+
+/// Drifts the cloud layer with the simulation clock, or steps through its time series,
+/// and keeps the surface's cloud shadows in step
+fn update_clouds(
+    mut data: ResMut<EarthData>,
+    clock: Res<SimulationClock>,
+    asset_server: Res<AssetServer>,
+    mut cloud_materials: ResMut<Assets<CloudMaterial>>,
+    mut earth_materials: ResMut<Assets<EarthMaterial>>,
+) {
+    let data = data.as_mut();
+    let Some(clouds) = cloud_materials.get_mut(&data.cloud_material) else {
+        return;
+    };
+
+    match &mut data.cloud_sequence {
+        Some(sequence) => {
+            // frames already show the clouds moving, so they don't drift
+            // until both frames around the clock have loaded the previous ones stay up
+            if let Some(sample) = sequence.update(clock.julian_date, &asset_server) {
+                clouds.cloud_texture = sample.current;
+                clouds.next_cloud_texture = sample.next;
+                clouds.cloud_frames = RasterFramesUniform {
+                    bounds: sequence.bounds.to_vec4(),
+                    blend: sample.blend,
+                    ..default()
+                };
+                clouds.cloud_drift = 0.0;
+            }
+        }
+        None => {
+            // wrapped in f64 first, the angle since J2000 is far too large for f32
+            let drift =
+                ((clock.julian_date - J2000) * CLOUD_DRIFT_SPEED).rem_euclid(std::f64::consts::TAU);
+            clouds.cloud_drift = drift as f32;
+        }
+    }
+
+    if let Some(earth_material) = data
+        .earth_material
+        .as_ref()
+        .and_then(|handle| earth_materials.get_mut(handle))
+    {
+        earth_material.extension.cloud_texture = clouds.cloud_texture.clone();
+        earth_material.extension.next_cloud_texture = clouds.next_cloud_texture.clone();
+        earth_material.extension.cloud_frames = clouds.cloud_frames;
+        earth_material.extension.surface.cloud_drift = clouds.cloud_drift;
+        earth_material.extension.surface.cloud_opacity = clouds.cloud_opacity;
+    }
+}
+
+/// Blends the monthly day textures around the simulation clock's date
+fn update_seasons(
+    mut data: ResMut<EarthData>,
+    clock: Res<SimulationClock>,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<EarthMaterial>>,
+) {
+    let data = data.as_mut();
+    let Some(monthly_textures) = &mut data.monthly_textures else {
+        return;
+    };
+    let Some(material) = data
+        .earth_material
+        .as_ref()
+        .and_then(|handle| materials.get_mut(handle))
+    else {
+        return;
+    };
+
+    // the previous months stay up until both months around the clock have loaded
+    if let Some(sample) = monthly_textures.update(clock.julian_date, &asset_server) {
+        material.extension.day_texture = sample.current;
+        material.extension.next_day_texture = sample.next;
+        material.extension.surface.day_blend = sample.blend;
+    }
 }
@@ -1,17 +1,17 @@
 use bevy::{
     asset::RenderAssetUsages,
     prelude::*,
-    render::{
-        render_resource::{Extent3d, TextureDimension, TextureFormat},
-    },
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
 };
 use image::{ImageBuffer, Rgba};
+use std::f32::consts::{PI, TAU};
 
-use crate::config::EARTH_RADIUS;
+use crate::config::{DISPLACEMENT_SCALE, EARTH_RADIUS};
 
 // Generates a normal map from a height map
-// each pixel's normal is calculated by sampling neighboring heights
-// and computing tanget vectors in world space
+// each pixel's normal is calculated from the height slopes to its neighbours,
+// and stored in tangent space so the shader can rotate it onto the sphere
+// slopes are exaggerated by DISPLACEMENT_SCALE, the shader rescales them to the current exaggeration
 pub fn generate_normal_map(height_map: &Image) -> Image {
     let width = height_map.texture_descriptor.size.width as usize;
     let height = height_map.texture_descriptor.size.height as usize;
@@ -32,7 +32,7 @@ pub fn generate_normal_map(height_map: &Image) -> Image {
                 },
                 TextureDimension::D2,
                 normal_data,
-                TextureFormat::Rgba8UnormSrgb,
+                TextureFormat::Rgba8Unorm,
                 RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD,
             );
         }
@@ -40,25 +40,22 @@ pub fn generate_normal_map(height_map: &Image) -> Image {
 
     for y in 0..height {
         for x in 0..width {
-            let h_north = sample_height(height_data, width, height, x, y + 1);
-            let h_south = sample_height(height_data, width, height, x, y.wrapping_sub(1));
+            // row 0 is the north pole, columns wrap around in longitude
+            let h_north = sample_height(height_data, width, height, x, y.saturating_sub(1));
+            let h_south = sample_height(height_data, width, height, x, y + 1);
             let h_east = sample_height(height_data, width, height, x + 1, y);
-            let h_west = sample_height(height_data, width, height, x.wrapping_sub(1), y);
+            let h_west = sample_height(height_data, width, height, x + width - 1, y);
 
-            let u = x as f32 / (width - 1) as f32;
-            let v = y as f32 / (height - 1) as f32;
+            // ground distance between neighbouring texels, in km
+            let latitude = (0.5 - (y as f32 + 0.5) / height as f32) * PI;
+            let dx = (TAU * EARTH_RADIUS * latitude.cos() / width as f32).max(1.0);
+            let dy = PI * EARTH_RADIUS / height as f32;
 
-            // calculate world positions
-            let pos_north = height_to_world_position(u, wrap_v(v + 1.0 / height as f32), h_north);
-            let pos_south = height_to_world_position(u, wrap_v(v - 1.0 / height as f32), h_south);
-            let pos_east = height_to_world_position(wrap_u(u + 1.0 / width as f32), v, h_east);
-            let pos_west = height_to_world_position(wrap_u(u - 1.0 / width as f32), v, h_west);
+            let slope_east = (h_east - h_west) * DISPLACEMENT_SCALE / (2.0 * dx);
+            let slope_north = (h_north - h_south) * DISPLACEMENT_SCALE / (2.0 * dy);
 
-            // calculate tangent vectors
-            let tangent_ns = (pos_north - pos_south).normalize();
-            let tangent_ew = (pos_east - pos_west).normalize();
-
-            let normal = tangent_ns.cross(tangent_ew).normalize();
+            // tangent space normal: x east, y north, z up, the same basis the shader builds
+            let normal = Vec3::new(-slope_east, -slope_north, 1.0).normalize();
 
             // to 255 range
             let r = ((normal.x + 1.0) * 0.5 * 255.0) as u8;
@@ -82,7 +79,7 @@ pub fn generate_normal_map(height_map: &Image) -> Image {
         },
         TextureDimension::D2,
         normal_data,
-        TextureFormat::Rgba8UnormSrgb,
+        TextureFormat::Rgba8Unorm,
         RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD,
     )
 }
@@ -103,38 +100,6 @@ fn sample_height(height_data: &[u8], width: usize, height: usize, x: usize, y: u
     }
 }
 
-/// Convert UV coordinates and height to world position on sphere
-fn height_to_world_position(u: f32, v: f32, height: f32) -> Vec3 {
-    // UV to longitude/latitude
-    let longitude = (u * 2.0 - 1.0) * std::f32::consts::PI; // -π to π
-    let latitude = (0.5 - v) * std::f32::consts::PI; // -π/2 to π/2
-
-    // apply height displacement
-    let radius = EARTH_RADIUS + height * crate::config::DISPLACEMENT_SCALE;
-
-    let x = radius * latitude.cos() * longitude.cos();
-    let y = radius * latitude.sin();
-    let z = radius * latitude.cos() * longitude.cos();
-
-    Vec3::new(x, y, z)
-}
-
-/// Wrap U coordinate (longitude)
-fn wrap_u(u: f32) -> f32 {
-    if u < 0.0 {
-        u + 1.0
-    } else if u > 1.0 {
-        u - 1.0
-    } else {
-        u
-    }
-}
-
-/// Clamp V coordinate (latitude)
-fn wrap_v(v: f32) -> f32 {
-    v.clamp(0.0, 1.0)
-}
-
 /// Save normal map to an image file
 pub fn save_image_as_png(image: &Image, path: &str) {
     let width = image.texture_descriptor.size.width;
@@ -156,4 +121,4 @@ pub fn save_image_as_png(image: &Image, path: &str) {
 
     // save to disk
     buffer.save(path).expect("Failed to save image");
-}
\ No newline at end of file
+}
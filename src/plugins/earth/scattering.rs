@@ -0,0 +1,875 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    image::ImageSampler,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future::poll_once},
+};
+use std::f32::consts::PI;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use super::EarthData;
+use crate::config::EARTH_RADIUS;
+use crate::plugins::earth::materials::AtmosphereUniform;
+
+// Precomputed atmospheric scattering
+// transmittance and multiple scattering follow Hillaire, "A Scalable and Production Ready Sky and
+// Atmosphere Rendering Technique" (2020), single scattering is stored in a 3D table indexed by
+// height, view zenith and sun zenith as in Elek, "Rendering Parametrizable Planetary Atmospheres
+// with Multiple Scattering in Real-Time" (2009), which drops Bruneton's fourth (azimuth) dimension
+// the lookup parameterizations must match atmosphere.wgsl
+
+// heights (km) over which rayleigh and mie densities fall off by 1/e
+const RAYLEIGH_SCALE_HEIGHT: f32 = 8.0;
+const MIE_SCALE_HEIGHT: f32 = 1.2;
+// mie extinction is roughly 1.1 times its scattering
+const MIE_EXTINCTION_RATIO: f32 = 1.1;
+const GROUND_ALBEDO: f32 = 0.3;
+// the coefficients are per meter, the scene is in km
+const METERS_PER_KM: f32 = 1000.0;
+
+// table sizes
+const TRANSMITTANCE_SIZE: (usize, usize) = (256, 64);
+const MULTIPLE_SCATTERING_SIZE: (usize, usize) = (32, 32);
+// view zenith, sun zenith, height
+const SCATTERING_SIZE: (usize, usize, usize) = (128, 32, 32);
+
+const TRANSMITTANCE_STEPS: usize = 40;
+const MULTIPLE_SCATTERING_STEPS: usize = 20;
+// directions integrated over the sphere for multiple scattering, squared
+const MULTIPLE_SCATTERING_DIRECTIONS: usize = 8;
+const SCATTERING_STEPS: usize = 32;
+// sun azimuths (relative to the view) averaged into each single scattering texel
+const SCATTERING_AZIMUTHS: usize = 4;
+
+const CACHE_MAGIC: &[u8; 8] = b"ATMLUT02";
+
+/// Atmosphere properties the tables are computed from, in km
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtmosphereParameters {
+    pub planet_radius: f32,
+    pub atmosphere_radius: f32,
+    // per km at sea level
+    pub rayleigh_scattering: Vec3,
+    pub mie_scattering: f32,
+}
+
+impl From<&AtmosphereUniform> for AtmosphereParameters {
+    fn from(uniform: &AtmosphereUniform) -> Self {
+        AtmosphereParameters {
+            planet_radius: EARTH_RADIUS,
+            atmosphere_radius: uniform.atomosphere_radius,
+            rayleigh_scattering: uniform.rayleigh_coeff * METERS_PER_KM,
+            mie_scattering: uniform.mie_coeff * METERS_PER_KM,
+        }
+    }
+}
+
+impl AtmosphereParameters {
+    fn thickness(&self) -> f32 {
+        self.atmosphere_radius - self.planet_radius
+    }
+
+    /// Rayleigh and mie scattering coefficients at a radius
+    fn scattering(&self, radius: f32) -> (Vec3, f32) {
+        let height = (radius - self.planet_radius).max(0.0);
+        (
+            self.rayleigh_scattering * (-height / RAYLEIGH_SCALE_HEIGHT).exp(),
+            self.mie_scattering * (-height / MIE_SCALE_HEIGHT).exp(),
+        )
+    }
+
+    fn extinction(&self, radius: f32) -> Vec3 {
+        let (rayleigh, mie) = self.scattering(radius);
+        rayleigh + Vec3::splat(mie * MIE_EXTINCTION_RATIO)
+    }
+
+    /// Cosine of the view zenith angle at which a ray from `radius` grazes the ground
+    fn horizon(&self, radius: f32) -> f32 {
+        let ratio = self.planet_radius / radius.max(self.planet_radius);
+        -(1.0 - ratio * ratio).max(0.0).sqrt()
+    }
+
+    /// Distance along a ray to where it hits the ground, or leaves the atmosphere
+    /// and whether it hit the ground
+    fn ray_length(&self, radius: f32, mu: f32) -> (f32, bool) {
+        // (R - r)(R + r) rather than R^2 - r^2, which loses grazing rays to f32 cancellation
+        let discriminant =
+            |sphere: f32| (sphere - radius) * (sphere + radius) + radius * radius * mu * mu;
+        let ground = discriminant(self.planet_radius);
+        if mu < 0.0 && ground >= 0.0 {
+            return (-radius * mu - ground.sqrt(), true);
+        }
+        let top = discriminant(self.atmosphere_radius);
+        ((-radius * mu + top.max(0.0).sqrt()).max(0.0), false)
+    }
+
+    /// Sample distances and step lengths along a ray, `steps` in total
+    /// air density peaks near the ground, so samples bunch up around the ray's lowest point
+    fn ray_samples(&self, radius: f32, mu: f32, length: f32, steps: usize) -> Vec<(f32, f32)> {
+        let lowest = (-radius * mu).clamp(0.0, length);
+        // at least one sample on each side of the lowest point that has any length
+        let before = ((steps as f32 * lowest / length.max(1e-6)).round() as usize).clamp(
+            usize::from(lowest > 0.0),
+            steps - usize::from(lowest < length),
+        );
+
+        // squared spacing, dense at the segment's start
+        let segment = |start: f32, end: f32, count: usize| {
+            let edge = move |k: usize| {
+                let s = k as f32 / count as f32;
+                start + (end - start) * s * s
+            };
+            (0..count).map(move |k| {
+                let (a, b) = (edge(k), edge(k + 1));
+                (0.5 * (a + b), (b - a).abs())
+            })
+        };
+
+        // down to the lowest point, walked backwards so the result stays in ray order
+        let mut samples: Vec<(f32, f32)> = segment(lowest, 0.0, before).collect();
+        samples.reverse();
+        samples.extend(segment(lowest, length, steps - before));
+        samples
+    }
+
+    // Lookup parameterizations, (0, 1) texture coordinates to and from physical values
+
+    fn height_to_unit(&self, radius: f32) -> f32 {
+        ((radius - self.planet_radius) / self.thickness())
+            .clamp(0.0, 1.0)
+            .sqrt()
+    }
+
+    fn unit_to_radius(&self, unit: f32) -> f32 {
+        self.planet_radius + unit * unit * self.thickness()
+    }
+
+    /// View zenith cosine, with half the texture on each side of the horizon (Yusov 2013)
+    fn unit_to_view(&self, radius: f32, unit: f32) -> f32 {
+        let horizon = self.horizon(radius);
+        // keep texel centers off the horizon itself, where the ray length jumps
+        if unit >= 0.5 {
+            horizon + ((unit - 0.5) * 2.0).powf(5.0) * (1.0 - horizon) + 1e-4
+        } else {
+            horizon - ((0.5 - unit) * 2.0).powf(5.0) * (1.0 + horizon) - 1e-4
+        }
+    }
+
+    fn transmittance_view_to_unit(&self, radius: f32, mu: f32) -> f32 {
+        let horizon = self.horizon(radius);
+        ((mu - horizon) / (1.0 - horizon)).clamp(0.0, 1.0).sqrt()
+    }
+
+    fn transmittance_unit_to_view(&self, radius: f32, unit: f32) -> f32 {
+        let horizon = self.horizon(radius);
+        horizon + unit * unit * (1.0 - horizon)
+    }
+}
+
+/// Sun zenith cosine, most of the texture is spent around sunrise and sunset
+fn unit_to_sun(unit: f32) -> f32 {
+    -((1.0 - unit * (1.0 - (-3.6f32).exp())).ln() + 0.8) / 2.8
+}
+
+fn texel_center(index: usize, size: usize) -> f32 {
+    (index as f32 + 0.5) / size as f32
+}
+
+/// RGB(A) values on a 2D or 3D grid
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lut {
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+    // row-major, 4 floats per texel
+    pub data: Vec<f32>,
+}
+
+impl Lut {
+    fn new(width: usize, height: usize, depth: usize) -> Self {
+        Lut {
+            width,
+            height,
+            depth,
+            data: vec![0.0; width * height * depth * 4],
+        }
+    }
+
+    fn set(&mut self, x: usize, y: usize, z: usize, value: Vec4) {
+        let index = ((z * self.height + y) * self.width + x) * 4;
+        self.data[index..index + 4].copy_from_slice(&value.to_array());
+    }
+
+    fn get(&self, x: usize, y: usize) -> Vec4 {
+        let index = (y * self.width + x) * 4;
+        Vec4::from_slice(&self.data[index..index + 4])
+    }
+
+    /// A table of one value, 3D tables need two layers to stay 3D textures
+    fn constant(depth: usize, value: Vec4) -> Self {
+        Lut {
+            width: 1,
+            height: 1,
+            depth,
+            data: value.to_array().repeat(depth),
+        }
+    }
+
+    /// Bilinear lookup in a 2D table, texture coordinates clamped to the edge texels
+    fn sample(&self, u: f32, v: f32) -> Vec4 {
+        let x = (u * self.width as f32 - 0.5).clamp(0.0, (self.width - 1) as f32);
+        let y = (v * self.height as f32 - 0.5).clamp(0.0, (self.height - 1) as f32);
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(self.width - 1), (y0 + 1).min(self.height - 1));
+        let (fx, fy) = (x.fract(), y.fract());
+
+        let top = self.get(x0, y0).lerp(self.get(x1, y0), fx);
+        let bottom = self.get(x0, y1).lerp(self.get(x1, y1), fx);
+        top.lerp(bottom, fy)
+    }
+
+    /// Half float texture with linear filtering, 32 bit floats are not filterable everywhere
+    pub fn to_image(&self) -> Image {
+        let bytes: Vec<u8> = self
+            .data
+            .iter()
+            .flat_map(|&value| f32_to_f16(value).to_le_bytes())
+            .collect();
+
+        let mut image = Image::new(
+            Extent3d {
+                width: self.width as u32,
+                height: self.height as u32,
+                depth_or_array_layers: self.depth as u32,
+            },
+            if self.depth > 1 {
+                TextureDimension::D3
+            } else {
+                TextureDimension::D2
+            },
+            bytes,
+            TextureFormat::Rgba16Float,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        image.sampler = ImageSampler::linear();
+        image
+    }
+
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
+        for size in [self.width, self.height, self.depth] {
+            writer.write_all(&(size as u32).to_le_bytes())?;
+        }
+        for value in &self.data {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Reads a table written by `write`, which must be `size` (width, height, depth)
+    fn read(reader: &mut impl Read, size: (usize, usize, usize)) -> io::Result<Self> {
+        let mut sizes = [0usize; 3];
+        for size in &mut sizes {
+            *size = read_u32(reader)? as usize;
+        }
+        if sizes != [size.0, size.1, size.2] {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("lookup table is {sizes:?}, expected {size:?}"),
+            ));
+        }
+        let mut lut = Lut::new(size.0, size.1, size.2);
+        for value in &mut lut.data {
+            *value = f32::from_bits(read_u32(reader)?);
+        }
+        Ok(lut)
+    }
+}
+
+/// Everything besides `AtmosphereParameters` that the tables depend on, stored in the cache
+/// so changing any of it recomputes them
+fn model_settings() -> [f32; 16] {
+    [
+        RAYLEIGH_SCALE_HEIGHT,
+        MIE_SCALE_HEIGHT,
+        MIE_EXTINCTION_RATIO,
+        GROUND_ALBEDO,
+        TRANSMITTANCE_SIZE.0 as f32,
+        TRANSMITTANCE_SIZE.1 as f32,
+        MULTIPLE_SCATTERING_SIZE.0 as f32,
+        MULTIPLE_SCATTERING_SIZE.1 as f32,
+        SCATTERING_SIZE.0 as f32,
+        SCATTERING_SIZE.1 as f32,
+        SCATTERING_SIZE.2 as f32,
+        TRANSMITTANCE_STEPS as f32,
+        MULTIPLE_SCATTERING_STEPS as f32,
+        MULTIPLE_SCATTERING_DIRECTIONS as f32,
+        SCATTERING_STEPS as f32,
+        SCATTERING_AZIMUTHS as f32,
+    ]
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Rounds to the nearest half float, the tables only hold small positive values
+fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    let mantissa = bits & 0x7f_ffff;
+
+    if value.is_nan() {
+        return sign | 0x7e00;
+    }
+    if exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if exponent <= 0 {
+        // subnormal half floats, anything smaller flushes to zero
+        if exponent < -10 {
+            return sign;
+        }
+        let mantissa = (mantissa | 0x80_0000) >> (1 - exponent);
+        return sign | ((mantissa + 0x1000) >> 13) as u16;
+    }
+    // rounding may carry into the exponent, which is still the right answer
+    sign | (((exponent as u32) << 10) + ((mantissa + 0x1000) >> 13)) as u16
+}
+
+// computing the tables takes a while without a cache, so it runs on the async compute pool
+// and the atmosphere starts out clear
+
+/// The lookup tables being loaded or computed in the background, and the images they fill in
+pub struct AtmosphereLutTask {
+    task: Task<AtmosphereLuts>,
+    pub transmittance: Handle<Image>,
+    pub scattering: Handle<Image>,
+    pub scattering_multiple: Handle<Image>,
+}
+
+impl AtmosphereLutTask {
+    /// Starts [`AtmosphereLuts::load_or_compute`], its images show no atmosphere until then
+    pub fn spawn(
+        parameters: AtmosphereParameters,
+        path: &'static str,
+        images: &mut Assets<Image>,
+    ) -> Self {
+        let task = AsyncComputeTaskPool::get()
+            .spawn(async move { AtmosphereLuts::load_or_compute(parameters, path) });
+        AtmosphereLutTask {
+            task,
+            // full transmittance and no scattered light
+            transmittance: images.add(Lut::constant(1, Vec4::ONE).to_image()),
+            scattering: images.add(Lut::constant(2, Vec4::ZERO).to_image()),
+            scattering_multiple: images.add(Lut::constant(2, Vec4::W).to_image()),
+        }
+    }
+}
+
+/// Swaps the lookup tables in for the placeholders once their task is done
+pub(super) fn finish_atmosphere_luts(
+    mut data: ResMut<EarthData>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Some(pending) = data.lut_task.as_mut() else {
+        return;
+    };
+    let Some(luts) = block_on(poll_once(&mut pending.task)) else {
+        return;
+    };
+    for (handle, lut) in [
+        (&pending.transmittance, &luts.transmittance),
+        (&pending.scattering, &luts.scattering),
+        (&pending.scattering_multiple, &luts.scattering_multiple),
+    ] {
+        if let Some(image) = images.get_mut(handle) {
+            *image = lut.to_image();
+        }
+    }
+    data.lut_task = None;
+}
+
+/// Every table the atmosphere shader reads
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtmosphereLuts {
+    pub parameters: AtmosphereParameters,
+    // transmittance to the top of the atmosphere, by view zenith and height
+    pub transmittance: Lut,
+    // Hillaire's second order isotropic scattering, by sun zenith and height
+    pub multiple_scattering: Lut,
+    // rgb: rayleigh single scattering, a: red channel of mie single scattering
+    // by view zenith, sun zenith and height, integrated to the ground or the top of the atmosphere
+    pub scattering: Lut,
+    // rgb: multiple scattering integrated the same way, a: mean transmittance along the ray
+    pub scattering_multiple: Lut,
+}
+
+impl AtmosphereLuts {
+    pub fn compute(parameters: AtmosphereParameters) -> Self {
+        let transmittance = compute_transmittance(&parameters);
+        let multiple_scattering = compute_multiple_scattering(&parameters, &transmittance);
+        let (scattering, scattering_multiple) =
+            compute_scattering(&parameters, &transmittance, &multiple_scattering);
+
+        AtmosphereLuts {
+            parameters,
+            transmittance,
+            multiple_scattering,
+            scattering,
+            scattering_multiple,
+        }
+    }
+
+    /// Loads the tables from `path` if they were computed with the same parameters,
+    /// otherwise computes them and writes them there for next time
+    pub fn load_or_compute(parameters: AtmosphereParameters, path: &str) -> Self {
+        match Self::load(path) {
+            Ok(luts) if luts.parameters == parameters => return luts,
+            Ok(_) => info!("atmosphere parameters changed, recomputing lookup tables"),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            // an older format or different model settings
+            Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+                info!("atmosphere lookup tables are out of date ({error}), recomputing them")
+            }
+            Err(error) => warn!("could not read atmosphere lookup tables from {path}: {error}"),
+        }
+
+        let luts = Self::compute(parameters);
+        if let Err(error) = luts.save(path) {
+            warn!("could not cache atmosphere lookup tables to {path}: {error}");
+        }
+        luts
+    }
+
+    /// Writes the tables to `path`, through a temporary file so an interrupted save
+    /// never leaves a partial cache behind
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let temporary = format!("{path}.tmp");
+        let result = self.write(&temporary);
+        if result.is_err() {
+            let _ = fs::remove_file(&temporary);
+        }
+        result?;
+        fs::rename(&temporary, path)
+    }
+
+    fn write(&self, path: &str) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(fs::File::create(path)?);
+        writer.write_all(CACHE_MAGIC)?;
+        let parameters = self.parameters;
+        for value in [
+            parameters.planet_radius,
+            parameters.atmosphere_radius,
+            parameters.rayleigh_scattering.x,
+            parameters.rayleigh_scattering.y,
+            parameters.rayleigh_scattering.z,
+            parameters.mie_scattering,
+        ]
+        .into_iter()
+        .chain(model_settings())
+        {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        for lut in self.tables() {
+            lut.write(&mut writer)?;
+        }
+        writer.into_inner()?.sync_all()
+    }
+
+    pub fn load(path: &str) -> io::Result<Self> {
+        let mut reader = io::BufReader::new(fs::File::open(path)?);
+
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != CACHE_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not an atmosphere lookup table cache",
+            ));
+        }
+
+        let mut values = [0.0; 6];
+        for value in &mut values {
+            *value = f32::from_bits(read_u32(&mut reader)?);
+        }
+        let parameters = AtmosphereParameters {
+            planet_radius: values[0],
+            atmosphere_radius: values[1],
+            rayleigh_scattering: Vec3::new(values[2], values[3], values[4]),
+            mie_scattering: values[5],
+        };
+
+        let mut settings = [0.0; 16];
+        for value in &mut settings {
+            *value = f32::from_bits(read_u32(&mut reader)?);
+        }
+        if settings != model_settings() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "computed with different model settings",
+            ));
+        }
+
+        let (transmittance_width, transmittance_height) = TRANSMITTANCE_SIZE;
+        let (multiple_width, multiple_height) = MULTIPLE_SCATTERING_SIZE;
+        Ok(AtmosphereLuts {
+            parameters,
+            transmittance: Lut::read(&mut reader, (transmittance_width, transmittance_height, 1))?,
+            multiple_scattering: Lut::read(&mut reader, (multiple_width, multiple_height, 1))?,
+            scattering: Lut::read(&mut reader, SCATTERING_SIZE)?,
+            scattering_multiple: Lut::read(&mut reader, SCATTERING_SIZE)?,
+        })
+    }
+
+    fn tables(&self) -> [&Lut; 4] {
+        [
+            &self.transmittance,
+            &self.multiple_scattering,
+            &self.scattering,
+            &self.scattering_multiple,
+        ]
+    }
+}
+
+fn transmittance_to_top(
+    parameters: &AtmosphereParameters,
+    transmittance: &Lut,
+    radius: f32,
+    mu: f32,
+) -> Vec3 {
+    if mu < parameters.horizon(radius) {
+        return Vec3::ZERO;
+    }
+    transmittance
+        .sample(
+            parameters.transmittance_view_to_unit(radius, mu),
+            parameters.height_to_unit(radius),
+        )
+        .truncate()
+}
+
+fn compute_transmittance(parameters: &AtmosphereParameters) -> Lut {
+    let (width, height) = TRANSMITTANCE_SIZE;
+    let mut lut = Lut::new(width, height, 1);
+
+    for y in 0..height {
+        let radius = parameters.unit_to_radius(texel_center(y, height));
+        for x in 0..width {
+            let mu = parameters.transmittance_unit_to_view(radius, texel_center(x, width));
+            let (length, _) = parameters.ray_length(radius, mu);
+
+            let mut optical_depth = Vec3::ZERO;
+            for (t, step) in parameters.ray_samples(radius, mu, length, TRANSMITTANCE_STEPS) {
+                let sample_radius = (radius * radius + t * t + 2.0 * radius * mu * t).sqrt();
+                optical_depth += parameters.extinction(sample_radius) * step;
+            }
+
+            lut.set(x, y, 0, (-optical_depth).exp().extend(1.0));
+        }
+    }
+
+    lut
+}
+
+/// Radius and sun zenith cosine `t` km along a ray
+fn march(radius: f32, mu: f32, mu_s: f32, nu: f32, t: f32) -> (f32, f32) {
+    let sample_radius = (radius * radius + t * t + 2.0 * radius * mu * t).sqrt();
+    let sample_mu_s = (radius * mu_s + t * nu) / sample_radius;
+    (sample_radius, sample_mu_s.clamp(-1.0, 1.0))
+}
+
+fn compute_multiple_scattering(parameters: &AtmosphereParameters, transmittance: &Lut) -> Lut {
+    let (width, height) = MULTIPLE_SCATTERING_SIZE;
+    let mut lut = Lut::new(width, height, 1);
+    let directions = MULTIPLE_SCATTERING_DIRECTIONS;
+    let isotropic_phase = 1.0 / (4.0 * PI);
+
+    for y in 0..height {
+        let radius = parameters.unit_to_radius(texel_center(y, height));
+        for x in 0..width {
+            // this table is small, so it is linear in the sun zenith cosine
+            let mu_s = texel_center(x, width) * 2.0 - 1.0;
+
+            let mut second_order = Vec3::ZERO;
+            let mut transfer = Vec3::ZERO;
+
+            // evenly spread directions over the sphere
+            for i in 0..directions {
+                for j in 0..directions {
+                    let mu = 1.0 - 2.0 * texel_center(i, directions);
+                    let azimuth = texel_center(j, directions) * 2.0 * PI;
+                    let nu = mu * mu_s
+                        + (1.0 - mu * mu).max(0.0).sqrt()
+                            * (1.0 - mu_s * mu_s).max(0.0).sqrt()
+                            * azimuth.cos();
+
+                    let (length, hits_ground) = parameters.ray_length(radius, mu);
+                    let samples =
+                        parameters.ray_samples(radius, mu, length, MULTIPLE_SCATTERING_STEPS);
+                    let mut optical_depth = Vec3::ZERO;
+
+                    for (t, step) in samples {
+                        let (sample_radius, sample_mu_s) = march(radius, mu, mu_s, nu, t);
+                        let (rayleigh, mie) = parameters.scattering(sample_radius);
+                        let scattering = rayleigh + Vec3::splat(mie);
+
+                        optical_depth += parameters.extinction(sample_radius) * step;
+                        let view_transmittance = (-optical_depth).exp();
+                        let sun_transmittance = transmittance_to_top(
+                            parameters,
+                            transmittance,
+                            sample_radius,
+                            sample_mu_s,
+                        );
+
+                        second_order += view_transmittance
+                            * sun_transmittance
+                            * scattering
+                            * isotropic_phase
+                            * step;
+                        transfer += view_transmittance * scattering * step;
+                    }
+
+                    // sunlight bouncing off the ground
+                    if hits_ground {
+                        let (_, ground_mu_s) = march(radius, mu, mu_s, nu, length);
+                        let sun_transmittance = transmittance_to_top(
+                            parameters,
+                            transmittance,
+                            parameters.planet_radius,
+                            ground_mu_s,
+                        );
+                        second_order += (-optical_depth).exp()
+                            * sun_transmittance
+                            * ground_mu_s.max(0.0)
+                            * GROUND_ALBEDO
+                            / PI;
+                    }
+                }
+            }
+
+            // both integrals are over the sphere with an isotropic phase, i.e. an average
+            let samples = (directions * directions) as f32;
+            let second_order = second_order / samples;
+            let transfer = transfer / samples;
+
+            // infinite series of higher orders, 1 + f + f^2 + ...
+            let psi = second_order / (Vec3::ONE - transfer).max(Vec3::splat(1e-3));
+            lut.set(x, y, 0, psi.extend(1.0));
+        }
+    }
+
+    lut
+}
+
+fn compute_scattering(
+    parameters: &AtmosphereParameters,
+    transmittance: &Lut,
+    multiple_scattering: &Lut,
+) -> (Lut, Lut) {
+    let (width, height, depth) = SCATTERING_SIZE;
+    let mut single = Lut::new(width, height, depth);
+    let mut multiple = Lut::new(width, height, depth);
+
+    for z in 0..depth {
+        let radius = parameters.unit_to_radius(texel_center(z, depth));
+        for y in 0..height {
+            let mu_s = unit_to_sun(texel_center(y, height)).clamp(-1.0, 1.0);
+            for x in 0..width {
+                let mu = parameters.unit_to_view(radius, texel_center(x, width));
+                let (length, _) = parameters.ray_length(radius, mu);
+                let samples = parameters.ray_samples(radius, mu, length, SCATTERING_STEPS);
+
+                let mut rayleigh_sum = Vec3::ZERO;
+                let mut mie_sum = 0.0;
+                let mut multiple_sum = Vec3::ZERO;
+                let mut mean_transmittance = 0.0;
+
+                // the table has no azimuth, so average over a few sun azimuths
+                for a in 0..SCATTERING_AZIMUTHS {
+                    let azimuth = texel_center(a, SCATTERING_AZIMUTHS) * PI;
+                    let nu = mu * mu_s
+                        + (1.0 - mu * mu).max(0.0).sqrt()
+                            * (1.0 - mu_s * mu_s).max(0.0).sqrt()
+                            * azimuth.cos();
+
+                    let mut optical_depth = Vec3::ZERO;
+                    for &(t, step) in &samples {
+                        let (sample_radius, sample_mu_s) = march(radius, mu, mu_s, nu, t);
+                        let (rayleigh, mie) = parameters.scattering(sample_radius);
+
+                        optical_depth += parameters.extinction(sample_radius) * step;
+                        let view_transmittance = (-optical_depth).exp();
+                        let sun_transmittance = transmittance_to_top(
+                            parameters,
+                            transmittance,
+                            sample_radius,
+                            sample_mu_s,
+                        );
+                        let light = view_transmittance * sun_transmittance * step;
+
+                        rayleigh_sum += light * rayleigh;
+                        mie_sum += light.x * mie;
+
+                        let psi = multiple_scattering
+                            .sample(
+                                (sample_mu_s + 1.0) * 0.5,
+                                parameters.height_to_unit(sample_radius),
+                            )
+                            .truncate();
+                        multiple_sum +=
+                            view_transmittance * psi * (rayleigh + Vec3::splat(mie)) * step;
+                    }
+
+                    let total = (-optical_depth).exp();
+                    mean_transmittance += (total.x + total.y + total.z) / 3.0;
+                }
+
+                let samples = SCATTERING_AZIMUTHS as f32;
+                single.set(x, y, z, (rayleigh_sum / samples).extend(mie_sum / samples));
+                multiple.set(
+                    x,
+                    y,
+                    z,
+                    (multiple_sum / samples).extend(mean_transmittance / samples),
+                );
+            }
+        }
+    }
+
+    (single, multiple)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{MIE_COEFF, RAYLEIGH_COEFF};
+
+    fn earth() -> AtmosphereParameters {
+        AtmosphereParameters {
+            planet_radius: EARTH_RADIUS,
+            atmosphere_radius: EARTH_RADIUS + 100.0,
+            rayleigh_scattering: Vec3::from_array(RAYLEIGH_COEFF) * METERS_PER_KM,
+            mie_scattering: MIE_COEFF * METERS_PER_KM,
+        }
+    }
+
+    fn f16_to_f32(bits: u16) -> f32 {
+        let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+        let exponent = ((bits >> 10) & 0x1f) as i32;
+        let mantissa = (bits & 0x3ff) as f32;
+        if exponent == 0 {
+            sign * mantissa * 2f32.powi(-24)
+        } else {
+            sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15)
+        }
+    }
+
+    /// Small tables of the cached sizes, filled with distinct values
+    fn test_luts() -> AtmosphereLuts {
+        let lut = |(width, height, depth): (usize, usize, usize), offset: f32| {
+            let mut lut = Lut::new(width, height, depth);
+            for (i, value) in lut.data.iter_mut().enumerate() {
+                *value = offset + i as f32 * 1e-3;
+            }
+            lut
+        };
+        let (transmittance_width, transmittance_height) = TRANSMITTANCE_SIZE;
+        let (multiple_width, multiple_height) = MULTIPLE_SCATTERING_SIZE;
+        AtmosphereLuts {
+            parameters: earth(),
+            transmittance: lut((transmittance_width, transmittance_height, 1), 0.0),
+            multiple_scattering: lut((multiple_width, multiple_height, 1), 1.0),
+            scattering: lut(SCATTERING_SIZE, 2.0),
+            scattering_multiple: lut(SCATTERING_SIZE, 3.0),
+        }
+    }
+
+    fn temporary_path(name: &str) -> String {
+        let directory = std::env::temp_dir().join(format!("atmosphere-{}", std::process::id()));
+        directory.join(name).to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn transmittance_falls_towards_the_horizon() {
+        let parameters = earth();
+        let lut = compute_transmittance(&parameters);
+
+        let up = transmittance_to_top(&parameters, &lut, parameters.atmosphere_radius, 1.0);
+        assert!(up.min_element() > 0.999, "{up}");
+
+        // x runs from the horizon up to the zenith
+        for y in 0..lut.height {
+            for x in 1..lut.width {
+                let (lower, higher) = (lut.get(x - 1, y), lut.get(x, y));
+                assert!(
+                    lower.cmple(higher + 1e-6).all(),
+                    "row {y}: {lower} then {higher}"
+                );
+            }
+        }
+        let horizon = lut.get(0, 0).truncate();
+        assert!(horizon.max_element() < 0.1, "{horizon}");
+    }
+
+    #[test]
+    fn half_floats_round_trip() {
+        for bits in 0..=u16::MAX {
+            // infinities and NaNs
+            if (bits >> 10) & 0x1f == 0x1f {
+                continue;
+            }
+            let value = f16_to_f32(bits);
+            assert_eq!(f32_to_f16(value), bits, "{value}");
+        }
+        assert_eq!(f32_to_f16(1.0), 0x3c00);
+        assert_eq!(f32_to_f16(65504.0), 0x7bff);
+    }
+
+    #[test]
+    fn saved_tables_load_back() {
+        let path = temporary_path("round_trip.bin");
+        let luts = test_luts();
+        luts.save(&path).unwrap();
+
+        assert_eq!(AtmosphereLuts::load(&path).unwrap(), luts);
+        assert!(!Path::new(&format!("{path}.tmp")).exists());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn truncated_cache_is_an_error() {
+        let path = temporary_path("truncated.bin");
+        test_luts().save(&path).unwrap();
+        let length = fs::metadata(&path).unwrap().len();
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(length / 2)
+            .unwrap();
+
+        assert!(AtmosphereLuts::load(&path).is_err());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_tables_of_the_wrong_size() {
+        let mut bytes = Vec::new();
+        Lut::new(4, 4, 1).write(&mut bytes).unwrap();
+
+        let error = Lut::read(&mut bytes.as_slice(), (4, 8, 1)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(Lut::read(&mut bytes.as_slice(), (4, 4, 1)).is_ok());
+    }
+}
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+
+use super::sequence::SequenceSample;
+use crate::plugins::clock::julian::CalendarDate;
+
+// Monthly surface textures, e.g. NASA's Blue Marble Next Generation
+// each texture is a monthly composite, so it is shown fully at the middle of its month
+// and blended into the next one in between
+
+/// Month (0-11) before and after `julian_date`, and how far it is between their midpoints
+pub fn month_blend(julian_date: f64) -> (usize, usize, f32) {
+    let date = CalendarDate::from_julian_date(julian_date);
+    let month_start = CalendarDate::new(date.year, date.month, 1).to_julian_date();
+    let next_month_start = match date.month {
+        12 => CalendarDate::new(date.year + 1, 1, 1),
+        month => CalendarDate::new(date.year, month + 1, 1),
+    }
+    .to_julian_date();
+
+    let fraction = (julian_date - month_start) / (next_month_start - month_start);
+    // months counted from the middle of january
+    let position = (date.month - 1) as f64 + fraction - 0.5;
+    let current = position.floor().rem_euclid(12.0) as usize;
+    (current, (current + 1) % 12, position.rem_euclid(1.0) as f32)
+}
+
+/// Twelve monthly textures, only the ones around the clock are kept loaded
+pub struct MonthlyTextures {
+    paths: Vec<String>,
+    handles: Vec<Option<Handle<Image>>>,
+}
+
+impl MonthlyTextures {
+    /// `pattern` contains `{month}`, replaced by 01 to 12
+    pub fn from_pattern(pattern: &str) -> Self {
+        MonthlyTextures {
+            paths: (1..=12)
+                .map(|month| pattern.replace("{month}", &format!("{month:02}")))
+                .collect(),
+            handles: vec![None; 12],
+        }
+    }
+
+    /// Loads the months around `julian_date` and the one after, releases the others
+    /// and returns the pair to blend once both have loaded
+    pub fn update(
+        &mut self,
+        julian_date: f64,
+        asset_server: &AssetServer,
+    ) -> Option<SequenceSample> {
+        let (current, next, blend) = month_blend(julian_date);
+        let keep = [current, next, (next + 1) % 12];
+
+        for (month, handle) in self.handles.iter_mut().enumerate() {
+            if !keep.contains(&month) {
+                *handle = None;
+            } else if handle.is_none() {
+                *handle = Some(asset_server.load(self.paths[month].clone()));
+            }
+        }
+
+        let loaded = |month: usize| {
+            let handle = self.handles[month].as_ref()?;
+            asset_server.is_loaded(handle).then(|| handle.clone())
+        };
+        Some(SequenceSample {
+            current: loaded(current)?,
+            next: loaded(next)?,
+            blend,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn month_start(year: i32, month: u32) -> f64 {
+        CalendarDate::new(year, month, 1).to_julian_date()
+    }
+
+    #[test]
+    fn middle_of_the_month_shows_only_that_month() {
+        for month in 1..=12 {
+            let start = month_start(2024, month);
+            let end = match month {
+                12 => month_start(2025, 1),
+                month => month_start(2024, month + 1),
+            };
+            let (current, next, blend) = month_blend((start + end) / 2.0);
+            assert_eq!(current, month as usize - 1);
+            assert_eq!(next, month as usize % 12);
+            assert!(blend.abs() < 1e-6, "month {month}: {blend}");
+        }
+    }
+
+    #[test]
+    fn wraps_from_december_to_january() {
+        let new_year = month_start(2025, 1);
+        for julian_date in [new_year - 1e-4, new_year, new_year + 1e-4] {
+            let (current, next, blend) = month_blend(julian_date);
+            assert_eq!((current, next), (11, 0));
+            assert!((blend - 0.5).abs() < 1e-3, "{blend}");
+        }
+        // early january still blends in the remains of december
+        assert_eq!(month_blend(new_year + 5.0).0, 11);
+    }
+
+    #[test]
+    fn blend_is_continuous_across_month_boundaries() {
+        for month in 1..=12 {
+            let boundary = month_start(2023, month);
+            let (before_current, before_next, before) = month_blend(boundary - 1e-5);
+            let (after_current, after_next, after) = month_blend(boundary + 1e-5);
+            assert_eq!((before_current, before_next), (after_current, after_next));
+            assert!(
+                (before - after).abs() < 1e-4,
+                "month {month}: {before} vs {after}"
+            );
+        }
+    }
+}
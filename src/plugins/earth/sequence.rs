@@ -0,0 +1,301 @@
+use bevy::{asset::LoadState, image::ImageLoaderSettings, prelude::*};
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+use std::fmt;
+
+use crate::plugins::clock::series::{bracket, series_lines};
+
+// Time series of georeferenced rasters, e.g. hourly global cloud composites
+// manifest format, one entry per line, `#` starts a comment:
+//   bounds <west> <south> <east> <north>        degrees, optional, defaults to the whole globe
+//   <ISO 8601 UTC timestamp> <asset path>        e.g. 2024-05-01T06:00:00Z textures/clouds/0600.png
+// every frame shares the bounds and must be an equirectangular image
+
+/// Extent of an equirectangular raster, in radians
+/// `east` may exceed pi for rasters that cross the antimeridian
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeoBounds {
+    pub west: f32,
+    pub south: f32,
+    pub east: f32,
+    pub north: f32,
+}
+
+impl GeoBounds {
+    pub const GLOBAL: GeoBounds = GeoBounds {
+        west: -PI,
+        south: -FRAC_PI_2,
+        east: PI,
+        north: FRAC_PI_2,
+    };
+
+    pub fn from_degrees(west: f32, south: f32, east: f32, north: f32) -> Self {
+        let (west, east) = (west.to_radians(), east.to_radians());
+        GeoBounds {
+            west,
+            south: south.to_radians(),
+            // keep the raster running eastwards across the antimeridian
+            east: if east <= west { east + TAU } else { east },
+            north: north.to_radians(),
+        }
+    }
+
+    /// Packed as the shaders expect it: west, south, east, north
+    pub fn to_vec4(self) -> Vec4 {
+        Vec4::new(self.west, self.south, self.east, self.north)
+    }
+}
+
+impl Default for GeoBounds {
+    fn default() -> Self {
+        GeoBounds::GLOBAL
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SequenceError {
+    // line number
+    InvalidTimestamp(usize),
+    MissingPath(usize),
+    InvalidBounds(usize),
+    DuplicateTimestamp(usize),
+}
+
+impl fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceError::InvalidTimestamp(line) => {
+                write!(f, "line {line} has an invalid timestamp")
+            }
+            SequenceError::MissingPath(line) => write!(f, "line {line} has no image path"),
+            SequenceError::InvalidBounds(line) => write!(f, "line {line} has invalid bounds"),
+            SequenceError::DuplicateTimestamp(line) => {
+                write!(f, "line {line} repeats an earlier timestamp")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SequenceError {}
+
+/// One timestamped image of a `RasterSequence`
+#[derive(Clone, Debug)]
+pub struct RasterFrame {
+    // julian date (UTC)
+    pub time: f64,
+    // asset path
+    pub path: String,
+    // only set while the frame is close to the clock
+    handle: Option<Handle<Image>>,
+}
+
+/// The two frames around a time and how far between them it is
+#[derive(Clone, Debug)]
+pub struct SequenceSample {
+    pub current: Handle<Image>,
+    pub next: Handle<Image>,
+    // 0 at `current`, 1 at `next`
+    pub blend: f32,
+}
+
+/// An ordered series of rasters, loaded a few frames ahead of the clock and released behind it
+#[derive(Clone, Debug)]
+pub struct RasterSequence {
+    pub bounds: GeoBounds,
+    // frames kept loaded ahead of the clock, one more is kept behind it for running backwards
+    pub preload: usize,
+    // colour images are srgb, data such as cloud cover is linear
+    pub is_srgb: bool,
+    frames: Vec<RasterFrame>,
+}
+
+impl RasterSequence {
+    /// Parses a manifest, lines that fail are skipped and reported
+    pub fn parse(manifest: &str, preload: usize, is_srgb: bool) -> (Self, Vec<SequenceError>) {
+        let mut bounds = GeoBounds::GLOBAL;
+        let mut frames: Vec<RasterFrame> = Vec::new();
+        let mut errors = Vec::new();
+
+        for mut line in series_lines(manifest) {
+            let line_number = line.number;
+            if line.first == "bounds" {
+                let degrees: Vec<f32> =
+                    line.fields.filter_map(|field| field.parse().ok()).collect();
+                match degrees[..] {
+                    [west, south, east, north] if south < north => {
+                        bounds = GeoBounds::from_degrees(west, south, east, north);
+                    }
+                    _ => errors.push(SequenceError::InvalidBounds(line_number)),
+                }
+                continue;
+            }
+
+            let Some(time) = line.time() else {
+                errors.push(SequenceError::InvalidTimestamp(line_number));
+                continue;
+            };
+            let Some(path) = line.fields.next() else {
+                errors.push(SequenceError::MissingPath(line_number));
+                continue;
+            };
+            if frames.iter().any(|frame| frame.time == time) {
+                errors.push(SequenceError::DuplicateTimestamp(line_number));
+                continue;
+            }
+
+            frames.push(RasterFrame {
+                time,
+                path: path.to_string(),
+                handle: None,
+            });
+        }
+
+        frames.sort_by(|a, b| a.time.total_cmp(&b.time));
+        let sequence = RasterSequence {
+            bounds,
+            preload,
+            is_srgb,
+            frames,
+        };
+        (sequence, errors)
+    }
+
+    pub fn frames(&self) -> &[RasterFrame] {
+        &self.frames
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Indices of the frames at or before and after `julian_date`, and the blend between them
+    /// outside the sequence the first or last frame is held
+    pub fn bracket(&self, julian_date: f64) -> Option<(usize, usize, f32)> {
+        let (current, next, blend) = bracket(&self.frames, julian_date, |frame| frame.time)?;
+        Some((current, next, blend as f32))
+    }
+
+    /// Loads the frames around `julian_date`, releases the others
+    /// and returns the pair to show once both have finished loading
+    pub fn update(
+        &mut self,
+        julian_date: f64,
+        asset_server: &AssetServer,
+    ) -> Option<SequenceSample> {
+        self.drop_failed_frames(asset_server);
+        let (current, next, blend) = self.bracket(julian_date)?;
+
+        // dropping the last handle to a frame unloads its image
+        let keep = current.saturating_sub(1)..=current + self.preload.max(1);
+        let is_srgb = self.is_srgb;
+        for (index, frame) in self.frames.iter_mut().enumerate() {
+            if !keep.contains(&index) {
+                frame.handle = None;
+            } else if frame.handle.is_none() {
+                frame.handle = Some(asset_server.load_with_settings(
+                    frame.path.clone(),
+                    move |settings: &mut ImageLoaderSettings| settings.is_srgb = is_srgb,
+                ));
+            }
+        }
+
+        let loaded = |index: usize| {
+            let handle = self.frames[index].handle.as_ref()?;
+            asset_server.is_loaded(handle).then(|| handle.clone())
+        };
+        Some(SequenceSample {
+            current: loaded(current)?,
+            next: loaded(next)?,
+            blend,
+        })
+    }
+
+    /// Frames that can't be loaded are removed, so the sequence interpolates across the gap
+    fn drop_failed_frames(&mut self, asset_server: &AssetServer) {
+        self.frames.retain(|frame| {
+            let Some(handle) = &frame.handle else {
+                return true;
+            };
+            let failed = matches!(asset_server.load_state(handle), LoadState::Failed(_));
+            if failed {
+                warn!("skipping raster frame {}: it failed to load", frame.path);
+            }
+            !failed
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::clock::julian::CalendarDate;
+
+    const MANIFEST: &str = "\
+# hourly cloud cover
+bounds -30 -60 150 60
+
+2024-05-01T06:00:00Z clouds/0600.png
+2024-05-01T03:00:00Z clouds/0300.png  # out of order
+2024-05-01T09:00:00Z
+not-a-date clouds/bad.png
+2024-05-01T03:00:00Z clouds/again.png
+bounds 0 10 20
+";
+
+    fn julian(hour: u32, minute: u32) -> f64 {
+        CalendarDate::new(2024, 5, 1)
+            .with_time(hour, minute, 0.0)
+            .to_julian_date()
+    }
+
+    #[test]
+    fn parses_manifests() {
+        let (sequence, errors) = RasterSequence::parse(MANIFEST, 2, false);
+
+        assert_eq!(
+            sequence.bounds,
+            GeoBounds::from_degrees(-30.0, -60.0, 150.0, 60.0)
+        );
+        let paths: Vec<&str> = sequence
+            .frames()
+            .iter()
+            .map(|frame| frame.path.as_str())
+            .collect();
+        assert_eq!(paths, ["clouds/0300.png", "clouds/0600.png"]);
+        assert_eq!(sequence.frames()[0].time, julian(3, 0));
+        assert_eq!(
+            errors,
+            vec![
+                SequenceError::MissingPath(6),
+                SequenceError::InvalidTimestamp(7),
+                SequenceError::DuplicateTimestamp(8),
+                SequenceError::InvalidBounds(9),
+            ]
+        );
+    }
+
+    #[test]
+    fn bounds_cross_the_antimeridian_eastwards() {
+        let bounds = GeoBounds::from_degrees(170.0, -10.0, -170.0, 10.0);
+        assert!((bounds.east - bounds.west - 20f32.to_radians()).abs() < 1e-6);
+        assert_eq!(
+            RasterSequence::parse("", 1, false).0.bounds,
+            GeoBounds::GLOBAL
+        );
+    }
+
+    #[test]
+    fn brackets_the_clock() {
+        let (sequence, _) = RasterSequence::parse(MANIFEST, 2, false);
+
+        assert_eq!(sequence.bracket(julian(1, 0)), Some((0, 0, 0.0)));
+        let (current, next, blend) = sequence.bracket(julian(4, 0)).unwrap();
+        assert_eq!((current, next), (0, 1));
+        assert!((blend - 1.0 / 3.0).abs() < 1e-4, "{blend}");
+        assert_eq!(sequence.bracket(julian(3, 0)), Some((0, 1, 0.0)));
+        assert_eq!(sequence.bracket(julian(6, 0)), Some((1, 1, 0.0)));
+        assert_eq!(sequence.bracket(julian(12, 0)), Some((1, 1, 0.0)));
+
+        let (empty, _) = RasterSequence::parse("", 2, false);
+        assert_eq!(empty.bracket(julian(6, 0)), None);
+    }
+}
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+
+use super::EarthData;
+use crate::config::*;
+use crate::plugins::earth::materials::{EarthMaterial, ShadingUniform};
+
+// Look of the globe's surface, tunable while running instead of recompiling the shaders
+// each field starts from the config.rs constant of the same name, which documents it
+
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct EarthShadingSettings {
+    pub normal_strength: f32,
+    pub land_roughness: f32,
+    pub terminator_width: f32,
+    pub night_ambient: f32,
+    pub city_lights_strength: f32,
+    pub night_desaturation: f32,
+    pub calm_water_roughness: f32,
+    pub rough_water_roughness: f32,
+    pub cloud_shadow_strength: f32,
+    pub terrain_shadow_strength: f32,
+    pub displacement_scale: f32,
+}
+
+impl Default for EarthShadingSettings {
+    fn default() -> Self {
+        EarthShadingSettings {
+            normal_strength: NORMAL_STRENGTH,
+            land_roughness: LAND_ROUGHNESS,
+            terminator_width: TERMINATOR_WIDTH,
+            night_ambient: NIGHT_AMBIENT,
+            city_lights_strength: CITY_LIGHTS_STRENGTH,
+            night_desaturation: NIGHT_DESATURATION,
+            calm_water_roughness: CALM_WATER_ROUGHNESS,
+            rough_water_roughness: ROUGH_WATER_ROUGHNESS,
+            cloud_shadow_strength: CLOUD_SHADOW_STRENGTH,
+            terrain_shadow_strength: TERRAIN_SHADOW_STRENGTH,
+            displacement_scale: DISPLACEMENT_SCALE,
+        }
+    }
+}
+
+impl EarthShadingSettings {
+    pub fn to_uniform(&self) -> ShadingUniform {
+        ShadingUniform {
+            normal_strength: self.normal_strength,
+            terminator_width: self.terminator_width,
+            night_ambient: self.night_ambient,
+            night_desaturation: self.night_desaturation,
+            city_lights_strength: self.city_lights_strength,
+            calm_water_roughness: self.calm_water_roughness,
+            rough_water_roughness: self.rough_water_roughness,
+            cloud_shadow_strength: self.cloud_shadow_strength,
+            terrain_shadow_strength: self.terrain_shadow_strength,
+        }
+    }
+}
+
+/// Copies the settings into the earth material whenever they change
+/// the material is created from the current settings, so it starts out in step
+pub(super) fn sync_shading_settings(
+    settings: Res<EarthShadingSettings>,
+    data: Res<EarthData>,
+    mut materials: ResMut<Assets<EarthMaterial>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let Some(handle) = &data.earth_material else {
+        return;
+    };
+    let Some(material) = materials.get_mut(handle) else {
+        return;
+    };
+
+    material.base.perceptual_roughness = settings.land_roughness;
+    material.extension.shading = settings.to_uniform();
+    material.extension.surface.displacement_scale = settings.displacement_scale;
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use std::f32::consts::PI;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct LatLon {
     // radians
     pub latitude: f32,
@@ -20,13 +21,27 @@ impl From<Vec3> for LatLon {
 }
 
 impl LatLon {
+    pub fn from_degrees(latitude: f32, longitude: f32) -> Self {
+        LatLon {
+            latitude: latitude * (PI / 180.0),
+            longitude: longitude * (PI / 180.0),
+        }
+    }
+
+    /// inverse of `From<Vec3>`, returns a point on the unit sphere
+    pub fn to_unit_vector(self) -> Vec3 {
+        let (sin_lat, cos_lat) = self.latitude.sin_cos();
+        let (sin_lon, cos_lon) = self.longitude.sin_cos();
+        Vec3::new(cos_lat * sin_lon, sin_lat, cos_lat * cos_lon)
+    }
+
     pub fn as_degrees(&self) -> (f32, f32) {
         let latitude = self.latitude * (180.0 / PI);
         let longitude = self.longitude * (180.0 / PI);
         (latitude, longitude)
     }
 
-    pub fn to_uv(&self) -> (f32, f32) {
+    pub fn to_uv(self) -> (f32, f32) {
         let (lat, lon) = self.as_degrees();
         let v = (90.0 - lat) / 180.0;
         let u = (lon + 180.0) / 360.0;
@@ -1,2 +1,9 @@
+pub mod aurora;
+pub mod camera;
+pub mod clock;
 pub mod earth;
+pub mod satellites;
+pub mod solar_system;
+pub mod stations;
+pub mod sun;
 pub mod ui;
\ No newline at end of file
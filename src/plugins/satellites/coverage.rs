@@ -0,0 +1,216 @@
+use bevy::prelude::*;
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use crate::plugins::earth::uv::LatLon;
+use crate::plugins::satellites::{Satellite, sensor::Sensor};
+
+/// Equirectangular raster of accumulated sensor coverage
+/// each cell holds the minutes of footprint samples that saw it,
+/// for a swath the strip swept between one sample and the next
+#[derive(Clone, Debug)]
+pub struct CoverageRaster {
+    pub width: usize,
+    pub height: usize,
+    // row-major, row 0 is the north pole
+    pub minutes: Vec<f32>,
+}
+
+impl CoverageRaster {
+    pub fn new(width: usize, height: usize) -> Self {
+        CoverageRaster {
+            width,
+            height,
+            minutes: vec![0.0; width * height],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.minutes.fill(0.0);
+    }
+
+    /// Cell containing a location, using the same layout as `LatLon::to_uv`
+    pub fn cell(&self, location: &LatLon) -> (usize, usize) {
+        let (u, v) = location.to_uv();
+        let x = ((u * self.width as f32) as usize).min(self.width - 1);
+        let y = ((v * self.height as f32) as usize).min(self.height - 1);
+        (x, y)
+    }
+
+    /// Minutes of coverage at a location
+    pub fn value_at(&self, location: &LatLon) -> f32 {
+        let (x, y) = self.cell(location);
+        self.minutes[y * self.width + x]
+    }
+
+    /// Location of a cell's center
+    pub fn cell_center(&self, x: usize, y: usize) -> LatLon {
+        LatLon {
+            latitude: FRAC_PI_2 - (y as f32 + 0.5) / self.height as f32 * PI,
+            longitude: (x as f32 + 0.5) / self.width as f32 * TAU - PI,
+        }
+    }
+
+    /// Adds `minutes` to every cell inside a footprint cap
+    pub fn add_footprint(&mut self, sub_satellite: &LatLon, central_angle: f32, minutes: f32) {
+        let center = sub_satellite.to_unit_vector();
+        let cos_angle = central_angle.cos();
+
+        // only visit rows the cap can reach
+        let rows = self.rows(
+            sub_satellite.latitude + central_angle,
+            sub_satellite.latitude - central_angle,
+        );
+
+        for y in rows {
+            for x in 0..self.width {
+                let cell = self.cell_center(x, y).to_unit_vector();
+                if cell.dot(center) >= cos_angle {
+                    self.minutes[y * self.width + x] += minutes;
+                }
+            }
+        }
+    }
+
+    /// Adds `minutes` to every cell within `central_angle` across the ground track
+    /// between two sub-satellite points, the track taken as a great circle
+    pub fn add_swath(&mut self, from: &LatLon, to: &LatLon, central_angle: f32, minutes: f32) {
+        let start = from.to_unit_vector();
+        let end = to.to_unit_vector();
+        let Some(normal) = start.cross(end).try_normalize() else {
+            return;
+        };
+        let along = normal.cross(start);
+        let length = start.angle_between(end);
+        let sin_angle = central_angle.sin();
+
+        let rows = self.rows(
+            from.latitude.max(to.latitude) + central_angle,
+            from.latitude.min(to.latitude) - central_angle,
+        );
+
+        for y in rows {
+            for x in 0..self.width {
+                let cell = self.cell_center(x, y).to_unit_vector();
+                // the end belongs to the next segment, so no cell is counted twice
+                let distance_along = cell.dot(along).atan2(cell.dot(start));
+                if cell.dot(normal).abs() <= sin_angle && (0.0..length).contains(&distance_along) {
+                    self.minutes[y * self.width + x] += minutes;
+                }
+            }
+        }
+    }
+
+    // rows between two latitudes (radians)
+    fn rows(&self, north: f32, south: f32) -> std::ops::Range<usize> {
+        let north = north.min(FRAC_PI_2);
+        let south = south.max(-FRAC_PI_2);
+        let first_row = (((FRAC_PI_2 - north) / PI) * self.height as f32).floor() as usize;
+        let last_row =
+            ((((FRAC_PI_2 - south) / PI) * self.height as f32).ceil() as usize).min(self.height);
+        first_row..last_row
+    }
+
+    /// Sweeps a satellite's footprint from `start` to `end` (julian dates) every `step` minutes
+    pub fn accumulate(
+        &mut self,
+        satellite: &Satellite,
+        sensor: &Sensor,
+        start: f64,
+        end: f64,
+        step: f64,
+    ) {
+        let steps = ((end - start) * 1440.0 / step).ceil().max(0.0) as usize;
+        let mut previous = None;
+        for i in 0..steps {
+            let time = start + i as f64 * step / 1440.0;
+            let Ok(position) = satellite.position_at(time) else {
+                previous = None;
+                continue;
+            };
+            let sub_satellite = LatLon::from(position);
+            let central_angle = sensor.central_angle(position);
+            match sensor {
+                Sensor::HalfAngle(_) => {
+                    self.add_footprint(&sub_satellite, central_angle, step as f32);
+                }
+                Sensor::SwathWidth(_) => {
+                    if let Some(previous) = previous {
+                        self.add_swath(&previous, &sub_satellite, central_angle, step as f32);
+                    }
+                }
+            }
+            previous = Some(sub_satellite);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // half degree cells
+    fn raster() -> CoverageRaster {
+        CoverageRaster::new(720, 360)
+    }
+
+    fn at(raster: &CoverageRaster, latitude: f32, longitude: f32) -> f32 {
+        raster.value_at(&LatLon::from_degrees(latitude, longitude))
+    }
+
+    #[test]
+    fn footprints_cover_a_cap() {
+        let mut raster = raster();
+        raster.add_footprint(&LatLon::from_degrees(0.0, 0.0), 5f32.to_radians(), 2.0);
+
+        assert_eq!(at(&raster, 0.0, 0.0), 2.0);
+        assert_eq!(at(&raster, 3.0, -3.0), 2.0);
+        assert_eq!(at(&raster, 0.0, 6.0), 0.0);
+        assert_eq!(at(&raster, -4.0, 4.0), 0.0);
+    }
+
+    #[test]
+    fn footprints_over_a_pole_cover_every_longitude() {
+        let mut raster = raster();
+        raster.add_footprint(&LatLon::from_degrees(89.0, 0.0), 3f32.to_radians(), 1.0);
+
+        assert!(
+            raster.minutes[..raster.width]
+                .iter()
+                .all(|&minutes| minutes == 1.0)
+        );
+        assert_eq!(at(&raster, 88.5, 180.0), 1.0);
+        assert_eq!(at(&raster, 85.0, 180.0), 0.0);
+    }
+
+    #[test]
+    fn swaths_cover_a_strip_along_the_track() {
+        let mut raster = raster();
+        let half_width = 1f32.to_radians();
+        raster.add_swath(
+            &LatLon::from_degrees(0.0, 0.0),
+            &LatLon::from_degrees(0.0, 2.0),
+            half_width,
+            0.5,
+        );
+
+        assert_eq!(at(&raster, 0.7, 1.0), 0.5);
+        assert_eq!(at(&raster, -0.7, 0.2), 0.5);
+        // across the track, behind its start and past its end
+        assert_eq!(at(&raster, 1.3, 1.0), 0.0);
+        assert_eq!(at(&raster, 0.0, -0.3), 0.0);
+        assert_eq!(at(&raster, 0.0, 2.3), 0.0);
+    }
+
+    #[test]
+    fn consecutive_swaths_count_each_cell_once() {
+        let mut raster = raster();
+        let track = [0.0, 1.6, 3.2, 4.8].map(|longitude| LatLon::from_degrees(10.0, longitude));
+        for segment in track.windows(2) {
+            raster.add_swath(&segment[0], &segment[1], 1f32.to_radians(), 1.0);
+        }
+
+        assert!(raster.minutes.iter().all(|&minutes| minutes <= 1.0));
+        assert_eq!(at(&raster, 10.0, 1.6), 1.0);
+        assert_eq!(at(&raster, 10.0, 3.2), 1.0);
+    }
+}
@@ -0,0 +1,333 @@
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future::poll_once},
+};
+
+pub mod coverage;
+pub mod sensor;
+pub mod sgp4;
+pub mod tle;
+
+use crate::config::*;
+use crate::plugins::clock::SimulationClock;
+use crate::plugins::earth::frames::{ecef_to_scene, teme_state_to_ecef, teme_to_scene};
+use coverage::CoverageRaster;
+use sensor::{Sensor, write_footprint_mesh, write_swath_mesh};
+use sgp4::{Sgp4, Sgp4Error};
+use tle::parse_tle_file;
+
+pub struct SatellitePlugin;
+
+impl Plugin for SatellitePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Coverage>()
+            .add_systems(Startup, (load_satellites, start_coverage_window))
+            .add_systems(
+                Update,
+                (
+                    propagate_satellites,
+                    update_tracks,
+                    draw_tracks,
+                    spawn_footprints,
+                    update_footprints,
+                    update_coverage,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// A satellite propagated from a two-line element set
+#[derive(Component, Clone)]
+pub struct Satellite {
+    pub model: Sgp4,
+    pub color: Color,
+    // minutes
+    pub period: f64,
+}
+
+impl Satellite {
+    /// Position in the scene's earth-fixed frame (km)
+    pub fn position_at(&self, julian_date: f64) -> Result<Vec3, Sgp4Error> {
+        let state = self.model.propagate_to(julian_date)?;
+        Ok(teme_to_scene(state.position, julian_date))
+    }
+
+    /// Position (km) and velocity over the ground (km/s) in the scene's earth-fixed frame
+    pub fn state_at(&self, julian_date: f64) -> Result<(Vec3, Vec3), Sgp4Error> {
+        let state = self.model.propagate_to(julian_date)?;
+        let (position, velocity) = teme_state_to_ecef(state.position, state.velocity, julian_date);
+        Ok((ecef_to_scene(position), ecef_to_scene(velocity)))
+    }
+}
+
+/// Cached orbit line and ground track, recomputed as the clock moves
+#[derive(Component, Default)]
+pub struct SatelliteTrack {
+    // one revolution of the inertial orbit, rotated into the current earth-fixed frame
+    pub orbit: Vec<Vec3>,
+    // sub-satellite points lifted just above the terrain
+    pub past: Vec<Vec3>,
+    pub future: Vec<Vec3>,
+    // julian date the track was computed for
+    computed_at: f64,
+}
+
+/// Surface polygon showing what a satellite's `Sensor` sees
+#[derive(Component)]
+pub struct Footprint {
+    pub satellite: Entity,
+}
+
+/// Sensor coverage of every satellite with a `Sensor`, accumulated over `window`
+/// changing the window recomputes the raster in the background, it stays empty until then
+#[derive(Resource)]
+pub struct Coverage {
+    pub raster: CoverageRaster,
+    // julian dates (UTC)
+    pub window: Option<(f64, f64)>,
+    // the sweep over the current window, until it is done
+    task: Option<Task<CoverageRaster>>,
+}
+
+impl Default for Coverage {
+    fn default() -> Self {
+        Coverage {
+            raster: CoverageRaster::new(COVERAGE_RASTER_SIZE.0, COVERAGE_RASTER_SIZE.1),
+            window: None,
+            task: None,
+        }
+    }
+}
+
+fn load_satellites(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let contents = match std::fs::read_to_string(SATELLITE_TLE_PATH) {
+        Ok(contents) => contents,
+        Err(error) => {
+            warn!("could not read satellites from {SATELLITE_TLE_PATH}: {error}");
+            return;
+        }
+    };
+
+    let (tles, errors) = parse_tle_file(&contents);
+    for error in errors {
+        warn!("skipping element set in {SATELLITE_TLE_PATH}: {error}");
+    }
+
+    let marker = meshes.add(Sphere::new(SATELLITE_MARKER_RADIUS).mesh().ico(2).unwrap());
+
+    for tle in tles {
+        let model = match Sgp4::new(&tle) {
+            Ok(model) => model,
+            Err(error) => {
+                warn!("skipping satellite {}: {error:?}", tle.name);
+                continue;
+            }
+        };
+
+        // spread hues by catalog number so colors stay stable between runs
+        let color = Color::hsl((tle.catalog_number * 47 % 360) as f32, 0.8, 0.6);
+        let material = materials.add(StandardMaterial {
+            base_color: color,
+            unlit: true,
+            ..default()
+        });
+
+        let swath = SENSOR_SWATHS
+            .iter()
+            .find(|(name, _)| *name == tle.name)
+            .map(|&(_, width)| Sensor::SwathWidth(width));
+        let cone = SENSOR_HALF_ANGLES
+            .iter()
+            .find(|(name, _)| *name == tle.name)
+            .map(|&(_, half_angle)| Sensor::HalfAngle(half_angle.to_radians()));
+        let sensor = swath.or(cone);
+
+        let mut satellite = commands.spawn((
+            Satellite {
+                period: tle.period(),
+                model,
+                color,
+            },
+            Name::new(tle.name),
+            SatelliteTrack::default(),
+            Mesh3d(marker.clone()),
+            MeshMaterial3d(material),
+            Transform::default(),
+        ));
+        if let Some(sensor) = sensor {
+            satellite.insert(sensor);
+        }
+    }
+}
+
+fn start_coverage_window(clock: Res<SimulationClock>, mut coverage: ResMut<Coverage>) {
+    coverage.window = Some((clock.julian_date, clock.julian_date + COVERAGE_WINDOW));
+}
+
+fn propagate_satellites(
+    clock: Res<SimulationClock>,
+    mut satellites: Query<(&Satellite, &mut Transform, &mut Visibility)>,
+) {
+    for (satellite, mut transform, mut visibility) in &mut satellites {
+        match satellite.position_at(clock.julian_date) {
+            Ok(position) => {
+                transform.translation = position;
+                *visibility = Visibility::Inherited;
+            }
+            // decayed satellites stay hidden until the clock moves back
+            Err(_) => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+fn update_tracks(
+    clock: Res<SimulationClock>,
+    mut satellites: Query<(&Satellite, &mut SatelliteTrack)>,
+) {
+    let now = clock.julian_date;
+
+    for (satellite, mut track) in &mut satellites {
+        if (now - track.computed_at).abs() * 1440.0 < TRACK_REFRESH_INTERVAL {
+            continue;
+        }
+        track.computed_at = now;
+
+        let duration = satellite.period * GROUND_TRACK_ORBITS;
+        let steps = (duration / TRACK_STEP).ceil() as usize;
+
+        // the inertial orbit is closed, so sample it in TEME and rotate everything with today's angle
+        let orbit_steps = (satellite.period / TRACK_STEP).ceil() as usize;
+        track.orbit = (0..=orbit_steps)
+            .filter_map(|i| {
+                let minutes = i as f64 * satellite.period / orbit_steps as f64;
+                let state = satellite.model.propagate_to(now + minutes / 1440.0).ok()?;
+                Some(teme_to_scene(state.position, now))
+            })
+            .collect();
+
+        let ground_point = |minutes: f64| {
+            let time = now + minutes / 1440.0;
+            let position = satellite.position_at(time).ok()?;
+            Some(position.normalize() * (EARTH_RADIUS + DISPLACEMENT_SCALE))
+        };
+
+        track.past = (0..=steps)
+            .filter_map(|i| ground_point(-(i as f64) * TRACK_STEP))
+            .collect();
+        track.future = (0..=steps)
+            .filter_map(|i| ground_point(i as f64 * TRACK_STEP))
+            .collect();
+    }
+}
+
+fn draw_tracks(mut gizmos: Gizmos, satellites: Query<(&Satellite, &SatelliteTrack)>) {
+    for (satellite, track) in &satellites {
+        gizmos.linestrip(track.orbit.iter().copied(), satellite.color.with_alpha(0.5));
+        gizmos.linestrip(track.past.iter().copied(), satellite.color.with_alpha(0.35));
+        gizmos.linestrip(track.future.iter().copied(), satellite.color);
+    }
+}
+
+fn spawn_footprints(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    sensors: Query<(Entity, &Satellite), Added<Sensor>>,
+) {
+    for (entity, satellite) in &sensors {
+        let mesh = Mesh::new(
+            bevy::mesh::PrimitiveTopology::TriangleList,
+            bevy::asset::RenderAssetUsages::default(),
+        );
+        let material = materials.add(StandardMaterial {
+            base_color: satellite.color.with_alpha(0.35),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            double_sided: true,
+            cull_mode: None,
+            ..default()
+        });
+
+        commands.spawn((
+            Footprint { satellite: entity },
+            Mesh3d(meshes.add(mesh)),
+            MeshMaterial3d(material),
+            Transform::default(),
+        ));
+    }
+}
+
+fn update_footprints(
+    mut commands: Commands,
+    clock: Res<SimulationClock>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    satellites: Query<(&Satellite, &Visibility, &Sensor)>,
+    mut footprints: Query<(Entity, &Footprint, &Mesh3d, &mut Visibility), Without<Satellite>>,
+) {
+    for (entity, footprint, mesh, mut visibility) in &mut footprints {
+        let Ok((satellite, satellite_visibility, sensor)) = satellites.get(footprint.satellite)
+        else {
+            // the satellite or its sensor is gone
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        *visibility = *satellite_visibility;
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        let (Ok((position, velocity)), Some(mesh)) = (
+            satellite.state_at(clock.julian_date),
+            meshes.get_mut(&mesh.0),
+        ) else {
+            continue;
+        };
+        let central_angle = sensor.central_angle(position);
+        let radius = EARTH_RADIUS + DISPLACEMENT_SCALE;
+        match sensor {
+            Sensor::HalfAngle(_) => write_footprint_mesh(mesh, position, central_angle, radius),
+            Sensor::SwathWidth(_) => {
+                write_swath_mesh(mesh, position, velocity, central_angle, SWATH_TRAIL, radius)
+            }
+        }
+    }
+}
+
+fn update_coverage(mut coverage: ResMut<Coverage>, satellites: Query<(&Satellite, &Sensor)>) {
+    let changed = coverage.is_changed();
+    // don't let the sweep's bookkeeping count as a change
+    let coverage = coverage.bypass_change_detection();
+
+    if changed {
+        coverage.raster.clear();
+        // replacing a running sweep drops and so cancels it
+        coverage.task = coverage.window.map(|(start, end)| {
+            let sensors: Vec<(Satellite, Sensor)> = satellites
+                .iter()
+                .map(|(satellite, sensor)| (satellite.clone(), *sensor))
+                .collect();
+            let (width, height) = (coverage.raster.width, coverage.raster.height);
+            AsyncComputeTaskPool::get().spawn(async move {
+                let mut raster = CoverageRaster::new(width, height);
+                for (satellite, sensor) in &sensors {
+                    raster.accumulate(satellite, sensor, start, end, COVERAGE_STEP);
+                }
+                raster
+            })
+        });
+    }
+
+    let Some(task) = &mut coverage.task else {
+        return;
+    };
+    if let Some(raster) = block_on(poll_once(task)) {
+        coverage.raster = raster;
+        coverage.task = None;
+    }
+}
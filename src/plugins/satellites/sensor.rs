@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use crate::config::EARTH_RADIUS;
+
+// Nadir-pointing sensor footprints
+// the footprint of a circular sensor cone on the globe's sphere is a spherical cap,
+// so it is described by the sub-satellite point and an earth central angle
+// a pushbroom swath only sees a line across the ground track, which sweeps out a strip
+// along it as the satellite moves, the central angle is then from the track to the strip's edges
+
+/// Field of view of a nadir-pointing sensor
+#[derive(Component, Clone, Copy, Debug)]
+pub enum Sensor {
+    // radians, measured from nadir
+    HalfAngle(f32),
+    // km across track on the ground
+    SwathWidth(f32),
+}
+
+impl Sensor {
+    /// Earth central angle (radians) between the sub-satellite point and the footprint edge,
+    /// across the track for a swath, for a satellite at `position` (km from the earth's center)
+    pub fn central_angle(&self, position: Vec3) -> f32 {
+        let radius = position.length();
+
+        // the widest cap a sensor at this height can see, reached at the horizon
+        let horizon = (EARTH_RADIUS / radius).clamp(-1.0, 1.0).acos();
+
+        match *self {
+            Sensor::HalfAngle(half_angle) => {
+                // nadir angle + central angle + elevation at the edge = 90°
+                let cos_elevation = radius * half_angle.sin() / EARTH_RADIUS;
+                if cos_elevation >= 1.0 {
+                    horizon
+                } else {
+                    FRAC_PI_2 - half_angle - cos_elevation.acos()
+                }
+            }
+            Sensor::SwathWidth(width) => (0.5 * width / EARTH_RADIUS).min(horizon),
+        }
+    }
+}
+
+/// Ring of `segments` points around the footprint edge, `radius` km from the earth's center
+pub fn footprint_outline(
+    sub_satellite: Vec3,
+    central_angle: f32,
+    radius: f32,
+    segments: usize,
+) -> Vec<Vec3> {
+    let center = sub_satellite.normalize();
+    let (u, v) = center.any_orthonormal_pair();
+    let (sin_angle, cos_angle) = central_angle.sin_cos();
+
+    (0..segments)
+        .map(|i| {
+            let azimuth = i as f32 / segments as f32 * TAU;
+            let (sin_azimuth, cos_azimuth) = azimuth.sin_cos();
+            (center * cos_angle + (u * cos_azimuth + v * sin_azimuth) * sin_angle) * radius
+        })
+        .collect()
+}
+
+/// Builds a triangle-fan surface mesh of a footprint, subdivided radially so it hugs the sphere
+/// positions are written into an existing mesh so it can be updated every frame
+pub fn write_footprint_mesh(mesh: &mut Mesh, sub_satellite: Vec3, central_angle: f32, radius: f32) {
+    const SEGMENTS: usize = 64;
+    const RINGS: usize = 8;
+
+    let center = sub_satellite.normalize();
+    let mut positions = vec![center * radius];
+    let mut normals = vec![center];
+
+    for ring in 1..=RINGS {
+        let angle = central_angle * ring as f32 / RINGS as f32;
+        for point in footprint_outline(center, angle, 1.0, SEGMENTS) {
+            positions.push(point * radius);
+            normals.push(point);
+        }
+    }
+
+    let mut indices = Vec::with_capacity(SEGMENTS * (6 * RINGS - 3));
+    let ring_start = |ring: usize| (1 + (ring - 1) * SEGMENTS) as u32;
+    for i in 0..SEGMENTS as u32 {
+        let next = (i + 1) % SEGMENTS as u32;
+        // center fan
+        indices.extend([0, ring_start(1) + i, ring_start(1) + next]);
+        // quads between rings
+        for ring in 1..RINGS {
+            let inner = ring_start(ring);
+            let outer = ring_start(ring + 1);
+            indices.extend([inner + i, outer + i, outer + next]);
+            indices.extend([inner + i, outer + next, inner + next]);
+        }
+    }
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_indices(bevy::mesh::Indices::U32(indices));
+}
+
+/// Builds the strip a swath sensor swept over the last `trail` seconds, behind the sub-satellite point
+/// `ground_velocity` (km/s) is the satellite's velocity over the ground, the track is taken as a great circle
+pub fn write_swath_mesh(
+    mesh: &mut Mesh,
+    position: Vec3,
+    ground_velocity: Vec3,
+    central_angle: f32,
+    trail: f32,
+    radius: f32,
+) {
+    // rows along the track, columns across it
+    const ROWS: usize = 32;
+    const COLUMNS: usize = 8;
+
+    let center = position.normalize();
+    let horizontal = ground_velocity - center * ground_velocity.dot(center);
+    let along = horizontal
+        .try_normalize()
+        .unwrap_or_else(|| center.any_orthonormal_vector());
+    // the sub-satellite point moves at the angular rate of the horizontal velocity at the satellite's radius
+    let length = horizontal.length() / position.length() * trail;
+    let across = center.cross(along);
+
+    let mut positions = Vec::with_capacity((ROWS + 1) * (COLUMNS + 1));
+    let mut normals = Vec::with_capacity(positions.capacity());
+    for row in 0..=ROWS {
+        let (sin_track, cos_track) = (-length * row as f32 / ROWS as f32).sin_cos();
+        let track = center * cos_track + along * sin_track;
+        for column in 0..=COLUMNS {
+            let offset = central_angle * (2.0 * column as f32 / COLUMNS as f32 - 1.0);
+            let (sin_offset, cos_offset) = offset.sin_cos();
+            let point = track * cos_offset + across * sin_offset;
+            positions.push(point * radius);
+            normals.push(point);
+        }
+    }
+
+    let mut indices = Vec::with_capacity(ROWS * COLUMNS * 6);
+    let index = |row: usize, column: usize| (row * (COLUMNS + 1) + column) as u32;
+    for row in 0..ROWS {
+        for column in 0..COLUMNS {
+            let (a, b) = (index(row, column), index(row, column + 1));
+            let (c, d) = (index(row + 1, column), index(row + 1, column + 1));
+            indices.extend([a, c, d, a, d, b]);
+        }
+    }
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_indices(bevy::mesh::Indices::U32(indices));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn central_angles() {
+        let position = Vec3::Y * (EARTH_RADIUS + 700.0);
+        let horizon = (EARTH_RADIUS / position.length()).acos();
+
+        assert!(Sensor::HalfAngle(0.0).central_angle(position).abs() < 1e-6);
+        // small angles see about altitude * tan(half angle) of ground
+        let narrow = Sensor::HalfAngle(1f32.to_radians()).central_angle(position);
+        assert!((narrow * EARTH_RADIUS - 700.0 * 1f32.to_radians().tan()).abs() < 0.1);
+        assert_eq!(Sensor::HalfAngle(1.5).central_angle(position), horizon);
+
+        let swath = Sensor::SwathWidth(290.0).central_angle(position);
+        assert!((swath - 145.0 / EARTH_RADIUS).abs() < 1e-6);
+        assert_eq!(Sensor::SwathWidth(1e5).central_angle(position), horizon);
+    }
+
+    #[test]
+    fn swaths_trail_behind_the_satellite() {
+        let position = Vec3::Z * (EARTH_RADIUS + 700.0);
+        // heading north at 7 km/s
+        let velocity = Vec3::Y * 7.0;
+        let half_width = 145.0 / EARTH_RADIUS;
+        let mut mesh = Mesh::new(
+            bevy::mesh::PrimitiveTopology::TriangleList,
+            bevy::asset::RenderAssetUsages::default(),
+        );
+        write_swath_mesh(
+            &mut mesh,
+            position,
+            velocity,
+            half_width,
+            60.0,
+            EARTH_RADIUS,
+        );
+
+        let Some(positions) = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(|positions| positions.as_float3())
+        else {
+            panic!("swath mesh has no positions");
+        };
+        let length = 7.0 / position.length() * 60.0;
+        for &point in positions {
+            let point = Vec3::from(point) / EARTH_RADIUS;
+            assert!((point.length() - 1.0).abs() < 1e-5);
+            // the track runs along the greenwich meridian, from the satellite back south
+            assert!(point.x.abs() <= half_width.sin() + 1e-5);
+            let behind = -point.y.atan2(point.z);
+            assert!((-1e-5..=length + 1e-5).contains(&behind));
+        }
+    }
+}
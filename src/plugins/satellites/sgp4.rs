@@ -0,0 +1,549 @@
+use bevy::math::DVec3;
+use std::f64::consts::TAU;
+
+use crate::plugins::satellites::tle::Tle;
+
+// SGP4 orbit propagator for near-earth satellites
+// based on Hoots & Roehrich, Spacetrack Report #3 (1980)
+// https://celestrak.org/NORAD/documentation/spacetrk.pdf
+// deep-space orbits (period >= 225 minutes) need SDP4 and are rejected
+
+// WGS72 constants, as used to generate the published element sets
+const EARTH_RADIUS_KM: f64 = 6378.135;
+const MU: f64 = 398600.8;
+const J2: f64 = 0.001082616;
+const J3: f64 = -0.00000253881;
+const J4: f64 = -0.00000165597;
+
+const CK2: f64 = 0.5 * J2;
+const CK4: f64 = -0.375 * J4;
+const A3OVK2: f64 = -J3 / CK2;
+// perigee height in earth radii below which the atmospheric model changes
+const S: f64 = 1.0 + 78.0 / EARTH_RADIUS_KM;
+const QOMS2T: f64 = (120.0 - 78.0) / EARTH_RADIUS_KM
+    * ((120.0 - 78.0) / EARTH_RADIUS_KM)
+    * ((120.0 - 78.0) / EARTH_RADIUS_KM)
+    * ((120.0 - 78.0) / EARTH_RADIUS_KM);
+
+pub const DEEP_SPACE_PERIOD: f64 = 225.0;
+
+/// sqrt(mu) in earth radii^1.5 per minute
+fn xke() -> f64 {
+    60.0 / (EARTH_RADIUS_KM * EARTH_RADIUS_KM * EARTH_RADIUS_KM / MU).sqrt()
+}
+
+/// Position and velocity in the TEME (true equator, mean equinox) frame
+#[derive(Clone, Copy, Debug)]
+pub struct StateVector {
+    // km
+    pub position: DVec3,
+    // km/s
+    pub velocity: DVec3,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sgp4Error {
+    DeepSpace,
+    // orbit has decayed or the elements are degenerate
+    Decayed,
+}
+
+/// Initialized SGP4 model for one element set
+#[derive(Clone, Debug)]
+pub struct Sgp4 {
+    epoch: f64,
+    bstar: f64,
+    inclination: f64,
+    eccentricity: f64,
+    argument_of_perigee: f64,
+    right_ascension: f64,
+    mean_anomaly: f64,
+
+    // recovered mean motion (radians/minute) and semi-major axis (earth radii)
+    xnodp: f64,
+    aodp: f64,
+
+    cosio: f64,
+    sinio: f64,
+    x3thm1: f64,
+    x1mth2: f64,
+    x7thm1: f64,
+    eta: f64,
+    c1: f64,
+    c4: f64,
+    c5: f64,
+    xmdot: f64,
+    omgdot: f64,
+    xnodot: f64,
+    omgcof: f64,
+    xmcof: f64,
+    xnodcf: f64,
+    t2cof: f64,
+    xlcof: f64,
+    aycof: f64,
+    delmo: f64,
+    sinmo: f64,
+    // perigee below 220 km uses a truncated drag model
+    simple: bool,
+    d2: f64,
+    d3: f64,
+    d4: f64,
+    t3cof: f64,
+    t4cof: f64,
+    t5cof: f64,
+}
+
+impl Sgp4 {
+    pub fn new(tle: &Tle) -> Result<Self, Sgp4Error> {
+        if tle.period() >= DEEP_SPACE_PERIOD {
+            return Err(Sgp4Error::DeepSpace);
+        }
+        if tle.mean_motion <= 0.0 || !(0.0..1.0).contains(&tle.eccentricity) {
+            return Err(Sgp4Error::Decayed);
+        }
+
+        let xke = xke();
+        let e0 = tle.eccentricity;
+        let i0 = tle.inclination;
+        let n0 = tle.mean_motion * TAU / 1440.0;
+
+        // recover original mean motion and semi-major axis from the kozai mean motion
+        let a1 = (xke / n0).powf(2.0 / 3.0);
+        let cosio = i0.cos();
+        let theta2 = cosio * cosio;
+        let x3thm1 = 3.0 * theta2 - 1.0;
+        let eosq = e0 * e0;
+        let betao2 = 1.0 - eosq;
+        let betao = betao2.sqrt();
+        let del1 = 1.5 * CK2 * x3thm1 / (a1 * a1 * betao * betao2);
+        let ao = a1 * (1.0 - del1 * (1.0 / 3.0 + del1 * (1.0 + 134.0 / 81.0 * del1)));
+        let delo = 1.5 * CK2 * x3thm1 / (ao * ao * betao * betao2);
+        let xnodp = n0 / (1.0 + delo);
+        let aodp = ao / (1.0 - delo);
+
+        let simple = aodp * (1.0 - e0) < 220.0 / EARTH_RADIUS_KM + 1.0;
+
+        // adjust the atmospheric density parameters for low perigees
+        let mut s4 = S;
+        let mut qoms24 = QOMS2T;
+        let perigee = (aodp * (1.0 - e0) - 1.0) * EARTH_RADIUS_KM;
+        if perigee < 156.0 {
+            s4 = if perigee <= 98.0 {
+                20.0
+            } else {
+                perigee - 78.0
+            };
+            qoms24 = ((120.0 - s4) / EARTH_RADIUS_KM).powi(4);
+            s4 = s4 / EARTH_RADIUS_KM + 1.0;
+        }
+
+        let pinvsq = 1.0 / (aodp * aodp * betao2 * betao2);
+        let tsi = 1.0 / (aodp - s4);
+        let eta = aodp * e0 * tsi;
+        let etasq = eta * eta;
+        let eeta = e0 * eta;
+        let psisq = (1.0 - etasq).abs();
+        let coef = qoms24 * tsi.powi(4);
+        let coef1 = coef / psisq.powf(3.5);
+
+        let c2 = coef1
+            * xnodp
+            * (aodp * (1.0 + 1.5 * etasq + eeta * (4.0 + etasq))
+                + 0.75 * CK2 * tsi / psisq * x3thm1 * (8.0 + 3.0 * etasq * (8.0 + etasq)));
+        let c1 = tle.bstar * c2;
+        let sinio = i0.sin();
+        let c3 = if e0 > 1e-4 {
+            coef * tsi * A3OVK2 * xnodp * sinio / e0
+        } else {
+            0.0
+        };
+        let x1mth2 = 1.0 - theta2;
+        let c4 = 2.0
+            * xnodp
+            * coef1
+            * aodp
+            * betao2
+            * (eta * (2.0 + 0.5 * etasq) + e0 * (0.5 + 2.0 * etasq)
+                - 2.0 * CK2 * tsi / (aodp * psisq)
+                    * (-3.0 * x3thm1 * (1.0 - 2.0 * eeta + etasq * (1.5 - 0.5 * eeta))
+                        + 0.75
+                            * x1mth2
+                            * (2.0 * etasq - eeta * (1.0 + etasq))
+                            * (2.0 * tle.argument_of_perigee).cos()));
+        let c5 = 2.0 * coef1 * aodp * betao2 * (1.0 + 2.75 * (etasq + eeta) + eeta * etasq);
+
+        // secular rates of mean anomaly, perigee and node
+        let theta4 = theta2 * theta2;
+        let temp1 = 3.0 * CK2 * pinvsq * xnodp;
+        let temp2 = temp1 * CK2 * pinvsq;
+        let temp3 = 1.25 * CK4 * pinvsq * pinvsq * xnodp;
+        let xmdot = xnodp
+            + 0.5 * temp1 * betao * x3thm1
+            + 0.0625 * temp2 * betao * (13.0 - 78.0 * theta2 + 137.0 * theta4);
+        let x1m5th = 1.0 - 5.0 * theta2;
+        let omgdot = -0.5 * temp1 * x1m5th
+            + 0.0625 * temp2 * (7.0 - 114.0 * theta2 + 395.0 * theta4)
+            + temp3 * (3.0 - 36.0 * theta2 + 49.0 * theta4);
+        let xhdot1 = -temp1 * cosio;
+        let xnodot = xhdot1
+            + (0.5 * temp2 * (4.0 - 19.0 * theta2) + 2.0 * temp3 * (3.0 - 7.0 * theta2)) * cosio;
+
+        let omgcof = tle.bstar * c3 * tle.argument_of_perigee.cos();
+        let xmcof = if e0 > 1e-4 {
+            -2.0 / 3.0 * coef * tle.bstar / eeta
+        } else {
+            0.0
+        };
+        let xnodcf = 3.5 * betao2 * xhdot1 * c1;
+        let t2cof = 1.5 * c1;
+        let xlcof = 0.125 * A3OVK2 * sinio * (3.0 + 5.0 * cosio) / (1.0 + cosio);
+        let aycof = 0.25 * A3OVK2 * sinio;
+        let delmo = (1.0 + eta * tle.mean_anomaly.cos()).powi(3);
+        let sinmo = tle.mean_anomaly.sin();
+        let x7thm1 = 7.0 * theta2 - 1.0;
+
+        let (mut d2, mut d3, mut d4, mut t3cof, mut t4cof, mut t5cof) =
+            (0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        if !simple {
+            let c1sq = c1 * c1;
+            d2 = 4.0 * aodp * tsi * c1sq;
+            let temp = d2 * tsi * c1 / 3.0;
+            d3 = (17.0 * aodp + s4) * temp;
+            d4 = 0.5 * temp * aodp * tsi * (221.0 * aodp + 31.0 * s4) * c1;
+            t3cof = d2 + 2.0 * c1sq;
+            t4cof = 0.25 * (3.0 * d3 + c1 * (12.0 * d2 + 10.0 * c1sq));
+            t5cof =
+                0.2 * (3.0 * d4 + 12.0 * c1 * d3 + 6.0 * d2 * d2 + 15.0 * c1sq * (2.0 * d2 + c1sq));
+        }
+
+        Ok(Sgp4 {
+            epoch: tle.epoch,
+            bstar: tle.bstar,
+            inclination: i0,
+            eccentricity: e0,
+            argument_of_perigee: tle.argument_of_perigee,
+            right_ascension: tle.right_ascension,
+            mean_anomaly: tle.mean_anomaly,
+            xnodp,
+            aodp,
+            cosio,
+            sinio,
+            x3thm1,
+            x1mth2,
+            x7thm1,
+            eta,
+            c1,
+            c4,
+            c5,
+            xmdot,
+            omgdot,
+            xnodot,
+            omgcof,
+            xmcof,
+            xnodcf,
+            t2cof,
+            xlcof,
+            aycof,
+            delmo,
+            sinmo,
+            simple,
+            d2,
+            d3,
+            d4,
+            t3cof,
+            t4cof,
+            t5cof,
+        })
+    }
+
+    /// Propagates to a julian date (UTC)
+    pub fn propagate_to(&self, julian_date: f64) -> Result<StateVector, Sgp4Error> {
+        self.propagate((julian_date - self.epoch) * 1440.0)
+    }
+
+    /// Propagates to `tsince` minutes after the epoch
+    pub fn propagate(&self, tsince: f64) -> Result<StateVector, Sgp4Error> {
+        let xke = xke();
+
+        // secular gravity and atmospheric drag
+        let xmdf = self.mean_anomaly + self.xmdot * tsince;
+        let omgadf = self.argument_of_perigee + self.omgdot * tsince;
+        let xnoddf = self.right_ascension + self.xnodot * tsince;
+        let tsq = tsince * tsince;
+        let xnode = xnoddf + self.xnodcf * tsq;
+        let mut omega = omgadf;
+        let mut xmp = xmdf;
+        let mut tempa = 1.0 - self.c1 * tsince;
+        let mut tempe = self.bstar * self.c4 * tsince;
+        let mut templ = self.t2cof * tsq;
+
+        if !self.simple {
+            let delomg = self.omgcof * tsince;
+            let delm = self.xmcof * ((1.0 + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            let temp = delomg + delm;
+            xmp = xmdf + temp;
+            omega = omgadf - temp;
+            let tcube = tsq * tsince;
+            let tfour = tsince * tcube;
+            tempa = tempa - self.d2 * tsq - self.d3 * tcube - self.d4 * tfour;
+            tempe += self.bstar * self.c5 * (xmp.sin() - self.sinmo);
+            templ += self.t3cof * tcube + tfour * (self.t4cof + tsince * self.t5cof);
+        }
+
+        let a = self.aodp * tempa * tempa;
+        let e = self.eccentricity - tempe;
+        if a < 1.0 || !(-0.001..1.0).contains(&e) {
+            return Err(Sgp4Error::Decayed);
+        }
+        let e = e.max(1e-6);
+        let xl = xmp + omega + xnode + self.xnodp * templ;
+        let beta = (1.0 - e * e).sqrt();
+        let xn = xke / a.powf(1.5);
+
+        // long period periodics
+        let axn = e * omega.cos();
+        let temp = 1.0 / (a * beta * beta);
+        let xll = temp * self.xlcof * axn;
+        let aynl = temp * self.aycof;
+        let xlt = xl + xll;
+        let ayn = e * omega.sin() + aynl;
+
+        // solve kepler's equation
+        let capu = (xlt - xnode).rem_euclid(TAU);
+        let mut epw = capu;
+        let (mut sinepw, mut cosepw) = (0.0, 0.0);
+        let (mut temp3, mut temp4, mut temp5, mut temp6) = (0.0, 0.0, 0.0, 0.0);
+        for _ in 0..10 {
+            sinepw = epw.sin();
+            cosepw = epw.cos();
+            temp3 = axn * sinepw;
+            temp4 = ayn * cosepw;
+            temp5 = axn * cosepw;
+            temp6 = ayn * sinepw;
+            let next = (capu - temp4 + temp3 - epw) / (1.0 - temp5 - temp6) + epw;
+            let converged = (next - epw).abs() <= 1e-12;
+            epw = next;
+            if converged {
+                break;
+            }
+        }
+
+        // short period preliminary quantities
+        let ecose = temp5 + temp6;
+        let esine = temp3 - temp4;
+        let elsq = axn * axn + ayn * ayn;
+        let temp = 1.0 - elsq;
+        let pl = a * temp;
+        if pl <= 0.0 {
+            return Err(Sgp4Error::Decayed);
+        }
+        let r = a * (1.0 - ecose);
+        let temp1 = 1.0 / r;
+        let rdot = xke * a.sqrt() * esine * temp1;
+        let rfdot = xke * pl.sqrt() * temp1;
+        let temp2 = a * temp1;
+        let betal = temp.sqrt();
+        let temp3 = 1.0 / (1.0 + betal);
+        let cosu = temp2 * (cosepw - axn + ayn * esine * temp3);
+        let sinu = temp2 * (sinepw - ayn - axn * esine * temp3);
+        let u = sinu.atan2(cosu);
+        let sin2u = 2.0 * sinu * cosu;
+        let cos2u = 2.0 * cosu * cosu - 1.0;
+        let temp = 1.0 / pl;
+        let temp1 = CK2 * temp;
+        let temp2 = temp1 * temp;
+
+        // short period periodics
+        let rk = r * (1.0 - 1.5 * temp2 * betal * self.x3thm1) + 0.5 * temp1 * self.x1mth2 * cos2u;
+        let uk = u - 0.25 * temp2 * self.x7thm1 * sin2u;
+        let xnodek = xnode + 1.5 * temp2 * self.cosio * sin2u;
+        let xinck = self.inclination + 1.5 * temp2 * self.cosio * self.sinio * cos2u;
+        let rdotk = rdot - xn * temp1 * self.x1mth2 * sin2u;
+        let rfdotk = rfdot + xn * temp1 * (self.x1mth2 * cos2u + 1.5 * self.x3thm1);
+
+        if rk < 1.0 {
+            return Err(Sgp4Error::Decayed);
+        }
+
+        // orientation vectors
+        let (sinuk, cosuk) = uk.sin_cos();
+        let (sinik, cosik) = xinck.sin_cos();
+        let (sinnok, cosnok) = xnodek.sin_cos();
+        let xmx = -sinnok * cosik;
+        let xmy = cosnok * cosik;
+        let u_vec = DVec3::new(
+            xmx * sinuk + cosnok * cosuk,
+            xmy * sinuk + sinnok * cosuk,
+            sinik * sinuk,
+        );
+        let v_vec = DVec3::new(
+            xmx * cosuk - cosnok * sinuk,
+            xmy * cosuk - sinnok * sinuk,
+            sinik * cosuk,
+        );
+
+        Ok(StateVector {
+            position: u_vec * rk * EARTH_RADIUS_KM,
+            velocity: (u_vec * rdotk + v_vec * rfdotk) * EARTH_RADIUS_KM / 60.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1 m and 1 mm/s
+    const POSITION_TOLERANCE: f64 = 1e-3;
+    const VELOCITY_TOLERANCE: f64 = 1e-6;
+
+    fn model(line1: &str, line2: &str) -> Sgp4 {
+        Sgp4::new(&Tle::parse(None, line1, line2).unwrap()).unwrap()
+    }
+
+    // minutes since epoch, km and km/s
+    fn assert_states(model: &Sgp4, states: &[(f64, [f64; 3], [f64; 3])]) {
+        for &(tsince, position, velocity) in states {
+            let state = model.propagate(tsince).unwrap();
+            let position_error = state.position.distance(DVec3::from(position));
+            let velocity_error = state.velocity.distance(DVec3::from(velocity));
+            assert!(
+                position_error < POSITION_TOLERANCE,
+                "{tsince} min: position off by {position_error} km"
+            );
+            assert!(
+                velocity_error < VELOCITY_TOLERANCE,
+                "{tsince} min: velocity off by {velocity_error} km/s"
+            );
+        }
+    }
+
+    // reference states from Vallado et al., "Revisiting Spacetrack Report #3" (AIAA 2006-6753)
+
+    #[test]
+    fn spacetrack_report_3_test_case() {
+        let model = model(
+            "1 88888U          80275.98708465  .00073094  13844-3  66816-4 0    87",
+            "2 88888  72.8435 115.9689 0086731  52.6988 110.5714 16.05824518  1058",
+        );
+        assert_states(
+            &model,
+            &[
+                (
+                    0.0,
+                    [2328.96975262, -5995.22051338, 1719.97297192],
+                    [2.912073281, -0.983417956, -7.090816210],
+                ),
+                (
+                    360.0,
+                    [2456.10706533, -6071.93855503, 1222.89768554],
+                    [2.679390040, -0.448290811, -7.228792155],
+                ),
+                (
+                    720.0,
+                    [2567.56229695, -6112.50383922, 713.96374435],
+                    [2.440245751, 0.098109002, -7.319959258],
+                ),
+                (
+                    1080.0,
+                    [2663.08964352, -6115.48290885, 196.40072866],
+                    [2.196121564, 0.652415093, -7.362824152],
+                ),
+                (
+                    1440.0,
+                    [2742.55398832, -6079.67009123, -326.39012649],
+                    [1.948497651, 1.211072678, -7.356193131],
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn high_eccentricity() {
+        let model = model(
+            "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+            "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+        );
+        assert_states(
+            &model,
+            &[
+                (
+                    0.0,
+                    [7022.46529266, -1400.08296755, 0.03995155],
+                    [1.893841015, 6.405893759, 4.534807250],
+                ),
+                (
+                    720.0,
+                    [-7134.59340119, 6531.68641334, 3260.27186483],
+                    [-4.113793027, -2.911922039, -2.557327851],
+                ),
+                (
+                    1440.0,
+                    [-938.55923943, -6268.18748831, -4294.02924751],
+                    [7.536105209, -0.427127707, 0.989878080],
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn moderate_drag() {
+        let model = model(
+            "1 06251U 62025E   06176.82412014  .00008885  00000-0  12808-3 0  3985",
+            "2 06251  58.0579  54.0425 0030035 139.1568 221.1854 15.56387291  6774",
+        );
+        assert!(!model.simple);
+        assert_states(
+            &model,
+            &[
+                (
+                    0.0,
+                    [3988.31022699, 5498.96657235, 0.90055879],
+                    [-3.290032738, 2.357652820, 6.496623475],
+                ),
+                (
+                    720.0,
+                    [3692.60030028, -976.24265255, -5623.36447493],
+                    [3.897257243, 6.415554948, 1.429112190],
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn very_low_perigee_uses_the_simple_drag_model() {
+        let model = model(
+            "1 22312U 93002D   06094.46235912  .99999999  81888-5  49949-3 0  3953",
+            "2 22312  62.1486  77.4698 0308723 267.9229  88.7392 15.95744531 98783",
+        );
+        assert!(model.simple);
+        assert_states(
+            &model,
+            &[
+                (
+                    0.0,
+                    [1442.10132912, 6510.23625449, 8.83145885],
+                    [-3.475714837, 0.997262768, 6.835860345],
+                ),
+                (
+                    474.2028672,
+                    [-3181.54698042, -3831.29976506, 4096.80242787],
+                    [1.114159970, -6.104773578, -4.829967400],
+                ),
+            ],
+        );
+        // the orbit has decayed two days on
+        assert_eq!(model.propagate(2880.0).unwrap_err(), Sgp4Error::Decayed);
+    }
+
+    #[test]
+    fn deep_space_orbits_are_rejected() {
+        let tle = Tle::parse(
+            None,
+            "1 04632U 70093B   04031.91070959 -.00000084  00000-0  10000-3 0  9955",
+            "2 04632  11.4628 273.1101 1450506 207.6000 143.9350  1.20231981 44145",
+        )
+        .unwrap();
+        assert_eq!(Sgp4::new(&tle).unwrap_err(), Sgp4Error::DeepSpace);
+    }
+}
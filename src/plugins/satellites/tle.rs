@@ -0,0 +1,324 @@
+use std::fmt;
+
+use crate::plugins::clock::julian::CalendarDate;
+
+// Two-line element set parsing
+// format reference: https://celestrak.org/columns/v04n03/
+
+/// Mean orbital elements of a satellite at an epoch, as published in a TLE
+#[derive(Clone, Debug)]
+pub struct Tle {
+    pub name: String,
+    pub catalog_number: u32,
+    // julian date (UTC)
+    pub epoch: f64,
+    // 1/earth radii
+    pub bstar: f64,
+    // radians
+    pub inclination: f64,
+    pub right_ascension: f64,
+    pub eccentricity: f64,
+    pub argument_of_perigee: f64,
+    pub mean_anomaly: f64,
+    // revolutions per day
+    pub mean_motion: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TleError {
+    // line number
+    TooShort(usize),
+    WrongLineNumber(usize),
+    MissingLine(usize),
+    Checksum(usize),
+    // field name
+    InvalidField(&'static str),
+}
+
+impl fmt::Display for TleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TleError::TooShort(line) => write!(f, "line {line} is shorter than 69 characters"),
+            TleError::WrongLineNumber(line) => write!(f, "line {line} has the wrong line number"),
+            TleError::MissingLine(line) => write!(f, "line {line} is missing"),
+            TleError::Checksum(line) => write!(f, "line {line} failed its checksum"),
+            TleError::InvalidField(field) => write!(f, "invalid {field}"),
+        }
+    }
+}
+
+impl std::error::Error for TleError {}
+
+impl Tle {
+    /// Parses a single element set, `name` is the optional title line
+    pub fn parse(name: Option<&str>, line1: &str, line2: &str) -> Result<Self, TleError> {
+        let line1 = line1.trim_end();
+        let line2 = line2.trim_end();
+        validate_line(line1, 1)?;
+        validate_line(line2, 2)?;
+
+        let catalog_number = parse_field::<u32>(line1, 2..7, "catalog number")?;
+
+        // two digit year, 57-99 are 1900s
+        let epoch_year = parse_field::<i32>(line1, 18..20, "epoch year")?;
+        let epoch_year = if epoch_year < 57 {
+            2000 + epoch_year
+        } else {
+            1900 + epoch_year
+        };
+        let epoch_day = parse_field::<f64>(line1, 20..32, "epoch day")?;
+        let epoch = CalendarDate::new(epoch_year, 1, 1).to_julian_date() + epoch_day - 1.0;
+
+        let bstar = parse_implied_decimal(&line1[53..61]).ok_or(TleError::InvalidField("bstar"))?;
+
+        let inclination = parse_field::<f64>(line2, 8..16, "inclination")?.to_radians();
+        let right_ascension = parse_field::<f64>(line2, 17..25, "right ascension")?.to_radians();
+        let eccentricity = parse_field::<f64>(line2, 26..33, "eccentricity")? * 1e-7;
+        let argument_of_perigee =
+            parse_field::<f64>(line2, 34..42, "argument of perigee")?.to_radians();
+        let mean_anomaly = parse_field::<f64>(line2, 43..51, "mean anomaly")?.to_radians();
+        let mean_motion = parse_field::<f64>(line2, 52..63, "mean motion")?;
+
+        let name = name
+            .map(|name| name.trim().trim_start_matches("0 ").to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| catalog_number.to_string());
+
+        Ok(Tle {
+            name,
+            catalog_number,
+            epoch,
+            bstar,
+            inclination,
+            right_ascension,
+            eccentricity,
+            argument_of_perigee,
+            mean_anomaly,
+            mean_motion,
+        })
+    }
+
+    /// Orbital period in minutes
+    pub fn period(&self) -> f64 {
+        1440.0 / self.mean_motion
+    }
+}
+
+/// Parses every element set in a file, in both two-line and three-line (titled) form
+/// returns the parsed sets and the errors for sets that failed to parse
+/// sets are found by their `1 ` and `2 ` prefixes, so a broken set doesn't take the rest with it
+pub fn parse_tle_file(contents: &str) -> (Vec<Tle>, Vec<TleError>) {
+    let mut tles = Vec::new();
+    let mut errors = Vec::new();
+
+    let lines: Vec<&str> = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+
+    let is_line = |index: usize, number: &str| {
+        lines
+            .get(index)
+            .is_some_and(|line| line.starts_with(number))
+    };
+
+    let mut i = 0;
+    while i < lines.len() {
+        // anything that isn't line 1 or 2 is a title
+        let (name, first) = if is_line(i, "1 ") {
+            (None, i)
+        } else if is_line(i, "2 ") {
+            errors.push(TleError::MissingLine(1));
+            i += 1;
+            continue;
+        } else {
+            (Some(lines[i]), i + 1)
+        };
+
+        if !is_line(first, "1 ") {
+            // a title straight before a line 2 belongs to it
+            errors.push(TleError::MissingLine(1));
+            i = if is_line(first, "2 ") {
+                first + 1
+            } else {
+                first
+            };
+            continue;
+        }
+        if !is_line(first + 1, "2 ") {
+            errors.push(TleError::MissingLine(2));
+            i = first + 1;
+            continue;
+        }
+
+        match Tle::parse(name, lines[first], lines[first + 1]) {
+            Ok(tle) => tles.push(tle),
+            Err(error) => errors.push(error),
+        }
+        i = first + 2;
+    }
+
+    (tles, errors)
+}
+
+fn validate_line(line: &str, number: usize) -> Result<(), TleError> {
+    if line.len() < 69 || !line.is_ascii() {
+        return Err(TleError::TooShort(number));
+    }
+    if !line.starts_with(&format!("{number} ")) {
+        return Err(TleError::WrongLineNumber(number));
+    }
+
+    // sum of all digits, minus signs count as 1
+    let checksum: u32 = line[..68]
+        .chars()
+        .map(|c| match c {
+            '-' => 1,
+            c => c.to_digit(10).unwrap_or(0),
+        })
+        .sum();
+
+    match line[68..69].parse::<u32>() {
+        Ok(expected) if expected == checksum % 10 => Ok(()),
+        _ => Err(TleError::Checksum(number)),
+    }
+}
+
+fn parse_field<T: std::str::FromStr>(
+    line: &str,
+    columns: std::ops::Range<usize>,
+    field: &'static str,
+) -> Result<T, TleError> {
+    line[columns]
+        .trim()
+        .parse()
+        .map_err(|_| TleError::InvalidField(field))
+}
+
+/// Parses fields like " 12345-3" which mean 0.12345e-3
+fn parse_implied_decimal(field: &str) -> Option<f64> {
+    let field = field.trim();
+    if field.is_empty() {
+        return Some(0.0);
+    }
+
+    let (sign, digits) = match field.strip_prefix('-') {
+        Some(rest) => (-1.0, rest),
+        None => (1.0, field.trim_start_matches('+')),
+    };
+
+    // exponent is the trailing signed digit
+    let split = digits.rfind(['-', '+']).unwrap_or(digits.len());
+    let mantissa: f64 = format!("0.{}", &digits[..split]).parse().ok()?;
+    let exponent: i32 = if split < digits.len() {
+        digits[split..].parse().ok()?
+    } else {
+        0
+    };
+
+    Some(sign * mantissa * 10f64.powi(exponent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE1: &str = "1 88888U          80275.98708465  .00073094  13844-3  66816-4 0    87";
+    const LINE2: &str = "2 88888  72.8435 115.9689 0086731  52.6988 110.5714 16.05824518  1058";
+
+    #[test]
+    fn parses_each_column() {
+        let tle = Tle::parse(Some("0 TEST SAT"), LINE1, LINE2).unwrap();
+
+        assert_eq!(tle.name, "TEST SAT");
+        assert_eq!(tle.catalog_number, 88888);
+        let epoch = CalendarDate::new(1980, 1, 1).to_julian_date() + 274.98708465;
+        assert!((tle.epoch - epoch).abs() < 1e-9);
+        assert!((tle.bstar - 0.66816e-4).abs() < 1e-12);
+        assert!((tle.inclination - 72.8435f64.to_radians()).abs() < 1e-12);
+        assert!((tle.right_ascension - 115.9689f64.to_radians()).abs() < 1e-12);
+        assert!((tle.eccentricity - 0.0086731).abs() < 1e-12);
+        assert!((tle.argument_of_perigee - 52.6988f64.to_radians()).abs() < 1e-12);
+        assert!((tle.mean_anomaly - 110.5714f64.to_radians()).abs() < 1e-12);
+        assert_eq!(tle.mean_motion, 16.05824518);
+    }
+
+    #[test]
+    fn untitled_sets_are_named_by_catalog_number() {
+        let tle = Tle::parse(None, LINE1, LINE2).unwrap();
+        assert_eq!(tle.name, "88888");
+    }
+
+    #[test]
+    fn rejects_a_bad_checksum() {
+        let corrupted = LINE1.replace("80275.98708465", "80275.98708466");
+        assert_eq!(
+            Tle::parse(None, &corrupted, LINE2).unwrap_err(),
+            TleError::Checksum(1)
+        );
+
+        let wrong_digit = format!("{}9", &LINE2[..68]);
+        assert_eq!(
+            Tle::parse(None, LINE1, &wrong_digit).unwrap_err(),
+            TleError::Checksum(2)
+        );
+    }
+
+    #[test]
+    fn rejects_short_and_swapped_lines() {
+        assert_eq!(
+            Tle::parse(None, &LINE1[..60], LINE2).unwrap_err(),
+            TleError::TooShort(1)
+        );
+        assert_eq!(
+            Tle::parse(None, LINE2, LINE1).unwrap_err(),
+            TleError::WrongLineNumber(1)
+        );
+    }
+
+    #[test]
+    fn implied_decimals() {
+        let cases = [
+            (" 66816-4", 0.66816e-4),
+            ("-11606-4", -0.11606e-4),
+            (" 00000-0", 0.0),
+            (" 12345+1", 1.2345),
+            ("        ", 0.0),
+        ];
+        for (field, expected) in cases {
+            let value = parse_implied_decimal(field).unwrap();
+            assert!((value - expected).abs() < 1e-15, "{field:?} gave {value}");
+        }
+        assert_eq!(parse_implied_decimal(" 1x345-4"), None);
+    }
+
+    #[test]
+    fn parses_files_with_and_without_titles() {
+        let contents = format!("TEST SAT\n{LINE1}\n{LINE2}\n\n{LINE1}\n{LINE2}\nBROKEN\n{LINE1}\n");
+        let (tles, errors) = parse_tle_file(&contents);
+
+        assert_eq!(tles.len(), 2);
+        assert_eq!(tles[0].name, "TEST SAT");
+        assert_eq!(tles[1].name, "88888");
+        assert_eq!(errors, vec![TleError::MissingLine(2)]);
+    }
+
+    #[test]
+    fn resynchronises_after_a_truncated_set() {
+        let contents = format!(
+            "FIRST\n{LINE1}\n{LINE2}\nNO LINE 2\n{LINE1}\nNO LINE 1\n{LINE2}\n{LINE2}\nLAST\n{LINE1}\n{LINE2}\n"
+        );
+        let (tles, errors) = parse_tle_file(&contents);
+
+        let names: Vec<&str> = tles.iter().map(|tle| tle.name.as_str()).collect();
+        assert_eq!(names, ["FIRST", "LAST"]);
+        assert_eq!(
+            errors,
+            vec![
+                TleError::MissingLine(2),
+                TleError::MissingLine(1),
+                TleError::MissingLine(1),
+            ]
+        );
+    }
+}
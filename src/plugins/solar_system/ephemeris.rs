@@ -0,0 +1,285 @@
+use bevy::math::DVec3;
+
+use super::vsop87::{self, Series};
+use crate::plugins::clock::julian::{J2000, centuries_since_j2000};
+use crate::plugins::earth::frames::teme_to_ecef;
+
+// Low precision planet and moon positions
+// planets evaluate a truncated VSOP87A series (see `vsop87`), within a few arcseconds 1900-2100
+// the moon uses the Astronomical Almanac's low precision formulae (about 0.3 degrees)
+
+pub const AU: f64 = 149_597_870.7;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Planet {
+    Mercury,
+    Venus,
+    Earth,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+}
+
+impl Planet {
+    pub const ALL: [Planet; 8] = [
+        Planet::Mercury,
+        Planet::Venus,
+        Planet::Earth,
+        Planet::Mars,
+        Planet::Jupiter,
+        Planet::Saturn,
+        Planet::Uranus,
+        Planet::Neptune,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Planet::Mercury => "Mercury",
+            Planet::Venus => "Venus",
+            Planet::Earth => "Earth",
+            Planet::Mars => "Mars",
+            Planet::Jupiter => "Jupiter",
+            Planet::Saturn => "Saturn",
+            Planet::Uranus => "Uranus",
+            Planet::Neptune => "Neptune",
+        }
+    }
+
+    /// Mean equatorial radius in km
+    pub fn radius(&self) -> f64 {
+        match self {
+            Planet::Mercury => 2439.7,
+            Planet::Venus => 6051.8,
+            Planet::Earth => 6378.1,
+            Planet::Mars => 3396.2,
+            Planet::Jupiter => 71492.0,
+            Planet::Saturn => 60268.0,
+            Planet::Uranus => 25559.0,
+            Planet::Neptune => 24764.0,
+        }
+    }
+
+    /// Sidereal orbital period in days
+    pub fn period(&self) -> f64 {
+        match self {
+            Planet::Mercury => 87.969,
+            Planet::Venus => 224.701,
+            Planet::Earth => 365.256,
+            Planet::Mars => 686.980,
+            Planet::Jupiter => 4332.589,
+            Planet::Saturn => 10759.22,
+            Planet::Uranus => 30685.4,
+            Planet::Neptune => 60189.0,
+        }
+    }
+
+    fn series(&self) -> &'static Series {
+        match self {
+            Planet::Mercury => &vsop87::MERCURY,
+            Planet::Venus => &vsop87::VENUS,
+            Planet::Earth => &vsop87::EARTH,
+            Planet::Mars => &vsop87::MARS,
+            Planet::Jupiter => &vsop87::JUPITER,
+            Planet::Saturn => &vsop87::SATURN,
+            Planet::Uranus => &vsop87::URANUS,
+            Planet::Neptune => &vsop87::NEPTUNE,
+        }
+    }
+
+    /// Heliocentric position in km, J2000 ecliptic frame
+    pub fn heliocentric_position(&self, julian_date: f64) -> DVec3 {
+        // julian millennia
+        let t = (julian_date - J2000) / 365_250.0;
+        let series = self.series();
+        DVec3::new(
+            evaluate(series.x, t),
+            evaluate(series.y, t),
+            evaluate(series.z, t),
+        ) * AU
+    }
+
+    /// Positions over one revolution centered on `julian_date`, in km, J2000 ecliptic frame
+    /// the path doesn't quite close, the orbit itself drifts over a revolution
+    pub fn orbit(&self, julian_date: f64, segments: usize) -> Vec<DVec3> {
+        let period = self.period();
+        (0..=segments)
+            .map(|i| {
+                let offset = (i as f64 / segments as f64 - 0.5) * period;
+                self.heliocentric_position(julian_date + offset)
+            })
+            .collect()
+    }
+}
+
+/// Sums one coordinate's series, a polynomial in `t` whose coefficients are sums of cosines
+fn evaluate(powers: &[&[vsop87::Term]], t: f64) -> f64 {
+    powers.iter().rev().fold(0.0, |sum, terms| {
+        let coefficient: f64 = terms.iter().map(|[a, b, c]| a * (b + c * t).cos()).sum();
+        sum * t + coefficient
+    })
+}
+
+/// Geocentric position of the moon in km, ecliptic frame of date
+pub fn moon_position(julian_date: f64) -> DVec3 {
+    let t = centuries_since_j2000(julian_date);
+    let term =
+        |amplitude: f64, phase: f64, rate: f64| amplitude * (phase + rate * t).to_radians().sin();
+    let cos_term =
+        |amplitude: f64, phase: f64, rate: f64| amplitude * (phase + rate * t).to_radians().cos();
+
+    let longitude = 218.32 + 481267.881 * t + term(6.29, 135.0, 477198.87)
+        - term(1.27, 259.3, -413335.36)
+        + term(0.66, 235.7, 890534.22)
+        + term(0.21, 269.9, 954397.74)
+        - term(0.19, 357.5, 35999.05)
+        - term(0.11, 186.5, 966404.03);
+    let latitude = term(5.13, 93.3, 483202.02) + term(0.28, 228.2, 960400.89)
+        - term(0.28, 318.3, 6003.15)
+        - term(0.17, 217.6, -407332.21);
+    let parallax = 0.9508
+        + cos_term(0.0518, 135.0, 477198.87)
+        + cos_term(0.0095, 259.3, -413335.36)
+        + cos_term(0.0078, 235.7, 890534.22)
+        + cos_term(0.0028, 269.9, 954397.74);
+
+    let distance = 6378.14 / parallax.to_radians().sin();
+    let (sin_lon, cos_lon) = longitude.to_radians().sin_cos();
+    let (sin_lat, cos_lat) = latitude.to_radians().sin_cos();
+
+    DVec3::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat) * distance
+}
+
+/// Rotates a J2000 ecliptic vector into the ecliptic of date
+/// only general precession in longitude is applied, the ecliptic itself moves much less
+pub fn precess_ecliptic(ecliptic: DVec3, julian_date: f64) -> DVec3 {
+    let precession = (1.396971 * centuries_since_j2000(julian_date)).to_radians();
+    let (sin_p, cos_p) = precession.sin_cos();
+    DVec3::new(
+        cos_p * ecliptic.x - sin_p * ecliptic.y,
+        sin_p * ecliptic.x + cos_p * ecliptic.y,
+        ecliptic.z,
+    )
+}
+
+/// Ecliptic of date to the scene's earth-fixed frame, in f64 to keep AU distances exact
+pub fn ecliptic_to_scene(ecliptic: DVec3, julian_date: f64) -> DVec3 {
+    // same obliquity as the solar ephemeris, so the sun lands where the light comes from
+    let obliquity = (23.439 - 0.0000004 * (julian_date - J2000)).to_radians();
+    let (sin_e, cos_e) = obliquity.sin_cos();
+    let equatorial = DVec3::new(
+        ecliptic.x,
+        cos_e * ecliptic.y - sin_e * ecliptic.z,
+        sin_e * ecliptic.y + cos_e * ecliptic.z,
+    );
+
+    // same swizzle as `ecef_to_scene`, without dropping to f32
+    let ecef = teme_to_ecef(equatorial, julian_date);
+    DVec3::new(ecef.y, ecef.z, ecef.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // full VSOP87A positions (AU), from the vsop87 crate's tests of the complete series
+    const REFERENCE: [(Planet, f64, [f64; 3]); 16] = [
+        (
+            Planet::Mercury,
+            2451545.0,
+            [-0.1300934, -0.4472877, -0.0245984],
+        ),
+        (
+            Planet::Mercury,
+            2415020.0,
+            [-0.3897247, -0.1502242, 0.0236199],
+        ),
+        (
+            Planet::Venus,
+            2451545.0,
+            [-0.7183023, -0.0326546, 0.0410143],
+        ),
+        (
+            Planet::Venus,
+            2415020.0,
+            [0.6971428, -0.2033631, -0.0430201],
+        ),
+        (
+            Planet::Earth,
+            2451545.0,
+            [-0.1771355, 0.9672416, -0.0000039],
+        ),
+        (Planet::Earth, 2415020.0, [-0.1883080, 0.9650689, 0.0002150]),
+        (Planet::Mars, 2451545.0, [1.3907159, -0.0134157, -0.0344678]),
+        (Planet::Mars, 2415020.0, [0.4284332, -1.3552354, -0.0389650]),
+        (
+            Planet::Jupiter,
+            2451545.0,
+            [4.0011740, 2.9385810, -0.1017838],
+        ),
+        (
+            Planet::Jupiter,
+            2415020.0,
+            [-3.0191224, -4.4582564, 0.0858642],
+        ),
+        (
+            Planet::Saturn,
+            2451545.0,
+            [6.4064069, 6.5699929, -0.3690768],
+        ),
+        (
+            Planet::Saturn,
+            2415020.0,
+            [-0.3695974, -10.0582398, 0.1916854],
+        ),
+        (
+            Planet::Uranus,
+            2451545.0,
+            [14.4318934, -13.7343163, -0.2381422],
+        ),
+        (
+            Planet::Uranus,
+            2415020.0,
+            [-6.4810833, -17.8526893, 0.0177935],
+        ),
+        (
+            Planet::Neptune,
+            2451545.0,
+            [16.8121117, -24.9916631, 0.1272190],
+        ),
+        (
+            Planet::Neptune,
+            2415020.0,
+            [1.5164557, 29.8254539, -0.6491400],
+        ),
+    ];
+
+    #[test]
+    fn truncated_series_matches_the_full_theory() {
+        for (planet, julian_date, expected) in REFERENCE {
+            let expected = DVec3::from_array(expected);
+            let position = planet.heliocentric_position(julian_date) / AU;
+            // 3 arcseconds
+            let tolerance = 1.5e-5 * expected.length();
+            assert!(
+                position.distance(expected) < tolerance,
+                "{} at {julian_date}: {position} vs {expected}",
+                planet.name()
+            );
+        }
+    }
+
+    #[test]
+    fn orbits_span_one_revolution() {
+        let jd = 2460000.5;
+        for planet in Planet::ALL {
+            let orbit = planet.orbit(jd, 64);
+            assert_eq!(orbit.len(), 65);
+            // the middle sample is now, the ends come back to nearly the same place
+            assert!(orbit[32].distance(planet.heliocentric_position(jd)) < 1.0);
+            let gap = orbit[0].distance(orbit[64]) / orbit[0].length();
+            assert!(gap < 0.01, "{} orbit is open by {gap}", planet.name());
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use bevy::math::DVec3;
+use bevy::prelude::*;
+
+pub mod ephemeris;
+pub mod vsop87;
+
+use crate::config::*;
+use crate::plugins::clock::SimulationClock;
+use ephemeris::{AU, Planet, ecliptic_to_scene, moon_position, precess_ecliptic};
+
+// Heliocentric solar system view
+// the scene stays earth-fixed with the earth at the origin, which acts as a floating origin:
+// every other body is placed relative to the earth in f64 and only converted to f32 at the end,
+// so the km units of the globe keep their precision while the sun sits 1 AU away
+// bevy's reverse-z infinite projection keeps depth precise over that range
+
+const TOGGLE_VIEW_KEY: KeyCode = KeyCode::KeyV;
+const ORBIT_SEGMENTS: usize = 128;
+// days the clock may move before the orbit lines are sampled again
+const ORBIT_REFRESH_INTERVAL: f64 = 1.0;
+
+pub struct SolarSystemPlugin;
+
+impl Plugin for SolarSystemPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SolarSystemView>()
+            .add_systems(Startup, spawn_bodies)
+            .add_systems(
+                Update,
+                (toggle_view, move_camera, position_bodies, draw_orbits).chain(),
+            );
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViewMode {
+    Earth,
+    Heliocentric,
+}
+
+/// Current view and how far the camera is through the transition to it
+#[derive(Resource)]
+pub struct SolarSystemView {
+    pub mode: ViewMode,
+    // 0 is the globe view, 1 the heliocentric view
+    pub progress: f32,
+    pub show_moon: bool,
+    pub show_planets: bool,
+    // camera pose to return to, captured when leaving the globe view
+    earth_camera: Option<Transform>,
+}
+
+impl Default for SolarSystemView {
+    fn default() -> Self {
+        SolarSystemView {
+            mode: ViewMode::Earth,
+            progress: 0.0,
+            show_moon: SHOW_MOON,
+            show_planets: SHOW_PLANETS,
+            earth_camera: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Body {
+    Sun,
+    Moon,
+    Planet(Planet),
+}
+
+impl Body {
+    /// km
+    pub fn radius(&self) -> f64 {
+        match self {
+            Body::Sun => 695_700.0,
+            Body::Moon => 1737.4,
+            Body::Planet(planet) => planet.radius(),
+        }
+    }
+
+    /// Position relative to the earth in km, ecliptic frame of date
+    pub fn geocentric_position(&self, julian_date: f64) -> DVec3 {
+        let earth = Planet::Earth.heliocentric_position(julian_date);
+        match self {
+            Body::Sun => precess_ecliptic(-earth, julian_date),
+            Body::Moon => moon_position(julian_date),
+            Body::Planet(Planet::Earth) => DVec3::ZERO,
+            Body::Planet(planet) => precess_ecliptic(
+                planet.heliocentric_position(julian_date) - earth,
+                julian_date,
+            ),
+        }
+    }
+}
+
+/// A sun, moon or planet sphere, scaled to its radius
+#[derive(Component)]
+pub struct CelestialBody {
+    pub body: Body,
+    // whether the heliocentric view may enlarge it, the moon would swallow the earth
+    exaggerate: bool,
+}
+
+fn spawn_bodies(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let sphere = meshes.add(Sphere::new(1.0).mesh().ico(5).unwrap());
+
+    let unlit = |color: Color| StandardMaterial {
+        base_color: color,
+        unlit: true,
+        ..default()
+    };
+
+    let mut bodies = vec![
+        (
+            Body::Sun,
+            materials.add(unlit(Color::srgb(1.0, 0.95, 0.8))),
+            true,
+        ),
+        // the moon is close enough to the earth to share its directional light
+        (
+            Body::Moon,
+            materials.add(StandardMaterial {
+                base_color: Color::srgb(0.6, 0.6, 0.6),
+                perceptual_roughness: 1.0,
+                ..default()
+            }),
+            false,
+        ),
+    ];
+
+    // planets are unlit, the directional light only points the right way near the earth
+    for planet in Planet::ALL {
+        let color = match planet {
+            Planet::Mercury => Color::srgb(0.6, 0.58, 0.55),
+            Planet::Venus => Color::srgb(0.9, 0.85, 0.7),
+            Planet::Earth => Color::srgb(0.3, 0.5, 0.9),
+            Planet::Mars => Color::srgb(0.8, 0.4, 0.25),
+            Planet::Jupiter => Color::srgb(0.85, 0.75, 0.6),
+            Planet::Saturn => Color::srgb(0.9, 0.82, 0.6),
+            Planet::Uranus => Color::srgb(0.6, 0.85, 0.9),
+            Planet::Neptune => Color::srgb(0.35, 0.5, 0.9),
+        };
+        bodies.push((Body::Planet(planet), materials.add(unlit(color)), true));
+    }
+
+    for (body, material, exaggerate) in bodies {
+        let name = match body {
+            Body::Sun => "Sun",
+            Body::Moon => "Moon",
+            Body::Planet(planet) => planet.name(),
+        };
+        commands.spawn((
+            CelestialBody { body, exaggerate },
+            Name::new(name),
+            Mesh3d(sphere.clone()),
+            MeshMaterial3d(material),
+            Transform::from_scale(Vec3::splat(body.radius() as f32)),
+            Visibility::Hidden,
+        ));
+    }
+}
+
+fn toggle_view(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut view: ResMut<SolarSystemView>,
+    cameras: Query<&Transform, With<Camera3d>>,
+) {
+    if keys.just_pressed(TOGGLE_VIEW_KEY) {
+        view.mode = match view.mode {
+            ViewMode::Earth => ViewMode::Heliocentric,
+            ViewMode::Heliocentric => ViewMode::Earth,
+        };
+
+        // remember where the globe was viewed from, so the camera can come back to it
+        if view.progress == 0.0 {
+            view.earth_camera = cameras.single().ok().copied();
+        }
+    }
+
+    let target = match view.mode {
+        ViewMode::Earth => 0.0,
+        ViewMode::Heliocentric => 1.0,
+    };
+    let step = time.delta_secs() / VIEW_TRANSITION_TIME;
+    view.progress = if target > view.progress {
+        (view.progress + step).min(target)
+    } else {
+        (view.progress - step).max(target)
+    };
+}
+
+fn position_bodies(
+    clock: Res<SimulationClock>,
+    view: Res<SolarSystemView>,
+    cameras: Query<&Transform, (With<Camera3d>, Without<CelestialBody>)>,
+    mut bodies: Query<(&CelestialBody, &mut Transform, &mut Visibility)>,
+) {
+    let camera = cameras
+        .single()
+        .map_or(Vec3::ZERO, |transform| transform.translation);
+
+    for (celestial, mut transform, mut visibility) in &mut bodies {
+        let body = celestial.body;
+        let position = ecliptic_to_scene(
+            body.geocentric_position(clock.julian_date),
+            clock.julian_date,
+        );
+        let translation = position.as_vec3();
+        let radius = body.radius() as f32;
+
+        // grow far away bodies to a minimum apparent size, blended in with the view
+        let distance = camera.distance(translation);
+        let enlarged = (distance * MIN_BODY_ANGULAR_SIZE / radius).max(1.0);
+        let scale = if celestial.exaggerate {
+            1.0 + (enlarged - 1.0) * view.progress
+        } else {
+            1.0
+        };
+
+        let visible = match body {
+            Body::Sun => true,
+            Body::Moon => view.show_moon,
+            // the earth marker only shows once it has grown around the globe
+            Body::Planet(Planet::Earth) => view.show_planets && scale > 1.05,
+            Body::Planet(_) => view.show_planets && view.progress > 0.0,
+        };
+
+        *transform =
+            Transform::from_translation(translation).with_scale(Vec3::splat(radius * scale));
+        *visibility = if visible {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Zooms logarithmically from the globe out to a view of the inner solar system centered on the sun
+/// the heliocentric pose is fixed in the ecliptic frame, so the planets hold still while the earth turns
+fn move_camera(
+    clock: Res<SimulationClock>,
+    view: Res<SolarSystemView>,
+    mut cameras: Query<&mut Transform, With<Camera3d>>,
+) {
+    // leave the camera alone in the globe view
+    if view.progress == 0.0 {
+        return;
+    }
+    let Some(earth_camera) = view.earth_camera else {
+        return;
+    };
+    let Ok(mut transform) = cameras.single_mut() else {
+        return;
+    };
+
+    let jd = clock.julian_date;
+    let to_scene = |ecliptic: DVec3| ecliptic_to_scene(ecliptic, jd);
+
+    let sun = to_scene(Body::Sun.geocentric_position(jd));
+
+    // look down on the sun from 30 degrees above the ecliptic
+    let (sin_tilt, cos_tilt) = 60f64.to_radians().sin_cos();
+    let offset = to_scene(DVec3::new(0.0, -cos_tilt, sin_tilt)).normalize();
+    let up = to_scene(DVec3::Y).as_vec3();
+    let far_distance = SOLAR_SYSTEM_CAMERA_DISTANCE * AU;
+    let far_rotation = Transform::from_translation((sun + offset * far_distance).as_vec3())
+        .looking_at(sun.as_vec3(), up)
+        .rotation;
+
+    let near_distance = earth_camera.translation.length() as f64;
+    let near_rotation = earth_camera.rotation;
+
+    let t = view.progress;
+    let t = t * t * (3.0 - 2.0 * t);
+    let distance = near_distance * (far_distance / near_distance).powf(t as f64);
+
+    // move the focus over to the sun in step with the zoom, so the globe stays in frame early on
+    let focus_weight =
+        ((distance - near_distance) / (far_distance - near_distance)).clamp(0.0, 1.0);
+    let focus = sun * focus_weight;
+
+    let rotation = near_rotation.slerp(far_rotation, t);
+    // cameras look down their local -z
+    let position = focus + (rotation * Vec3::Z).as_dvec3() * distance;
+
+    *transform = Transform {
+        translation: position.as_vec3(),
+        rotation,
+        ..default()
+    };
+}
+
+/// Sampled orbit lines in the J2000 ecliptic, recomputed as the clock moves
+#[derive(Default)]
+struct OrbitCache {
+    orbits: Vec<Vec<DVec3>>,
+    // julian date the orbits were sampled around
+    computed_at: f64,
+}
+
+fn draw_orbits(
+    mut gizmos: Gizmos,
+    mut cache: Local<OrbitCache>,
+    clock: Res<SimulationClock>,
+    view: Res<SolarSystemView>,
+) {
+    if view.progress == 0.0 || !view.show_planets {
+        return;
+    }
+
+    let jd = clock.julian_date;
+    if cache.orbits.is_empty() || (jd - cache.computed_at).abs() > ORBIT_REFRESH_INTERVAL {
+        cache.orbits = Planet::ALL
+            .iter()
+            .map(|planet| planet.orbit(jd, ORBIT_SEGMENTS))
+            .collect();
+        cache.computed_at = jd;
+    }
+
+    let earth = Planet::Earth.heliocentric_position(jd);
+    let color = Color::srgba(0.6, 0.7, 0.9, 0.4 * view.progress);
+
+    for orbit in &cache.orbits {
+        let points = orbit
+            .iter()
+            .map(|&point| ecliptic_to_scene(precess_ecliptic(point - earth, jd), jd).as_vec3());
+        gizmos.linestrip(points, color);
+    }
+}
@@ -0,0 +1,1543 @@
+// Truncated VSOP87A planetary theory, generated from the full series
+// Bretagnon & Francou, "Planetary theories in rectangular and spherical variables" (1988)
+// https://cdsarc.cds.unistra.fr/viz-bin/cat/VI/81
+// heliocentric x, y and z (AU) in the J2000 ecliptic, each a polynomial in julian millennia t
+// from J2000 whose coefficients are sums of terms A cos(B + C t)
+// terms whose amplitude at |t| = 0.1 is below a millionth of the planet's orbit radius are dropped,
+// which keeps positions within about 3 arcseconds of the full theory between 1900 and 2100
+
+use std::f64::consts::PI;
+
+/// A, B and C of one term
+pub type Term = [f64; 3];
+
+/// Terms of each power of t, for x, y and z
+pub struct Series {
+    pub x: &'static [&'static [Term]],
+    pub y: &'static [&'static [Term]],
+    pub z: &'static [&'static [Term]],
+}
+
+pub const MERCURY: Series = Series {
+    x: &[
+        &[
+            [0.37546291728, 4.39651506942, 26087.9031415742],
+            [0.03825746672, 1.16485604339, 52175.8062831484],
+            [0.02625615963, PI, 0.0],
+            [0.00584261333, 4.21599394757, 78263.70942472259],
+            [0.00105716695, 0.98379033182, 104351.61256629678],
+            [0.0002101173, 4.03469353923, 130439.51570787099],
+            [4.433373e-5, 0.80236674527, 156527.41884944518],
+            [9.74967e-6, 3.85319674536, 182615.3219910194],
+            [7.00327e-6, 4.45478725367, 24978.5245894808],
+            [6.26468e-6, 1.18563492001, 27197.2816936676],
+            [4.46989e-6, 2.97507181503, 1059.3819301892],
+            [3.98401e-6, 1.86487895049, 20426.571092422],
+            [2.77216e-6, 3.77909548342, 31749.2351907264],
+            [1.90657e-6, 4.27201801941, 53285.1848352418],
+            [1.8179e-6, 4.94857138217, 1109.3785520934],
+            [1.94418e-6, 0.67806013045, 4551.9534970588],
+            [2.21028e-6, 0.62082250658, 208703.2251325936],
+            [1.90713e-6, 1.17385212686, 5661.3320491522],
+            [1.38492e-6, 1.22446421973, 51066.427731055],
+            [1.51693e-6, 2.67604566886, 51116.4243529592],
+            [9.5481e-7, 0.16753795386, 529.6909650946],
+            [1.14338e-6, 0.56002737806, 57837.1383323006],
+            [7.5179e-7, 6.06954012703, 27147.28507176339],
+            [7.4528e-7, 4.92454709213, 46514.4742339962],
+            [6.8378e-7, 0.19832816518, 25132.3033999656],
+            [6.5034e-7, 4.90982770245, 21535.9496445154],
+            [8.3764e-7, 3.18666883018, 10213.285546211],
+            [5.7815e-7, 5.70200416012, 14765.2390432698],
+            [5.8577e-7, 5.41671940122, 27043.5028831828],
+            [6.0503e-7, 4.99652964522, 47623.8527860896],
+            [5.2662e-7, 2.29801650543, 26617.5941066688],
+            [5.4816e-7, 5.30909046008, 12566.1516999828],
+            [4.783e-7, 1.04296800531, 79373.087976816],
+            [5.1223e-7, 3.53627826803, 426.598190876],
+            [5.1287e-7, 3.67162149494, 234791.12827416777],
+            [4.8376e-7, 3.51890954721, 1589.0728952838],
+            [4.8308e-7, 3.65259825204, 25558.2121764796],
+            [4.8571e-7, 5.72915514275, 77204.32749453338],
+            [4.4563e-7, 2.47566796995, 41962.5207369374],
+        ],
+        &[
+            [0.00318848034, 0.0, 0.0],
+            [0.00105289019, 5.91600475006, 52175.8062831484],
+            [0.00032316001, 2.68247273347, 78263.70942472259],
+            [0.00011992889, 5.81575112963, 26087.9031415742],
+            [8.7832e-5, 5.73285747425, 104351.61256629678],
+            [2.329042e-5, 2.50023793407, 130439.51570787099],
+            [6.14473e-6, 5.55087602844, 156527.41884944518],
+        ],
+    ],
+    y: &[
+        &[
+            [0.37953642888, 2.8378061782, 26087.9031415742],
+            [0.11626131831, PI, 0.0],
+            [0.03854668215, 5.88780608966, 52175.8062831484],
+            [0.00587711268, 2.65498896201, 78263.70942472259],
+            [0.00106235493, 5.70550616735, 104351.61256629678],
+            [0.00021100828, 2.47291315849, 130439.51570787099],
+            [4.450056e-5, 5.52354907071, 156527.41884944518],
+            [9.78286e-6, 2.29102643026, 182615.3219910194],
+            [7.075e-6, 2.89516591531, 24978.5245894808],
+            [6.54742e-6, 5.92892123881, 27197.2816936676],
+            [4.48561e-6, 1.40595042211, 1059.3819301892],
+            [4.02168e-6, 0.30317998006, 20426.571092422],
+            [2.90604e-6, 2.23645868392, 31749.2351907264],
+            [1.91358e-6, 2.70792842547, 53285.1848352418],
+            [1.81119e-6, 0.23941291054, 1109.3785520934],
+            [1.93372e-6, 5.38698781997, 4551.9534970588],
+            [2.21718e-6, 5.3417067657, 208703.2251325936],
+            [1.39514e-6, 5.94698662319, 51066.427731055],
+            [1.54924e-6, 1.12201865761, 51116.4243529592],
+            [1.77242e-6, 2.78855813429, 5661.3320491522],
+            [1.16072e-6, 5.28608170116, 57837.1383323006],
+            [8.1399e-7, 3.50862797958, 21535.9496445154],
+            [8.6595e-7, 5.06999843254, 529.6909650946],
+            [7.5457e-7, 4.50396814445, 27147.28507176339],
+            [7.5078e-7, 3.36291170975, 46514.4742339962],
+            [6.9045e-7, 4.92160428335, 25132.3033999656],
+            [6.346e-7, 3.91239075334, 27043.5028831828],
+            [8.325e-7, 1.60127885818, 10213.285546211],
+            [5.816e-7, 4.1367760221, 14765.2390432698],
+            [6.1405e-7, 3.43747372432, 47623.8527860896],
+            [5.405e-7, 0.72631991357, 26617.5941066688],
+            [5.5113e-7, 3.74298611127, 12566.1516999828],
+            [5.2556e-7, 1.98636705426, 25558.2121764796],
+            [4.7932e-7, 5.76195364026, 79373.087976816],
+            [5.1303e-7, 1.96782292508, 426.598190876],
+            [5.1435e-7, 2.1092138113, 234791.12827416777],
+            [4.8521e-7, 1.95016897012, 1589.0728952838],
+            [4.9166e-7, 4.17094551348, 77204.32749453338],
+            [4.5435e-7, 0.91835136158, 41962.5207369374],
+        ],
+        &[
+            [0.00107803852, 4.34964793883, 52175.8062831484],
+            [0.00080651544, PI, 0.0],
+            [0.00032715354, 1.11763734425, 78263.70942472259],
+            [8.858158e-5, 4.16852401867, 104351.61256629678],
+            [0.00011914709, 1.2213998634, 26087.9031415742],
+            [2.344469e-5, 0.93615372641, 130439.51570787099],
+            [6.17838e-6, 3.98693992284, 156527.41884944518],
+        ],
+        &[[4.612157e-5, 0.0, 0.0]],
+    ],
+    z: &[
+        &[
+            [0.04607665326, 1.99295081967, 26087.9031415742],
+            [0.00708734365, PI, 0.0],
+            [0.00469171617, 5.04215742764, 52175.8062831484],
+            [0.00071626395, 1.80894256071, 78263.70942472259],
+            [0.00012957446, 4.8592203201, 104351.61256629678],
+            [2.575002e-5, 1.62646731545, 130439.51570787099],
+            [5.43259e-6, 4.67698860167, 156527.41884944518],
+            [1.19462e-6, 1.44437994097, 182615.3219910194],
+            [7.9477e-7, 4.94442849343, 27197.2816936676],
+            [8.2635e-7, 2.03127961938, 24978.5245894808],
+            [4.4712e-7, 0.52659463739, 1059.3819301892],
+            [4.3131e-7, 5.66922412963, 20426.571092422],
+        ],
+        &[
+            [0.00108722177, 3.91134750825, 26087.9031415742],
+            [0.00057826621, PI, 0.0],
+            [4.297352e-5, 2.56373047177, 52175.8062831484],
+            [2.435833e-5, 0.05112640506, 78263.70942472259],
+            [7.95699e-6, 3.20041081922, 104351.61256629678],
+        ],
+    ],
+};
+
+pub const VENUS: Series = Series {
+    x: &[
+        &[
+            [0.72211281391, 3.17575836361, 10213.285546211],
+            [0.00486448018, 0.0, 0.0],
+            [0.00244500474, 4.05566613861, 20426.571092422],
+            [2.800281e-5, 0.33147492492, 2352.8661537718],
+            [1.949669e-5, 4.23196016801, 1577.3435424478],
+            [1.241717e-5, 4.93573787058, 30639.856638633],
+            [1.162258e-5, 2.87958246189, 18073.7049386502],
+            [1.04669e-5, 1.75434920413, 6283.0758499914],
+            [7.64293e-6, 0.59379588767, 529.6909650946],
+            [6.69461e-6, 1.45721228842, 14143.4952424306],
+            [6.57195e-6, 0.50086450258, 8635.9420037632],
+            [4.76445e-6, 5.8430978284, 10186.9872264112],
+            [4.74466e-6, 3.64991163504, 10239.5838660108],
+            [5.59074e-6, 1.16554783301, 22003.9146348698],
+            [5.46778e-6, 2.71490884128, 11790.6290886588],
+            [4.08988e-6, 3.92725431993, 775.522611324],
+            [2.87059e-6, 2.79578956958, 9683.5945811164],
+            [2.68822e-6, 0.42000307859, 10742.9765113056],
+            [2.97742e-6, 5.65655811166, 5507.5532386674],
+            [2.14149e-6, 0.74884072598, 10021.8372800994],
+            [2.41103e-6, 5.80627627098, 10988.808157535],
+            [2.09303e-6, 2.47129919435, 10404.7338123226],
+            [2.71022e-6, 2.6237778032, 19896.8801273274],
+            [2.5448e-6, 5.09961413241, 9153.9036160218],
+            [1.74985e-6, 6.12704911391, 191.4482661116],
+            [1.7504e-6, 3.5316397756, 9437.762934887],
+            [1.2299e-6, 1.58925439374, 1059.3819301892],
+            [1.54379e-6, 5.3560770439, 4705.7323075436],
+            [1.47455e-6, 5.55780022641, 19651.048481098],
+            [8.2633e-7, 4.14038372098, 15720.8387848784],
+        ],
+        &[
+            [0.00033862636, PI, 0.0],
+            [0.00017234992, 0.92721124604, 20426.571092422],
+            [6.510416e-5, 2.19289889733, 10213.285546211],
+        ],
+    ],
+    y: &[
+        &[
+            [0.72324820731, 1.60573808356, 10213.285546211],
+            [0.00549506273, PI, 0.0],
+            [0.0024488479, 2.48564954004, 20426.571092422],
+            [2.789807e-5, 5.04214523606, 2352.8661537718],
+            [1.933868e-5, 5.80597990261, 1577.3435424478],
+            [1.243658e-5, 3.36573697344, 30639.856638633],
+            [1.16448e-5, 1.30970620277, 18073.7049386502],
+            [1.041872e-5, 0.18129136925, 6283.0758499914],
+            [7.70549e-6, 5.30366680002, 529.6909650946],
+            [6.70527e-6, 6.17032430376, 14143.4952424306],
+            [6.57675e-6, 5.21360427049, 8635.9420037632],
+            [4.77182e-6, 4.27309387857, 10186.9872264112],
+            [4.7569e-6, 2.08026660779, 10239.5838660108],
+            [5.59632e-6, 5.87842445808, 22003.9146348698],
+            [5.42381e-6, 1.15040078193, 11790.6290886588],
+            [3.67778e-6, 2.17623939625, 9437.762934887],
+            [4.07052e-6, 2.35411923107, 775.522611324],
+            [2.75646e-6, 1.23968348521, 9683.5945811164],
+            [2.68898e-6, 5.13218653673, 10742.9765113056],
+            [3.02219e-6, 0.94310085463, 5507.5532386674],
+            [2.14465e-6, 5.46202116536, 10021.8372800994],
+            [2.41591e-6, 4.23657289457, 10988.808157535],
+            [2.07456e-6, 0.88354754907, 10404.7338123226],
+            [2.74181e-6, 0.42777141449, 9153.9036160218],
+            [2.71427e-6, 1.0537672066, 19896.8801273274],
+            [1.75993e-6, 1.40721119359, 191.4482661116],
+            [1.2312e-6, 0.01710584424, 1059.3819301892],
+            [1.5408e-6, 3.78432893453, 4705.7323075436],
+            [1.46618e-6, 3.98848869231, 19651.048481098],
+            [8.0946e-7, 2.57565274435, 15720.8387848784],
+        ],
+        &[
+            [0.0003923143, 0.0, 0.0],
+            [0.00017282326, 5.638247359, 20426.571092422],
+            [5.968075e-5, 3.60854944086, 10213.285546211],
+        ],
+    ],
+    z: &[
+        &[
+            [0.04282990302, 0.26703856476, 10213.285546211],
+            [0.00035588343, PI, 0.0],
+            [0.00014501879, 1.1469691139, 20426.571092422],
+            [1.40675e-6, 0.85984113219, 1577.3435424478],
+            [1.34921e-6, 3.70465787853, 2352.8661537718],
+            [9.575e-7, 3.66962547073, 9437.762934887],
+            [7.291e-7, 1.5575027824, 9153.9036160218],
+            [7.3654e-7, 2.0277843478, 30639.856638633],
+        ],
+        &[
+            [0.00208096402, 1.88967278742, 10213.285546211],
+            [1.264989e-5, 3.71037501321, 20426.571092422],
+            [1.364144e-5, 0.0, 0.0],
+        ],
+        &[[9.148044e-5, 3.34791005272, 10213.285546211]],
+    ],
+};
+
+pub const EARTH: Series = Series {
+    x: &[
+        &[
+            [0.99982928844, 1.75348568475, 6283.0758499914],
+            [0.008352573, 1.7103453945, 12566.1516999828],
+            [0.00561144206, 0.0, 0.0],
+            [0.00010466628, 1.66722645223, 18849.2275499742],
+            [3.110838e-5, 0.66875185215, 83996.84731811189],
+            [2.552498e-5, 0.58310207301, 529.6909650946],
+            [2.137256e-5, 1.09235189672, 1577.3435424478],
+            [1.709103e-5, 0.49540223397, 6279.5527316424],
+            [1.707882e-5, 6.15315547484, 6286.5989683404],
+            [1.445242e-5, 3.4727278376, 2352.8661537718],
+            [1.091006e-5, 3.68984782465, 5223.6939198022],
+            [9.34429e-6, 6.07389922585, 12036.4607348882],
+            [8.99144e-6, 3.17571950523, 10213.285546211],
+            [5.66514e-6, 2.15262034016, 1059.3819301892],
+            [6.84416e-6, 1.30699021227, 5753.3848848968],
+            [7.34455e-6, 4.3550019653, 398.1490034082],
+            [6.81437e-6, 2.21821534685, 4705.7323075436],
+            [6.11238e-6, 5.38479234323, 6812.766815086],
+            [4.51836e-6, 6.08768280868, 5884.9268465832],
+            [4.51953e-6, 1.27933728354, 6256.7775301916],
+            [4.49517e-6, 5.36923831714, 6309.3741697912],
+            [4.06248e-6, 0.54361367084, 6681.2248533996],
+            [5.40957e-6, 0.78677364655, 775.522611324],
+            [5.47004e-6, 1.46146650376, 14143.4952424306],
+            [5.20484e-6, 4.43295799975, 7860.4193924392],
+            [2.1496e-6, 4.50213844573, 11506.7697697936],
+            [2.27892e-6, 1.23941482802, 7058.5984613154],
+            [2.25878e-6, 3.27244306207, 4694.0029547076],
+            [2.5582e-6, 2.26556277246, 12168.0026965746],
+            [2.56182e-6, 1.4547411619, 709.9330485583],
+            [1.7812e-6, 2.96205424204, 796.2980068164],
+            [1.61205e-6, 1.47337718956, 5486.777843175],
+            [1.78325e-6, 6.24374704602, 6283.14316029419],
+            [1.78325e-6, 0.40466470869, 6283.0085396886],
+            [1.55487e-6, 1.62409309523, 25132.3033999656],
+            [2.09024e-6, 5.85207528073, 11790.6290886588],
+            [1.99971e-6, 4.07209938245, 17789.845619785],
+            [1.28933e-6, 5.2169331415, 7079.3738568078],
+            [1.28099e-6, 4.80182882228, 3738.761430108],
+            [1.51691e-6, 0.86921639327, 213.299095438],
+        ],
+        &[
+            [0.00123403056, 0.0, 0.0],
+            [0.00051500156, 6.00266267204, 12566.1516999828],
+            [1.290726e-5, 5.95943124583, 18849.2275499742],
+            [1.068627e-5, 2.01554176551, 6283.0758499914],
+        ],
+    ],
+    y: &[
+        &[
+            [0.9998921103, 0.18265890456, 6283.0758499914],
+            [0.02442699036, PI, 0.0],
+            [0.00835292314, 0.13952878991, 12566.1516999828],
+            [0.00010466965, 0.09641690558, 18849.2275499742],
+            [3.110838e-5, 5.38114091484, 83996.84731811189],
+            [2.570338e-5, 5.3010397336, 529.6909650946],
+            [2.147473e-5, 2.66253538905, 1577.3435424478],
+            [1.709219e-5, 5.20780401071, 6279.5527316424],
+            [1.707987e-5, 4.58232858766, 6286.5989683404],
+            [1.440265e-5, 1.90068164664, 2352.8661537718],
+            [1.135092e-5, 5.2731341522, 5223.6939198022],
+            [9.34539e-6, 4.50301201844, 12036.4607348882],
+            [9.00565e-6, 1.6056328812, 10213.285546211],
+            [5.67126e-6, 0.58142248753, 1059.3819301892],
+            [7.44932e-6, 2.80728871886, 398.1490034082],
+            [6.39316e-6, 6.02923915017, 5753.3848848968],
+            [6.81324e-6, 0.64729627497, 4705.7323075436],
+            [6.11347e-6, 3.81381495286, 6812.766815086],
+            [4.50435e-6, 4.52785572489, 5884.9268465832],
+            [4.52018e-6, 5.99167242707, 6256.7775301916],
+            [4.49968e-6, 3.79880375595, 6309.3741697912],
+            [5.5139e-6, 3.96125249369, 5507.5532386674],
+            [4.06334e-6, 5.25616268027, 6681.2248533996],
+            [5.41273e-6, 5.49902805917, 775.522611324],
+            [5.4636e-6, 6.17311131785, 14143.4952424306],
+            [5.07084e-6, 2.87025193381, 7860.4193924392],
+            [2.19504e-6, 2.95216139568, 11506.7697697936],
+            [2.27937e-6, 5.95179248814, 7058.5984613154],
+            [2.27792e-6, 4.84547074733, 4694.0029547076],
+            [2.55845e-6, 0.69454231563, 12168.0026965746],
+            [2.56132e-6, 6.16722512388, 709.9330485583],
+            [1.79242e-6, 1.40003446021, 796.2980068164],
+            [1.7828e-6, 5.11717552231, 6283.0085396886],
+            [1.7828e-6, 4.67307255246, 6283.14316029419],
+            [1.55454e-6, 0.05340525434, 25132.3033999656],
+            [2.06257e-6, 4.28366728882, 11790.6290886588],
+            [1.49769e-6, 6.07429023278, 5486.777843175],
+            [2.00005e-6, 2.5014408812, 17789.845619785],
+            [1.29006e-6, 3.64623708634, 7079.3738568078],
+            [1.28211e-6, 3.23254821381, 3738.761430108],
+            [1.5279e-6, 5.5812080045, 213.299095438],
+            [1.18725e-6, 5.45361490488, 9437.762934887],
+        ],
+        &[
+            [0.00093046324, 0.0, 0.0],
+            [0.00051506609, 4.43180499286, 12566.1516999828],
+            [1.2908e-5, 4.3886054854, 18849.2275499742],
+        ],
+    ],
+    z: &[
+        &[
+            [2.7962e-6, 3.19870156017, 84334.66158130829],
+            [1.01625e-6, 5.42248110597, 5507.5532386674],
+        ],
+        &[
+            [0.00227822442, 3.41372504278, 6283.0758499914],
+            [5.429282e-5, 0.0, 0.0],
+            [1.903183e-5, 3.37061270964, 12566.1516999828],
+        ],
+    ],
+};
+
+pub const MARS: Series = Series {
+    x: &[
+        &[
+            [1.51769936383, 6.20403346548, 3340.6124266998],
+            [0.19502945246, PI, 0.0],
+            [0.07070919655, 0.25870338558, 6681.2248533996],
+            [0.00494196914, 0.59669127768, 10021.8372800994],
+            [0.00040938237, 0.93473307419, 13362.4497067992],
+            [0.00021067199, 1.80435656154, 3337.0893083508],
+            [0.00021041626, 1.17895619474, 3344.1355450488],
+            [0.00011370375, 4.83265211109, 1059.3819301892],
+            [0.00013527976, 0.63010765169, 529.6909650946],
+            [6.774107e-5, 3.61785048282, 3340.5951730476],
+            [6.77406e-5, 5.64862211431, 3340.629680352],
+            [8.226069e-5, 1.86843519535, 2281.2304965106],
+            [5.469046e-5, 1.13324429003, 2942.4634232916],
+            [4.817134e-5, 1.85091045536, 3738.761430108],
+            [4.937579e-5, 4.43241440654, 5621.8429232104],
+            [5.27626e-5, 2.33148083116, 6151.533888305],
+            [3.636667e-5, 6.11397592106, 796.2980068164],
+            [3.725823e-5, 1.27280182943, 16703.062133499],
+            [3.729746e-5, 1.21398323637, 398.1490034082],
+            [2.368513e-5, 2.9684189536, 2544.3144198834],
+            [2.397865e-5, 0.63553674054, 3149.1641605882],
+            [2.274646e-5, 2.35708328853, 3532.0606928114],
+            [1.977579e-5, 2.1408782611, 6677.7017350506],
+            [2.229176e-5, 1.69588962513, 3340.545116397],
+            [2.229117e-5, 1.28739323821, 3340.6797370026],
+            [2.182206e-5, 1.69655112969, 6283.0758499914],
+            [2.24101e-5, 4.82218655311, 8962.4553499102],
+            [1.677693e-5, 3.14442612046, 5884.9268465832],
+            [1.630482e-5, 0.24117974845, 4136.9104335162],
+            [1.958162e-5, 1.51914544555, 6684.7479717486],
+            [1.37847e-5, 2.18011900021, 1751.539531416],
+            [1.289804e-5, 4.70970778621, 1194.4470102246],
+            [1.468124e-5, 1.87869730543, 3870.3033917944],
+            [1.29017e-5, 0.43596325296, 2810.9214616052],
+            [1.57254e-5, 4.84809921789, 1589.0728952838],
+            [9.56752e-6, 5.36994227392, 426.598190876],
+            [8.19458e-6, 5.15884167649, 4399.994356889],
+            [7.08712e-6, 4.69562713369, 5486.777843175],
+            [7.19048e-6, 2.91145340412, 191.4482661116],
+            [7.02848e-6, 4.67590003722, 2146.1654164752],
+            [7.20121e-6, 2.65539067862, 9492.1463150048],
+            [6.31186e-6, 3.95569679737, 6681.2075997474],
+            [6.31186e-6, 5.98646842887, 6681.2421070518],
+            [5.82287e-6, 3.95295967777, 3185.1920272656],
+            [7.09131e-6, 0.92869188035, 213.299095438],
+            [5.55568e-6, 5.32014604077, 3496.032826134],
+            [4.9219e-6, 2.17606530808, 7079.3738568078],
+            [5.35122e-6, 3.30310120139, 1592.5960136328],
+            [4.17652e-6, 5.30163601083, 3341.592747768],
+            [4.17769e-6, 3.96482796919, 3339.6321056316],
+            [3.70752e-6, 5.08127148188, 8432.7643848156],
+            [3.60028e-6, 1.61089122901, 20043.6745601988],
+            [3.53036e-6, 3.88678429649, 6254.6266625236],
+            [3.52079e-6, 5.17043717929, 12303.06777661],
+            [3.21578e-6, 1.94644283196, 3553.9115221378],
+            [3.46042e-6, 6.16404119331, 5088.6288397668],
+            [3.01924e-6, 0.9846089956, 3127.3133312618],
+            [2.87211e-6, 2.07531303415, 7.1135470008],
+            [3.0706e-6, 0.00966130243, 1748.016413067],
+            [2.60116e-6, 1.89587348902, 1990.745017041],
+            [3.13875e-6, 5.106519666, 4535.0594369244],
+            [2.71815e-6, 1.94925691995, 6467.9257579616],
+            [2.23359e-6, 3.55354563068, 3319.8370312074],
+            [2.21615e-6, 5.71282076784, 3361.3878221922],
+            [2.34058e-6, 2.03750130921, 9623.6882766912],
+            [2.07708e-6, 2.03373205181, 6681.1575430968],
+            [2.05217e-6, 1.85790835685, 10025.3603984484],
+            [2.07851e-6, 2.47841996435, 10018.3141617504],
+            [2.53068e-6, 1.39828863887, 2914.0142358238],
+            [1.93257e-6, 4.93224646907, 2118.7638603784],
+            [2.18718e-6, 0.97547090194, 6489.776587288],
+            [1.90381e-6, 1.28527355998, 4690.4798363586],
+            [2.07708e-6, 1.62523566496, 6681.2921637024],
+            [1.78814e-6, 2.54776084181, 1221.8485663214],
+            [1.57307e-6, 0.48852895157, 2388.8940204492],
+            [1.77177e-6, 4.27081023055, 2957.7158944766],
+            [1.6471e-6, 0.57591122272, 7477.522860216],
+            [1.53944e-6, 4.44473119432, 639.897286314],
+            [1.6845e-6, 5.06929034077, 3723.508958923],
+            [1.61231e-6, 1.53118234358, 1349.8674096588],
+            [1.66323e-6, 3.16905368032, 10213.285546211],
+        ],
+        &[
+            [0.00861441374, PI, 0.0],
+            [0.00552437949, 5.09565872891, 6681.2248533996],
+            [0.00077184977, 5.43315636209, 10021.8372800994],
+            [0.00020467294, 5.57051812369, 3340.6124266998],
+            [9.589581e-5, 5.77107234791, 13362.4497067992],
+            [2.62061e-5, 6.22441295122, 3344.1355450488],
+            [2.620537e-5, 3.04172154436, 3337.0893083508],
+        ],
+        &[
+            [0.00056323939, 0.0, 0.0],
+            [0.00022122528, 3.54372113272, 6681.2248533996],
+        ],
+    ],
+    y: &[
+        &[
+            [1.51558976277, 4.63212206588, 3340.6124266998],
+            [0.07064550239, 4.97051892902, 6681.2248533996],
+            [0.08655481102, 0.0, 0.0],
+            [0.00493872848, 5.30877806694, 10021.8372800994],
+            [0.00040917422, 5.64698263703, 13362.4497067992],
+            [0.00021036784, 0.23240270955, 3337.0893083508],
+            [0.00021012921, 5.89022773653, 3344.1355450488],
+            [0.00011370034, 3.26131408801, 1059.3819301892],
+            [0.00013324177, 5.34259389724, 529.6909650946],
+            [6.764653e-5, 4.07671230062, 3340.629680352],
+            [6.7647e-5, 2.04594066912, 3340.5951730476],
+            [8.34622e-5, 3.42464704002, 2281.2304965106],
+            [5.400042e-5, 5.81507793194, 2942.4634232916],
+            [4.809528e-5, 0.27875310553, 3738.761430108],
+            [4.849523e-5, 2.8519098755, 5621.8429232104],
+            [5.263268e-5, 0.75811089992, 6151.533888305],
+            [3.609527e-5, 4.53244488294, 796.2980068164],
+            [3.724293e-5, 5.98516013322, 16703.062133499],
+            [3.805073e-5, 5.94234296399, 398.1490034082],
+            [2.39449e-5, 5.34678816191, 3149.1641605882],
+            [2.251027e-5, 0.76938193892, 3532.0606928114],
+            [1.975769e-5, 0.56949816579, 6677.7017350506],
+            [2.22603e-5, 5.99867316288, 3340.6797370026],
+            [2.226089e-5, 0.12398424247, 3340.545116397],
+            [2.177591e-5, 0.12334436516, 6283.0758499914],
+            [1.690439e-5, 1.58331163985, 5884.9268465832],
+            [2.234121e-5, 3.24909113765, 8962.4553499102],
+            [1.628395e-5, 4.95250906888, 4136.9104335162],
+            [1.956411e-5, 6.23095843554, 6684.7479717486],
+            [1.697214e-5, 0.81869636263, 2544.3144198834],
+            [1.385946e-5, 3.73437191158, 1751.539531416],
+            [1.439619e-5, 5.19505958438, 2810.9214616052],
+            [1.28189e-5, 3.13035275682, 1194.4470102246],
+            [1.469783e-5, 0.30415060688, 3870.3033917944],
+            [1.57188e-5, 3.2767949865, 1589.0728952838],
+            [1.575854e-5, 2.78266835243, 5092.1519581158],
+            [9.55007e-6, 3.80044052913, 426.598190876],
+            [8.19149e-6, 3.5878644054, 4399.994356889],
+            [7.09907e-6, 4.50556127152, 191.4482661116],
+            [7.19204e-6, 1.0835473505, 9492.1463150048],
+            [6.30626e-6, 2.38434217274, 6681.2075997474],
+            [6.30626e-6, 4.41511380423, 6681.2421070518],
+            [6.99407e-6, 5.7173749791, 213.299095438],
+            [5.92563e-6, 2.82214112368, 5486.777843175],
+            [5.81408e-6, 2.38087976114, 3185.1920272656],
+            [5.51361e-6, 3.73720813913, 3496.032826134],
+            [4.91736e-6, 0.60462673907, 7079.3738568078],
+            [4.03176e-6, 3.49532014869, 8432.7643848156],
+            [5.32702e-6, 1.72629618682, 1592.5960136328],
+            [4.17187e-6, 2.39288855164, 3339.6321056316],
+            [4.17083e-6, 3.72975291794, 3341.592747768],
+            [3.92731e-6, 1.39110771836, 2146.1654164752],
+            [3.59894e-6, 0.04010740278, 20043.6745601988],
+            [3.52248e-6, 2.32029465959, 6254.6266625236],
+            [3.51559e-6, 3.59860692655, 12303.06777661],
+            [3.21064e-6, 0.3714601715, 3553.9115221378],
+            [3.13108e-6, 5.61766202779, 3127.3133312618],
+            [2.66755e-6, 2.88006209994, 4562.4609930212],
+            [2.81855e-6, 0.42150291554, 7.1135470008],
+            [2.59253e-6, 0.3210291551, 1990.745017041],
+            [3.13501e-6, 3.53468286874, 4535.0594369244],
+            [2.65807e-6, 4.13657205734, 5088.6288397668],
+            [2.71152e-6, 0.37550883198, 6467.9257579616],
+            [2.23029e-6, 1.98134328602, 3319.8370312074],
+            [2.21308e-6, 4.14094711258, 3361.3878221922],
+            [2.3374e-6, 0.46548637345, 9623.6882766912],
+            [2.12622e-6, 2.22578594563, 1748.016413067],
+            [2.07526e-6, 0.46238962685, 6681.1575430968],
+            [2.05079e-6, 0.28680735926, 10025.3603984484],
+            [2.07717e-6, 0.90733952, 10018.3141617504],
+            [1.9314e-6, 3.36083067358, 2118.7638603784],
+            [2.44575e-6, 2.80556071941, 2914.0142358238],
+            [2.18542e-6, 5.68744233165, 6489.776587288],
+            [2.07526e-6, 0.05389324, 6681.2921637024],
+            [1.78369e-6, 4.10543898205, 1221.8485663214],
+            [1.56876e-6, 5.19786609301, 2388.8940204492],
+            [1.76877e-6, 2.69854646339, 2957.7158944766],
+            [1.64583e-6, 5.28781848597, 7477.522860216],
+            [1.53824e-6, 2.87500720379, 639.897286314],
+            [1.66545e-6, 1.59865996295, 10213.285546211],
+        ],
+        &[
+            [0.0142732421, PI, 0.0],
+            [0.00551063753, 3.52128320402, 6681.2248533996],
+            [0.00077091913, 3.86082685753, 10021.8372800994],
+            [0.00037310491, 1.16016958445, 3340.6124266998],
+            [9.582122e-5, 4.19942354479, 13362.4497067992],
+            [2.617695e-5, 1.4728455552, 3337.0893083508],
+            [2.611572e-5, 4.65030772498, 3344.1355450488],
+        ],
+        &[
+            [0.00035396765, PI, 0.0],
+            [0.00021950759, 1.96291594946, 6681.2248533996],
+        ],
+    ],
+    z: &[
+        &[
+            [0.0490120722, 3.76712324286, 3340.6124266998],
+            [0.00660669541, 0.0, 0.0],
+            [0.00228333904, 4.10544022266, 6681.2248533996],
+            [0.00015958402, 4.44367058261, 10021.8372800994],
+            [1.321976e-5, 4.78186604114, 13362.4497067992],
+            [6.7966e-6, 5.65109977813, 3337.0893083508],
+            [6.79219e-6, 5.02527030899, 3344.1355450488],
+            [5.3114e-6, 3.86748390045, 2281.2304965106],
+            [3.74993e-6, 4.33338216773, 529.6909650946],
+            [3.25315e-6, 2.24562508217, 1059.3819301892],
+            [2.18762e-6, 1.18094849702, 3340.5951730476],
+            [2.18761e-6, 3.21172012852, 3340.629680352],
+            [1.52443e-6, 2.1325453585, 5621.8429232104],
+        ],
+        &[
+            [0.00331842958, 6.05027773492, 3340.6124266998],
+            [0.00047930411, PI, 0.0],
+            [9.896501e-5, 1.61155844715, 6681.2248533996],
+            [1.700147e-5, 2.63703242065, 10021.8372800994],
+        ],
+    ],
+};
+
+pub const JUPITER: Series = Series {
+    x: &[
+        &[
+            [5.19663470114, 0.59945082355, 529.6909650946],
+            [0.3666264232, PI, 0.0],
+            [0.12593937922, 0.94911583701, 1059.3819301892],
+            [0.01500672056, 0.7317513461, 522.5774180938],
+            [0.01476224578, 3.61736921122, 536.8045120954],
+            [0.00457752736, 1.29883700755, 1589.0728952838],
+            [0.00301689798, 5.17372551148, 7.1135470008],
+            [0.00385975375, 2.01229910687, 103.0927742186],
+            [0.00194025405, 5.02580363996, 426.598190876],
+            [0.00150678793, 6.12003027739, 110.2063212194],
+            [0.00144867641, 5.5598057708, 632.7837393132],
+            [0.00134226996, 0.87648567011, 213.299095438],
+            [0.00103494641, 6.1932476912, 1052.2683831884],
+            [0.00114201562, 0.01567084269, 1162.4747044078],
+            [0.00072095575, 3.96117430643, 1066.49547719],
+            [0.00059486083, 4.45769374358, 949.1756089698],
+            [0.00068284021, 3.44051122631, 846.0828347512],
+            [0.00047092251, 1.44612636451, 419.4846438752],
+            [0.00030623417, 2.99132321427, 206.1855484372],
+            [0.00026613459, 4.85169906494, 323.5054166574],
+            [0.00019727457, 1.64891626213, 2118.7638603784],
+            [0.00016481594, 1.95150056568, 316.3918696566],
+            [0.00016101974, 0.8797315598, 515.463871093],
+            [0.00014209487, 2.07769621413, 742.9900605326],
+            [0.00015192516, 6.25820127906, 735.8765135318],
+            [0.00011423199, 3.48146108929, 543.9180590962],
+            [0.00012155285, 3.75229924999, 525.7588118315],
+            [0.00011996271, 0.58568573729, 533.6231183577],
+            [8.468556e-5, 3.47248751739, 639.897286314],
+            [8.223302e-5, 5.56680447143, 1478.8665740644],
+            [8.694124e-5, 0.38262009411, 1692.1656695024],
+            [7.427517e-5, 5.98380751196, 956.2891559706],
+            [7.51647e-5, 0.92896448412, 1265.5674786264],
+            [7.655867e-5, 0.14178789086, 1581.959348283],
+            [5.318791e-5, 1.10494016349, 526.5095713569],
+            [5.218492e-5, 3.23235129224, 532.8723588323],
+            [5.777311e-5, 5.03726165628, 14.2270940016],
+            [4.622685e-5, 3.75817086099, 1375.7737998458],
+            [3.939864e-5, 4.30892687511, 1596.1864422846],
+            [4.569444e-5, 2.1508728171, 95.9792272178],
+            [2.952712e-5, 3.85988483947, 309.2783226558],
+            [2.857935e-5, 6.01118473739, 117.3198682202],
+            [2.440094e-5, 4.23995765702, 433.7117378768],
+            [2.438257e-5, 3.88808463822, 220.4126424388],
+            [2.675112e-5, 3.18723449094, 1169.5882514086],
+            [2.386425e-5, 5.96354994324, 1045.1548361876],
+            [1.870097e-5, 0.52019313301, 1155.361157407],
+            [1.93906e-5, 5.91883412864, 625.6701923124],
+            [1.6315e-5, 4.41910383466, 942.062061969],
+            [1.451667e-5, 5.7611270604, 853.196381752],
+            [1.361286e-5, 1.34792748837, 1368.660252845],
+            [1.663331e-5, 1.94010629194, 838.9692877504],
+            [1.611229e-5, 5.49324974845, 74.7815985673],
+            [1.03357e-5, 0.08907208789, 1795.258443721],
+            [9.91481e-6, 3.08609505814, 1272.6810256272],
+            [9.34789e-6, 3.11151341633, 199.0720014364],
+            [9.34504e-6, 1.99938801336, 2648.454825473],
+            [8.58829e-6, 3.71316879557, 529.6427809848],
+            [8.58734e-6, 0.6277946469, 529.7391492044],
+            [1.088284e-5, 1.1340610419, 527.2432845398],
+            [1.080643e-5, 3.20528362573, 532.1386456494],
+            [9.59188e-6, 1.3478949421, 149.5631971346],
+            [8.40045e-6, 4.14390924077, 3.9321532631],
+            [9.41997e-6, 1.57612902656, 412.3710968744],
+            [9.326e-6, 5.34596782982, 380.12776796],
+            [6.65711e-6, 6.08446262481, 2008.557539159],
+            [7.47735e-6, 4.70954561325, 330.6189636582],
+            [6.93311e-6, 1.3375428932, 1063.3140834523],
+            [6.06761e-6, 0.11410967423, 2111.6503133776],
+            [6.80707e-6, 0.29377240207, 528.7277572481],
+            [6.78819e-6, 4.04669903131, 530.6541729411],
+            [5.72943e-6, 0.7431266377, 2221.856634597],
+            [5.64304e-6, 4.06331341841, 1055.4497769261],
+            [6.47982e-6, 5.12508099382, 984.6003316219],
+            [5.37627e-6, 0.67272668191, 1685.0521225016],
+            [5.3958e-6, 5.31458333755, 38.1330356378],
+        ],
+        &[
+            [0.00882389251, PI, 0.0],
+            [0.00635297172, 0.10662156868, 1059.3819301892],
+            [0.00599720482, 2.42996678275, 522.5774180938],
+            [0.0058915706, 1.91556314637, 536.8045120954],
+            [0.00081697204, 3.46668108797, 7.1135470008],
+            [0.00046201898, 0.45714214032, 1589.0728952838],
+            [0.0003250859, 1.74648849928, 1052.2683831884],
+            [0.00033891193, 4.10113482752, 529.6909650946],
+            [0.00031234303, 2.34698051502, 1066.49547719],
+            [0.00021244363, 4.36576178953, 110.2063212194],
+            [0.00018156701, 4.00572238779, 426.598190876],
+            [0.00013577576, 0.30008010246, 632.7837393132],
+            [0.00012889505, 2.57489294062, 515.463871093],
+            [9.125875e-5, 1.78082469962, 543.9180590962],
+            [8.085991e-5, 6.16136518902, 949.1756089698],
+            [7.142547e-5, 3.17267801203, 323.5054166574],
+        ],
+        &[
+            [0.00123864644, 4.13563277513, 522.5774180938],
+            [0.00121521296, 0.21155109275, 536.8045120954],
+            [0.00085355503, 0.0, 0.0],
+            [0.00077685547, 5.29776154458, 529.6909650946],
+        ],
+    ],
+    y: &[
+        &[
+            [5.19520046589, 5.31203162731, 529.6909650946],
+            [0.12592862602, 5.66160227728, 1059.3819301892],
+            [0.09363670616, PI, 0.0],
+            [0.01508275299, 5.43934968102, 522.5774180938],
+            [0.0147580937, 2.04679566495, 536.8045120954],
+            [0.00457750806, 6.01129093501, 1589.0728952838],
+            [0.00300686679, 3.6094805074, 7.1135470008],
+            [0.00378285578, 3.53006782383, 103.0927742186],
+            [0.00192333128, 3.45690564771, 426.598190876],
+            [0.00146104656, 4.62267224431, 110.2063212194],
+            [0.00139480058, 4.00075307706, 632.7837393132],
+            [0.00132696764, 5.62184581859, 213.299095438],
+            [0.00101999807, 4.57594598884, 1052.2683831884],
+            [0.0011404311, 4.72982262969, 1162.4747044078],
+            [0.00072091178, 2.39048659148, 1066.49547719],
+            [0.00059051769, 2.89529070968, 949.1756089698],
+            [0.00068374489, 1.86537074374, 846.0828347512],
+            [0.00029807369, 4.5210577274, 206.1855484372],
+            [0.00026933579, 3.86233956827, 419.4846438752],
+            [0.00026619714, 3.28203174951, 323.5054166574],
+            [0.0002087378, 3.79369881757, 735.8765135318],
+            [0.00019727397, 0.07818534532, 2118.7638603784],
+            [0.00018639846, 0.38751972138, 316.3918696566],
+            [0.00016355726, 5.56997881604, 515.463871093],
+            [0.00014606858, 0.47759399145, 742.9900605326],
+            [0.00011419853, 1.91089341468, 543.9180590962],
+            [0.00012153427, 2.18151972499, 525.7588118315],
+            [0.00011988875, 5.29687602089, 533.6231183577],
+            [8.443107e-5, 1.91435801697, 639.897286314],
+            [8.163163e-5, 4.00303742375, 1478.8665740644],
+            [8.732789e-5, 5.09607066097, 1692.1656695024],
+            [7.414115e-5, 4.41141990461, 956.2891559706],
+            [7.619486e-5, 5.59554151997, 1265.5674786264],
+            [7.779184e-5, 4.83346300662, 1581.959348283],
+            [5.322882e-5, 5.81740472645, 526.5095713569],
+            [5.217025e-5, 1.66178643542, 532.8723588323],
+            [5.772132e-5, 3.46915716927, 14.2270940016],
+            [4.528355e-5, 2.18377558038, 1375.7737998458],
+            [3.939875e-5, 2.73830531054, 1596.1864422846],
+            [4.567181e-5, 3.71300776935, 95.9792272178],
+            [3.235419e-5, 4.76600347062, 625.6701923124],
+            [3.14074e-5, 5.59566796922, 309.2783226558],
+            [2.855423e-5, 4.44478286006, 117.3198682202],
+            [2.445625e-5, 2.6703695223, 433.7117378768],
+            [2.253545e-5, 4.28462825722, 838.9692877504],
+            [2.672262e-5, 1.61857897069, 1169.5882514086],
+            [2.423639e-5, 2.32942339839, 220.4126424388],
+            [2.362662e-5, 4.60417580207, 1155.361157407],
+            [2.409581e-5, 4.33196301609, 1045.1548361876],
+            [1.458169e-5, 4.18761881277, 853.196381752],
+            [1.432195e-5, 3.248245545, 942.062061969],
+            [1.646568e-5, 3.91965876562, 74.7815985673],
+            [1.05027e-5, 4.83706014327, 1795.258443721],
+            [1.002355e-5, 1.5093193987, 1272.6810256272],
+            [9.22972e-6, 4.68727792575, 199.0720014364],
+            [9.34476e-6, 0.4288605543, 2648.454825473],
+            [8.58322e-6, 2.14237489817, 529.6427809848],
+            [8.58227e-6, 5.34018602564, 529.7391492044],
+            [1.087727e-5, 5.84673086939, 527.2432845398],
+            [1.079512e-5, 1.63448507346, 532.1386456494],
+            [8.06006e-6, 1.68267639334, 1368.660252845],
+            [9.5727e-6, 6.06002229163, 149.5631971346],
+            [8.47127e-6, 5.93043140082, 3.9321532631],
+            [9.80751e-6, 0.62999941324, 380.12776796],
+            [6.8208e-6, 4.52942528324, 2008.557539159],
+            [6.69757e-6, 4.030164062, 728.762966531],
+            [7.47759e-6, 3.13980492033, 330.6189636582],
+            [6.23272e-6, 4.84897478374, 2111.6503133776],
+            [6.93931e-6, 6.05213927263, 1063.3140834523],
+            [6.79997e-6, 5.00632204302, 528.7277572481],
+            [6.78365e-6, 2.47630881775, 530.6541729411],
+            [5.75319e-6, 5.46049674365, 2221.856634597],
+            [5.62901e-6, 2.4930554767, 1055.4497769261],
+            [6.47097e-6, 3.5549639159, 984.6003316219],
+            [5.40076e-6, 3.74723115522, 38.1330356378],
+        ],
+        &[
+            [0.01694798253, PI, 0.0],
+            [0.00634859798, 4.8190319965, 1059.3819301892],
+            [0.00601160431, 0.8581124994, 522.5774180938],
+            [0.00588928504, 0.3449157689, 536.8045120954],
+            [0.00081187145, 1.90914316532, 7.1135470008],
+            [0.0004688809, 1.91294535618, 529.6909650946],
+            [0.00046194129, 5.16955994561, 1589.0728952838],
+            [0.00032503453, 0.17640743623, 1052.2683831884],
+            [0.00031231694, 0.77623645597, 1066.49547719],
+            [0.00019462096, 3.0095711947, 110.2063212194],
+            [0.00017738615, 2.46531787101, 426.598190876],
+            [0.00013701692, 5.02070197804, 632.7837393132],
+            [0.00013034616, 0.98979834442, 515.463871093],
+            [9.12266e-5, 0.21022587969, 543.9180590962],
+            [8.10905e-5, 4.58123811601, 949.1756089698],
+            [7.145229e-5, 1.60381236094, 323.5054166574],
+        ],
+        &[
+            [0.00124032509, 2.56495576833, 522.5774180938],
+            [0.00121455991, 4.9239876638, 536.8045120954],
+            [0.00076523263, 3.75913371793, 529.6909650946],
+            [0.00076943042, PI, 0.0],
+        ],
+    ],
+    z: &[
+        &[
+            [0.11823100489, 3.55844646343, 529.6909650946],
+            [0.00859031952, 0.0, 0.0],
+            [0.00286562094, 3.90812238338, 1059.3819301892],
+            [0.00042388592, 3.60144191032, 522.5774180938],
+            [0.00033295491, 0.30297050585, 536.8045120954],
+            [0.0001041616, 4.25764593061, 1589.0728952838],
+            [7.449294e-5, 5.2421310415, 103.0927742186],
+            [6.910102e-5, 1.75032945752, 7.1135470008],
+            [5.292012e-5, 1.68231447192, 426.598190876],
+            [4.313598e-5, 3.70673689841, 213.299095438],
+            [3.784265e-5, 2.71522544491, 110.2063212194],
+            [3.798016e-5, 2.16715743175, 632.7837393132],
+            [2.455385e-5, 2.96904135659, 1052.2683831884],
+            [2.461547e-5, 2.99889460411, 1162.4747044078],
+            [2.001451e-5, 2.68535838309, 419.4846438752],
+            [2.163471e-5, 6.26718259854, 846.0828347512],
+            [1.633653e-5, 0.64194743493, 1066.49547719],
+            [1.450672e-5, 1.17108416193, 949.1756089698],
+            [6.93095e-6, 5.14278041161, 316.3918696566],
+            [7.15042e-6, 1.4121119782, 323.5054166574],
+            [5.4972e-6, 4.84164274378, 742.9900605326],
+            [5.43619e-6, 3.69636561822, 515.463871093],
+        ],
+        &[
+            [0.00407072175, 1.52699353482, 529.6909650946],
+            [0.00020307341, 2.59878269248, 1059.3819301892],
+            [0.00014424953, 4.85400155025, 536.8045120954],
+            [0.00015474611, 0.0, 0.0],
+            [0.00012730364, 5.45536715732, 522.5774180938],
+        ],
+    ],
+};
+
+pub const SATURN: Series = Series {
+    x: &[
+        &[
+            [9.51638335797, 0.87441380794, 213.299095438],
+            [0.26412374238, 0.1239089262, 426.598190876],
+            [0.06760430339, 4.16767145778, 206.1855484372],
+            [0.06624260115, 0.7509473778, 220.4126424388],
+            [0.04244797817, 0.0, 0.0],
+            [0.02336340488, 2.02227784673, 7.1135470008],
+            [0.01255372247, 2.17338917731, 110.2063212194],
+            [0.01115684467, 3.15686878377, 419.4846438752],
+            [0.01097683232, 5.65753337256, 639.897286314],
+            [0.00716328481, 2.71149993708, 316.3918696566],
+            [0.00509313365, 4.9586562478, 103.0927742186],
+            [0.00433994439, 0.72012820974, 529.6909650946],
+            [0.00372894461, 0.00137195497, 433.7117378768],
+            [0.00097843523, 1.01485750417, 323.5054166574],
+            [0.00080600536, 5.62103979796, 11.0457002639],
+            [0.00083782316, 0.62038893702, 227.5261894396],
+            [0.00074150224, 2.38206066655, 632.7837393132],
+            [0.00070219382, 0.88789752415, 209.3669421749],
+            [0.00068855792, 4.01788097627, 217.2312487011],
+            [0.00065620467, 2.69728593339, 202.2533951741],
+            [0.00058297911, 2.16155251399, 224.3447957019],
+            [0.00054022837, 4.90928184374, 853.196381752],
+            [0.00045550446, 1.8823503783, 14.2270940016],
+            [0.00038345667, 4.39815501478, 199.0720014364],
+            [0.00044551703, 5.60763553535, 63.7358983034],
+            [0.00025165185, 0.37800582257, 216.4804891757],
+            [0.00024554499, 4.53150598095, 210.1177017003],
+            [0.00024673219, 5.9089157385, 522.5774180938],
+            [0.0002467705, 5.6038938242, 415.5524906121],
+            [0.00025491374, 1.63922423181, 117.3198682202],
+            [0.00031253049, 4.62976601833, 735.8765135318],
+            [0.00023372467, 5.53491987276, 647.0108333148],
+            [0.00023355468, 0.18791490124, 149.5631971346],
+            [0.00024805815, 5.50327676733, 74.7815985673],
+            [0.00014731703, 4.67981909838, 277.0349937414],
+            [0.00012427525, 1.02995545746, 1059.3819301892],
+            [9.943329e-5, 0.84628387596, 3.9321532631],
+            [0.00012393514, 4.19747622821, 490.3340891794],
+            [0.00012026472, 5.66372282839, 351.8165923087],
+            [8.222014e-5, 2.47875301104, 742.9900605326],
+            [9.087093e-5, 4.33505326762, 1052.2683831884],
+            [6.717741e-5, 5.51897460997, 838.9692877504],
+            [6.232999e-5, 2.45837758015, 846.0828347512],
+            [7.161671e-5, 2.18152751738, 95.9792272178],
+            [6.321101e-5, 0.8391540877, 309.2783226558],
+            [6.074958e-5, 6.15905897331, 440.8252848776],
+            [5.343894e-5, 3.60046273598, 412.3710968744],
+            [4.860582e-5, 0.26461045175, 536.8045120954],
+            [5.775802e-5, 5.30717695229, 38.1330356378],
+            [5.194178e-5, 4.54584467686, 210.8514148832],
+            [5.152474e-5, 0.3466951715, 215.7467759928],
+            [3.79254e-5, 5.99766568983, 422.6660376129],
+            [3.762834e-5, 3.72112920226, 212.3358875915],
+            [3.747433e-5, 1.16965137714, 214.2623032845],
+            [3.114576e-5, 0.84631897292, 213.2509113282],
+            [3.113641e-5, 4.0441036719, 213.3472795478],
+            [2.990421e-5, 0.04148806852, 625.6701923124],
+            [4.111695e-5, 5.96153153046, 137.0330241624],
+            [2.96645e-5, 5.39568820046, 138.5174968707],
+            [2.827527e-5, 0.73252555642, 330.6189636582],
+            [3.363323e-5, 1.42089586686, 437.6438911399],
+            [2.886599e-5, 1.14057922619, 85.8272988312],
+            [2.634075e-5, 5.40645201521, 288.0806940053],
+            [2.713354e-5, 0.96812639712, 203.7378678824],
+            [3.16939e-5, 5.76640408988, 76.2660712756],
+            [2.618634e-5, 5.49334837098, 127.4717966068],
+            [2.527746e-5, 5.09752068381, 628.8515860501],
+            [2.989778e-5, 4.15673836604, 1066.49547719],
+            [2.507415e-5, 1.49447138038, 9.5612275556],
+            [2.470181e-5, 5.27435870056, 1155.361157407],
+            [2.427626e-5, 3.97311214231, 222.8603229936],
+            [3.128325e-5, 4.05483976553, 1368.660252845],
+            [2.309076e-5, 3.67821438247, 430.5303441391],
+            [2.162629e-5, 3.26951119901, 340.7708920448],
+            [2.912676e-5, 1.76893577106, 3.1813937377],
+            [2.095366e-5, 3.55759089756, 423.4167971383],
+            [2.33527e-5, 5.86791072516, 388.4651552382],
+            [1.634262e-5, 4.54357767539, 12.5301729722],
+            [1.635975e-5, 2.1996886978, 212.7778305762],
+            [1.632759e-5, 2.69164822165, 213.8203602998],
+            [1.498689e-5, 3.60168057129, 52.6901980395],
+            [1.461217e-5, 5.92456743836, 429.7795846137],
+            [1.963947e-5, 2.0508648718, 1.4844727083],
+            [1.538425e-5, 4.78544077085, 860.3099287528],
+            [1.485856e-5, 5.65501463408, 949.1756089698],
+            [1.418116e-5, 5.41419993599, 350.3321196004],
+            [1.147607e-5, 0.19147238521, 942.062061969],
+            [1.111703e-5, 0.47907488492, 234.6397364404],
+            [1.019566e-5, 5.00707811029, 1471.7530270636],
+            [1.00919e-5, 1.34289487761, 265.9892934775],
+            [9.77078e-6, 6.08156695465, 515.463871093],
+            [1.08945e-5, 5.8269067271, 362.8622925726],
+            [1.092244e-5, 1.13561107749, 173.9422195228],
+            [1.173456e-5, 3.79591687208, 1685.0521225016],
+            [1.118369e-5, 3.46624149583, 703.6331846174],
+            [1.150595e-5, 3.74707160019, 200.7689224658],
+            [1.007761e-5, 1.08964371328, 225.8292684102],
+        ],
+        &[
+            [0.07575103962, 0.0, 0.0],
+            [0.03085041716, 4.27565749128, 426.598190876],
+            [0.02714918399, 5.85229412397, 206.1855484372],
+            [0.02643100909, 5.33291950584, 220.4126424388],
+            [0.0062710452, 0.32898307969, 7.1135470008],
+            [0.00256560953, 3.52478934343, 639.897286314],
+            [0.00312356512, 4.83001724941, 419.4846438752],
+            [0.00189196274, 4.48642453552, 433.7117378768],
+            [0.0020364657, 1.10998681782, 213.299095438],
+            [0.00119531145, 1.14735096078, 110.2063212194],
+            [0.00066764238, 3.72346596928, 316.3918696566],
+            [0.00066901225, 5.2025750038, 227.5261894396],
+            [0.0003100084, 6.06067919437, 199.0720014364],
+            [0.000304181, 0.18746903351, 14.2270940016],
+            [0.0002227521, 6.19530878014, 103.0927742186],
+            [0.00018939377, 2.77618306725, 853.196381752],
+            [0.00018093009, 5.09162723865, 209.3669421749],
+            [0.00017777854, 6.10381593351, 217.2312487011],
+            [0.00016296201, 4.86945681437, 216.4804891757],
+            [0.0001712025, 4.59611664188, 632.7837393132],
+            [0.00015894491, 0.03653502304, 210.1177017003],
+            [0.00016192653, 5.6079801445, 323.5054166574],
+            [0.0001446601, 3.6744938009, 647.0108333148],
+            [0.00011061528, 0.03163071461, 117.3198682202],
+            [9.873183e-5, 5.20065307357, 202.2533951741],
+        ],
+        &[
+            [0.00560746334, 1.26401632282, 206.1855484372],
+            [0.00545834518, 3.62343709657, 220.4126424388],
+            [0.00443342186, PI, 0.0],
+            [0.00336109713, 2.4254743246, 213.299095438],
+            [0.00224302269, 2.49151203519, 426.598190876],
+        ],
+    ],
+    y: &[
+        &[
+            [9.52986882699, 5.58600556665, 213.299095438],
+            [0.79387988806, PI, 0.0],
+            [0.26441781302, 4.83528061849, 426.598190876],
+            [0.06916653915, 2.55279408706, 206.1855484372],
+            [0.06633570703, 5.46258848288, 220.4126424388],
+            [0.02345609742, 0.44652132519, 7.1135470008],
+            [0.01183874652, 1.34638298371, 419.4846438752],
+            [0.01245790434, 0.60367177975, 110.2063212194],
+            [0.01098751131, 4.08608782813, 639.897286314],
+            [0.00700849336, 1.13611298025, 316.3918696566],
+            [0.00434466176, 5.42474696262, 529.6909650946],
+            [0.00373327342, 4.71308726958, 433.7117378768],
+            [0.00335162363, 0.66422253983, 103.0927742186],
+            [0.00097837745, 5.72844290173, 323.5054166574],
+            [0.00080571808, 4.0529544991, 11.0457002639],
+            [0.00083899691, 5.33204070267, 227.5261894396],
+            [0.00070158491, 5.59777963629, 209.3669421749],
+            [0.00065937657, 1.25969608208, 202.2533951741],
+            [0.00070957225, 0.88888207567, 632.7837393132],
+            [0.00068985859, 2.44460312617, 217.2312487011],
+            [0.00058382264, 0.58978766922, 224.3447957019],
+            [0.00054049836, 3.33757904879, 853.196381752],
+            [0.0004579093, 0.30331527632, 14.2270940016],
+            [0.00041976402, 2.62591355948, 199.0720014364],
+            [0.00044697175, 0.90661238256, 63.7358983034],
+            [0.00025199575, 5.08963506006, 216.4804891757],
+            [0.00024640836, 2.95445247282, 210.1177017003],
+            [0.00024835151, 4.02630190571, 415.5524906121],
+            [0.00025545907, 0.06626229252, 117.3198682202],
+            [0.00029666833, 6.09910638345, 735.8765135318],
+            [0.00023396742, 3.96337393635, 647.0108333148],
+            [0.00023380691, 4.90051072276, 149.5631971346],
+            [0.00020272215, 2.34319548198, 309.2783226558],
+            [0.00020099552, 0.98365186365, 522.5774180938],
+            [0.0002482795, 3.926814289, 74.7815985673],
+            [0.00015383927, 3.10227822627, 277.0349937414],
+            [0.0001162921, 5.74108283772, 1059.3819301892],
+            [0.00012422966, 2.62557865743, 490.3340891794],
+            [0.00012048048, 4.09265980116, 351.8165923087],
+            [9.551796e-5, 3.48788042094, 95.9792272178],
+            [7.670379e-5, 1.16594276164, 742.9900605326],
+            [6.919946e-5, 1.17090063883, 412.3710968744],
+            [9.034877e-5, 5.86816144198, 1052.2683831884],
+            [6.536751e-5, 0.84246459392, 838.9692877504],
+            [6.082097e-5, 4.58758280729, 440.8252848776],
+            [5.027211e-5, 0.93213690546, 846.0828347512],
+            [4.838146e-5, 4.98563812475, 536.8045120954],
+            [5.768897e-5, 3.73776690402, 38.1330356378],
+            [5.201849e-5, 2.9748280243, 210.8514148832],
+            [5.156578e-5, 5.05796998564, 215.7467759928],
+            [3.792348e-5, 4.41806046981, 422.6660376129],
+            [3.881104e-5, 3.38026646963, 3.9321532631],
+            [3.768751e-5, 2.1495424736, 212.3358875915],
+            [3.75201e-5, 5.88125434018, 214.2623032845],
+            [3.118938e-5, 5.55799397159, 213.2509113282],
+            [3.118056e-5, 2.47259780102, 213.3472795478],
+            [3.815691e-5, 2.44166851155, 625.6701923124],
+            [4.042463e-5, 1.24471211016, 137.0330241624],
+            [2.829944e-5, 5.44434225998, 330.6189636582],
+            [3.36727e-5, 6.13298847057, 437.6438911399],
+            [2.891352e-5, 5.85313497106, 85.8272988312],
+            [2.774595e-5, 5.61594351302, 203.7378678824],
+            [2.638715e-5, 3.83678156812, 288.0806940053],
+            [3.17168e-5, 4.19553075395, 76.2660712756],
+            [2.532374e-5, 3.52629372341, 628.8515860501],
+            [2.533632e-5, 3.89788590926, 138.5174968707],
+            [2.982174e-5, 2.58535107213, 1066.49547719],
+            [2.620642e-5, 0.69751279148, 127.4717966068],
+            [2.501775e-5, 6.19929274396, 9.5612275556],
+            [2.448467e-5, 0.54179432209, 1155.361157407],
+            [2.431496e-5, 2.40122451395, 222.8603229936],
+            [3.138628e-5, 5.63058455924, 1368.660252845],
+            [2.269226e-5, 2.12401905105, 430.5303441391],
+            [2.078049e-5, 1.95682348964, 423.4167971383],
+            [2.67075e-5, 0.18165311734, 3.1813937377],
+            [2.339764e-5, 4.29619053852, 388.4651552382],
+            [1.63658e-5, 2.97440139727, 12.5301729722],
+            [1.639181e-5, 0.62823227849, 212.7778305762],
+            [1.634235e-5, 1.12043073218, 213.8203602998],
+            [1.499665e-5, 5.16865990579, 52.6901980395],
+            [1.462908e-5, 4.35285690993, 429.7795846137],
+            [1.678952e-5, 2.07211719214, 949.1756089698],
+            [1.989253e-5, 0.42496478369, 1.4844727083],
+            [1.540242e-5, 3.21449770483, 860.3099287528],
+            [1.437063e-5, 3.84293543293, 350.3321196004],
+            [1.276377e-5, 2.9872898777, 340.7708920448],
+            [1.152164e-5, 1.78736848302, 942.062061969],
+            [1.112617e-5, 5.19114183145, 234.6397364404],
+            [1.011023e-5, 0.27242160432, 1471.7530270636],
+            [1.162807e-5, 2.35040840317, 200.7689224658],
+            [1.091025e-5, 4.25638370205, 362.8622925726],
+            [1.090678e-5, 5.85086226218, 173.9422195228],
+            [9.79441e-6, 4.380303625, 1162.4747044078],
+            [1.186647e-5, 5.38323620554, 1685.0521225016],
+            [1.120819e-5, 1.89478696683, 703.6331846174],
+            [1.008898e-5, 5.8011030245, 225.8292684102],
+        ],
+        &[
+            [0.05373889135, 0.0, 0.0],
+            [0.03090575152, 2.70346890906, 426.598190876],
+            [0.02741594312, 4.26667636015, 206.1855484372],
+            [0.02647489677, 3.76132298889, 220.4126424388],
+            [0.00631520527, 5.0324550528, 7.1135470008],
+            [0.00256799701, 1.95351819758, 639.897286314],
+            [0.0031227193, 3.25850205023, 419.4846438752],
+            [0.00189433319, 2.91501840819, 433.7117378768],
+            [0.00164133553, 5.29239290066, 213.299095438],
+            [0.00116791227, 5.8914667576, 110.2063212194],
+            [0.00067210919, 2.17042636344, 316.3918696566],
+            [0.00067003292, 3.63101075514, 227.5261894396],
+            [0.00033002406, 4.35527405801, 199.0720014364],
+            [0.00030628998, 4.88861760772, 14.2270940016],
+            [0.00022234714, 4.62212779231, 103.0927742186],
+            [0.00018945004, 1.20412493845, 853.196381752],
+            [0.00018079959, 3.51566153251, 209.3669421749],
+            [0.00017791543, 4.53214140649, 217.2312487011],
+            [0.00016320701, 3.2978403097, 216.4804891757],
+            [0.00015944258, 4.74503265169, 210.1177017003],
+            [0.00016717122, 3.00270792752, 632.7837393132],
+            [0.00016149947, 4.04186432517, 323.5054166574],
+            [0.00014481431, 2.1029829865, 647.0108333148],
+            [0.0001108404, 4.74073871754, 117.3198682202],
+            [9.905491e-5, 3.60258599375, 202.2533951741],
+        ],
+        &[
+            [0.00563706537, 5.97115878242, 206.1855484372],
+            [0.00547012116, 2.05154973426, 220.4126424388],
+            [0.00458518613, 0.0, 0.0],
+            [0.00362294249, 0.89540100509, 213.299095438],
+            [0.00225521642, 0.91699821445, 426.598190876],
+        ],
+    ],
+    z: &[
+        &[
+            [0.4135695094, 3.60234142982, 213.299095438],
+            [0.01148283576, 2.85128367469, 426.598190876],
+            [0.01214249867, 0.0, 0.0],
+            [0.00329280791, 0.57121407104, 206.1855484372],
+            [0.00286934048, 3.48073526693, 220.4126424388],
+            [0.00099076584, 4.73369511264, 7.1135470008],
+            [0.0005736182, 4.92611225093, 110.2063212194],
+            [0.00047738127, 2.10039779728, 639.897286314],
+            [0.00043458803, 5.84904978051, 419.4846438752],
+            [0.00034565673, 5.4261422959, 316.3918696566],
+            [0.00016185391, 2.72987173675, 433.7117378768],
+            [9.00127e-5, 1.38140102737, 103.0927742186],
+            [0.00011433574, 3.71662021072, 529.6909650946],
+            [5.398708e-5, 5.13204892363, 202.2533951741],
+            [3.902467e-5, 3.71499738796, 323.5054166574],
+            [3.709212e-5, 5.05549348785, 632.7837393132],
+            [3.6141e-5, 3.35210451276, 227.5261894396],
+            [3.379953e-5, 2.13868919206, 11.0457002639],
+            [3.089874e-5, 3.62572857085, 209.3669421749],
+            [2.683064e-5, 4.87689555581, 224.3447957019],
+            [2.963493e-5, 0.46490184985, 217.2312487011],
+            [2.343367e-5, 1.3455827834, 853.196381752],
+            [2.423663e-5, 2.9290709476, 63.7358983034],
+            [1.701916e-5, 1.89892525654, 735.8765135318],
+            [1.941205e-5, 4.59421314662, 14.2270940016],
+            [1.990145e-5, 0.73166053611, 199.0720014364],
+            [1.460265e-5, 3.12851339724, 522.5774180938],
+            [1.148341e-5, 4.41139213915, 117.3198682202],
+            [1.092809e-5, 3.10679381209, 216.4804891757],
+            [1.015179e-5, 1.97897195994, 647.0108333148],
+            [1.098254e-5, 0.96097709156, 210.1177017003],
+            [9.9103e-6, 2.99610026682, 846.0828347512],
+            [1.028743e-5, 2.11933059243, 415.5524906121],
+        ],
+        &[
+            [0.01906503283, 4.94544746116, 213.299095438],
+            [0.00528301265, PI, 0.0],
+            [0.00130262284, 2.26140980879, 206.1855484372],
+            [0.00101466332, 1.79095829545, 220.4126424388],
+            [0.00085947578, 0.51612788497, 426.598190876],
+            [0.00022257446, 3.07684015656, 7.1135470008],
+            [0.00016179946, 1.19987517506, 419.4846438752],
+        ],
+        &[[0.00131275155, 0.08868998101, 213.299095438]],
+    ],
+};
+
+pub const URANUS: Series = Series {
+    x: &[
+        &[
+            [19.17370730359, 5.48133416489, 74.7815985673],
+            [1.32272523872, 0.0, 0.0],
+            [0.44402496796, 1.65967519586, 149.5631971346],
+            [0.14668209481, 3.42395862804, 73.297125859],
+            [0.14130269479, 4.39572927934, 76.2660712756],
+            [0.06201106178, 5.14043574125, 1.4844727083],
+            [0.01542951343, 4.12121838072, 224.3447957019],
+            [0.0144421666, 2.65117115201, 148.0787244263],
+            [0.00944995563, 1.65869338757, 11.0457002639],
+            [0.00657524815, 0.57595170636, 151.0476698429],
+            [0.00621624676, 3.05882246638, 77.7505439839],
+            [0.00585182542, 4.79934779678, 71.8126531507],
+            [0.0063400027, 4.09556589724, 63.7358983034],
+            [0.00547699056, 3.63127725056, 85.8272988312],
+            [0.00458219984, 3.90788284112, 2.9689454166],
+            [0.00496087649, 0.59947400861, 529.6909650946],
+            [0.00383625535, 6.18762010576, 138.5174968707],
+            [0.00267938156, 0.96885660137, 213.299095438],
+            [0.00215368005, 5.30877641428, 38.1330356378],
+            [0.00145505389, 2.31759757085, 70.8494453042],
+            [0.00135340032, 5.51062460816, 78.7137518304],
+            [0.00119593859, 4.10138544267, 39.6175083461],
+            [0.00125105686, 2.51455273063, 111.4301614968],
+            [0.00111260244, 5.12252784325, 222.8603229936],
+            [0.00104619827, 3.90538916334, 146.594251718],
+            [0.00110125387, 4.45473528724, 35.1640902212],
+            [0.00063584588, 0.29966233158, 299.1263942692],
+            [0.00053904041, 3.92590422507, 3.9321532631],
+            [0.00065066905, 3.73008452906, 109.9456887885],
+            [0.00039181662, 2.68841280769, 4.4534181249],
+            [0.00034341683, 3.03781661928, 225.8292684102],
+            [0.00033134636, 2.54201591218, 65.2203710117],
+            [0.00034555652, 1.84699329257, 79.2350166922],
+            [0.0003386705, 5.98418436103, 70.3281804424],
+            [0.00028371614, 2.58026657123, 127.4717966068],
+            [0.00035943348, 4.08754543016, 202.2533951741],
+            [0.00025208833, 5.30272144657, 9.5612275556],
+            [0.00023467802, 4.09729860322, 145.6310438715],
+            [0.00022963939, 5.51475073655, 84.3428261229],
+            [0.00031823951, 5.53948583244, 152.5321425512],
+            [0.00028384953, 6.01785430306, 184.7272873558],
+            [0.00026657176, 6.11027939727, 160.6088973985],
+            [0.00019676762, 5.53431398332, 74.6697239827],
+            [0.00019653873, 2.28660913421, 74.8934731519],
+            [0.0001995428, 0.57450958037, 12.5301729722],
+            [0.00018565067, 0.62225019017, 52.6901980395],
+            [0.00020084756, 4.47297488471, 22.0914005278],
+            [0.00019926329, 1.39878194708, 112.9146342051],
+            [0.00018575632, 5.7021747579, 33.6796175129],
+            [0.0001658787, 4.86920309163, 108.4612160802],
+            [0.00015171194, 2.88415453399, 41.1019810544],
+            [0.000112458, 6.11597016146, 71.6002048296],
+            [0.00013948521, 6.2754569416, 221.3758502853],
+            [0.0001079835, 1.70031857078, 77.962992305],
+            [0.00013593955, 2.55407820633, 87.3117715395],
+            [0.00011997848, 0.94875212305, 1059.3819301892],
+            [0.00012884351, 5.0873799947, 145.1097790097],
+            [0.00012394786, 6.2189287885, 72.3339180125],
+            [0.00012253318, 0.19452856525, 36.6485629295],
+            [0.00011538642, 1.77241794539, 77.2292791221],
+            [8.738409e-5, 4.96956808452, 186.2117600641],
+            [7.095608e-5, 1.30384750044, 297.6419215609],
+            [6.262602e-5, 1.71385983783, 153.4953503977],
+            [7.487302e-5, 0.11408470667, 426.598190876],
+            [7.798974e-5, 5.82410372587, 340.7708920448],
+            [6.669249e-5, 5.08626589612, 62.2514255951],
+            [5.505358e-5, 3.31282108025, 140.001969579],
+            [5.372927e-5, 4.12498282863, 75.3028634291],
+            [5.354242e-5, 3.69263973447, 74.2603337055],
+            [4.478123e-5, 1.11838191479, 66.70484372],
+            [4.233075e-5, 3.94913608184, 265.9892934775],
+            [5.038353e-5, 4.68664376918, 18.1592472647],
+            [4.57047e-5, 0.97536665751, 183.2428146475],
+            [4.751325e-5, 4.95762395337, 73.8183907208],
+            [4.448651e-5, 0.29436142982, 114.3991069134],
+            [3.31234e-5, 0.52418923788, 82.8583534146],
+            [4.515952e-5, 2.8857630312, 75.7448064138],
+            [3.559276e-5, 1.47627607503, 5.9378908332],
+            [3.268117e-5, 0.51827231333, 220.4126424388],
+            [3.578235e-5, 1.11528903208, 137.0330241624],
+            [3.004737e-5, 5.12122132051, 7.1135470008],
+            [2.882392e-5, 2.76136583899, 373.9079928365],
+            [2.579454e-5, 3.84784330333, 277.0349937414],
+            [2.597765e-5, 0.22409539936, 96.8729990951],
+            [2.560744e-5, 4.4423622345, 80.1982245387],
+            [2.722745e-5, 6.09456175016, 106.9767433719],
+            [2.528025e-5, 0.89508396542, 68.8437077341],
+            [2.631138e-5, 0.04831552531, 305.3461693927],
+            [2.541716e-5, 0.64495056482, 32.1951448046],
+            [2.241129e-5, 5.22377697501, 3.1813937377],
+            [1.965145e-5, 0.09207526632, 20.6069278195],
+            [2.232022e-5, 0.63571664756, 80.7194894005],
+            [1.933814e-5, 5.75490033864, 74.7334144575],
+            [1.933817e-5, 2.06557585395, 74.8297826771],
+            [2.138391e-5, 4.20897429922, 74.5209661364],
+            [2.126427e-5, 3.61171465436, 75.0422309982],
+            [2.215516e-5, 2.18613112875, 259.5088859231],
+            [1.927679e-5, 1.29228021932, 159.1244246902],
+            [2.182901e-5, 1.23755478345, 479.2883889155],
+            [2.075591e-5, 3.15586933464, 131.4039498699],
+            [2.064173e-5, 3.6020860641, 835.0371344873],
+        ],
+        &[
+            [0.00739730021, 6.01067825116, 149.5631971346],
+            [0.00526878306, PI, 0.0],
+            [0.00239840801, 5.33657762707, 73.297125859],
+            [0.00229676787, 2.48204455775, 76.2660712756],
+            [0.00111045158, 5.5715723596, 11.0457002639],
+            [0.00096352822, 0.35070389084, 63.7358983034],
+            [0.0008151187, 1.21058618039, 85.8272988312],
+            [0.00045687564, 2.29216583843, 138.5174968707],
+            [0.00051382501, 2.1893512526, 224.3447957019],
+            [0.0003884433, 0.30724575951, 70.8494453042],
+            [0.00036158493, 1.23634798757, 78.7137518304],
+            [0.00032333094, 5.06666556704, 74.7815985673],
+            [0.00021685656, 4.93710968392, 151.0476698429],
+            [0.0001944197, 1.30617490304, 77.7505439839],
+        ],
+    ],
+    y: &[
+        &[
+            [19.16518231584, 3.91045677002, 74.7815985673],
+            [0.44390465203, 0.08884111329, 149.5631971346],
+            [0.16256125476, PI, 0.0],
+            [0.14755940186, 1.85423280679, 73.297125859],
+            [0.14123958128, 2.82486076549, 76.2660712756],
+            [0.06250078231, 3.56960243857, 1.4844727083],
+            [0.01542668264, 2.55040539213, 224.3447957019],
+            [0.01442356575, 1.08004542712, 148.0787244263],
+            [0.00938975501, 0.09275714761, 11.0457002639],
+            [0.00650331846, 2.76142680222, 63.7358983034],
+            [0.0065734312, 5.28830704469, 151.0476698429],
+            [0.0062132677, 1.48795811387, 77.7505439839],
+            [0.00541961958, 3.24476486661, 71.8126531507],
+            [0.00547472694, 2.06037924573, 85.8272988312],
+            [0.0045958912, 2.3374553607, 2.9689454166],
+            [0.00495936105, 5.3120575374, 529.6909650946],
+            [0.00387922853, 4.62026923885, 138.5174968707],
+            [0.00268363417, 5.6808529902, 213.299095438],
+            [0.00216239629, 3.7380076758, 38.1330356378],
+            [0.00144032475, 0.7501570092, 70.8494453042],
+            [0.0013529082, 3.93970260616, 78.7137518304],
+            [0.00119670613, 2.5305878378, 39.6175083461],
+            [0.00124868545, 0.94315917319, 111.4301614968],
+            [0.0011120486, 3.55163219419, 222.8603229936],
+            [0.00104507929, 2.33345675603, 146.594251718],
+            [0.00108584454, 6.02234848388, 35.1640902212],
+            [0.00063573747, 5.0120496792, 299.1263942692],
+            [0.00053289771, 2.38437587876, 3.9321532631],
+            [0.00063774261, 2.15607602904, 109.9456887885],
+            [0.00039218598, 1.11841109252, 4.4534181249],
+            [0.00034205426, 0.92405922576, 65.2203710117],
+            [0.00034334377, 1.46696169843, 225.8292684102],
+            [0.00034538316, 0.27613780697, 79.2350166922],
+            [0.00039256771, 5.75956853703, 202.2533951741],
+            [0.00026157754, 3.74097610798, 9.5612275556],
+            [0.00023427328, 2.52740125551, 145.6310438715],
+            [0.00022933138, 3.9445554035, 84.3428261229],
+            [0.00031816303, 3.96860170484, 152.5321425512],
+            [0.00025237176, 4.45141413666, 70.3281804424],
+            [0.00028372491, 4.44714627097, 184.7272873558],
+            [0.00026652859, 4.53944395347, 160.6088973985],
+            [0.00019666208, 3.96350065335, 74.6697239827],
+            [0.00019643845, 0.71577796385, 74.8934731519],
+            [0.00019838981, 5.29113397354, 12.5301729722],
+            [0.00021523908, 4.93565132068, 36.6485629295],
+            [0.00015537967, 1.8786327546, 52.6901980395],
+            [0.000201151, 3.45473780762, 127.4717966068],
+            [0.00020051641, 2.90386352937, 22.0914005278],
+            [0.00019901477, 6.11075402434, 112.9146342051],
+            [0.00018126776, 0.98478853787, 33.6796175129],
+            [0.00015174962, 1.31314034959, 41.1019810544],
+            [0.0001123902, 4.54508334011, 71.6002048296],
+            [0.00013948849, 4.70474945682, 221.3758502853],
+            [0.00010819728, 0.12807029856, 77.962992305],
+            [0.00013589665, 0.9831371993, 87.3117715395],
+            [0.00011996772, 5.66129275335, 1059.3819301892],
+            [0.00012407787, 4.6494578334, 72.3339180125],
+            [0.0001153114, 0.20190074645, 77.2292791221],
+            [8.73615e-5, 3.39874828293, 186.2117600641],
+            [7.093587e-5, 6.01613487245, 297.6419215609],
+            [6.408245e-5, 3.93246367895, 62.2514255951],
+            [6.261153e-5, 0.14258542752, 153.4953503977],
+            [7.494e-5, 4.82565771386, 426.598190876],
+            [7.856014e-5, 1.12354254831, 340.7708920448],
+            [5.516018e-5, 1.73758326119, 140.001969579],
+            [5.556643e-5, 3.68095215063, 145.1097790097],
+            [5.368405e-5, 2.55422957958, 75.3028634291],
+            [5.350948e-5, 2.12171493922, 74.2603337055],
+            [4.508794e-5, 5.82224064821, 66.70484372],
+            [4.290374e-5, 5.54490766551, 265.9892934775],
+            [5.013871e-5, 3.11907749268, 18.1592472647],
+            [4.326138e-5, 5.70135056853, 183.2428146475],
+            [4.750018e-5, 3.38678300054, 73.8183907208],
+            [4.445347e-5, 5.00638490308, 114.3991069134],
+            [3.314154e-5, 5.23054574329, 82.8583534146],
+            [4.509054e-5, 1.31254342829, 75.7448064138],
+            [3.553107e-5, 6.18906516846, 5.9378908332],
+            [3.265634e-5, 5.23063560176, 220.4126424388],
+            [3.575435e-5, 5.83994849224, 137.0330241624],
+            [2.880734e-5, 1.1903842433, 373.9079928365],
+            [2.885443e-5, 3.50279993038, 7.1135470008],
+            [2.594155e-5, 4.93691413537, 96.8729990951],
+            [2.559357e-5, 2.87184237678, 80.1982245387],
+            [2.676616e-5, 1.61805362044, 305.3461693927],
+            [2.24653e-5, 5.80081898763, 108.4612160802],
+            [2.474483e-5, 2.21173751117, 32.1951448046],
+            [2.060991e-5, 6.24178596384, 56.6223513026],
+            [1.958711e-5, 4.80807045815, 20.6069278195],
+            [2.227451e-5, 5.34765264557, 80.7194894005],
+            [1.937874e-5, 0.49529839431, 74.8297826771],
+            [1.937871e-5, 4.18462288684, 74.7334144575],
+            [2.164382e-5, 0.47581392325, 3.1813937377],
+            [2.138407e-5, 2.63817804331, 74.5209661364],
+            [2.130909e-5, 2.04143912495, 75.0422309982],
+            [2.212861e-5, 0.61491281306, 259.5088859231],
+            [2.275258e-5, 1.55666401505, 131.4039498699],
+            [1.925946e-5, 6.00527473515, 159.1244246902],
+            [2.240648e-5, 0.47739127862, 181.7583419392],
+            [1.924499e-5, 2.64284880495, 206.1855484372],
+            [2.177437e-5, 2.80437422101, 479.2883889155],
+            [2.045249e-5, 5.17400788104, 835.0371344873],
+        ],
+        &[
+            [0.02157896385, 0.0, 0.0],
+            [0.00739227349, 4.43963890935, 149.5631971346],
+            [0.00238545685, 3.76882493145, 73.297125859],
+            [0.00229396424, 0.91090183978, 76.2660712756],
+            [0.00110137111, 4.00844441616, 11.0457002639],
+            [0.00094979054, 5.07141537066, 63.7358983034],
+            [0.00081474163, 5.92275367106, 85.8272988312],
+            [0.00045457174, 0.73292241207, 138.5174968707],
+            [0.00051366974, 0.61844114994, 224.3447957019],
+            [0.00038296005, 5.01873578671, 70.8494453042],
+            [0.00036146116, 5.94859452787, 78.7137518304],
+            [0.00032420558, 4.32617271732, 74.7815985673],
+            [0.00021673269, 3.36607263522, 151.0476698429],
+            [0.00019425087, 6.01842187783, 77.7505439839],
+        ],
+    ],
+    z: &[
+        &[
+            [0.25878127698, 2.61861272578, 74.7815985673],
+            [0.01774318778, PI, 0.0],
+            [0.00599316131, 5.08119500585, 149.5631971346],
+            [0.0019028189, 1.61643841193, 76.2660712756],
+            [0.00190881685, 0.57869575952, 73.297125859],
+            [0.00084626761, 2.26030150166, 1.4844727083],
+            [0.00030734257, 0.23571721555, 63.7358983034],
+            [0.00020842052, 1.26054208091, 224.3447957019],
+            [0.00019734273, 6.04314677688, 148.0787244263],
+            [0.0001253753, 5.17169051466, 11.0457002639],
+            [0.00014582864, 6.14852037212, 71.8126531507],
+            [0.00010407529, 3.65320417038, 213.299095438],
+            [0.00011261541, 3.55973769686, 529.6909650946],
+            [8.855669e-5, 4.03774505739, 151.0476698429],
+            [8.23946e-5, 0.34225652715, 77.7505439839],
+            [7.950169e-5, 0.72564903051, 85.8272988312],
+            [6.867469e-5, 0.81417174224, 2.9689454166],
+            [5.64872e-5, 3.45324719543, 138.5174968707],
+            [4.581938e-5, 1.69668682344, 38.1330356378],
+            [2.578399e-5, 5.1969644739, 111.4301614968],
+            [2.96407e-5, 6.14338802239, 35.1640902212],
+            [2.330304e-5, 5.7264022615, 70.8494453042],
+            [1.985215e-5, 0.76408839812, 39.6175083461],
+            [2.037011e-5, 0.95353587037, 70.3281804424],
+        ],
+        &[
+            [0.00655916626, 0.0127194766, 74.7815985673],
+            [0.00049648951, 0.0, 0.0],
+            [0.00023874178, 2.7387049122, 149.5631971346],
+        ],
+    ],
+};
+
+pub const NEPTUNE: Series = Series {
+    x: &[
+        &[
+            [30.05890004476, 5.31211340029, 38.1330356378],
+            [0.27080164222, PI, 0.0],
+            [0.13505661755, 3.50078975634, 76.2660712756],
+            [0.15726094556, 0.11319072675, 36.6485629295],
+            [0.14935120126, 1.08499403018, 39.6175083461],
+            [0.02597313814, 1.99590301412, 1.4844727083],
+            [0.01074040708, 5.38502938672, 74.7815985673],
+            [0.00823793287, 1.43221581862, 35.1640902212],
+            [0.00817588813, 0.78180174031, 2.9689454166],
+            [0.00565534918, 5.98964907613, 41.1019810544],
+            [0.00495719107, 0.59948143567, 529.6909650946],
+            [0.00307525907, 0.40023311011, 73.297125859],
+            [0.00272253551, 0.87443494387, 213.299095438],
+            [0.00135887219, 5.54676577816, 77.7505439839],
+            [0.00090965704, 1.68910246115, 114.3991069134],
+            [0.00069040539, 5.8346912352, 4.4534181249],
+            [0.00060813556, 2.6258995838, 33.6796175129],
+            [0.00054690827, 1.55799996661, 71.8126531507],
+            [0.0002888926, 4.78966826027, 42.5864537627],
+            [0.00012614732, 3.57002516434, 112.9146342051],
+            [0.00012749153, 2.73719269645, 111.4301614968],
+            [0.00012013994, 0.94912933496, 1059.3819301892],
+            [7.54065e-5, 2.77783477855, 70.3281804424],
+            [7.573383e-5, 0.10011329853, 426.598190876],
+            [8.004318e-5, 1.6396562626, 108.4612160802],
+            [6.464842e-5, 4.62580066013, 5.9378908332],
+            [5.56586e-5, 3.82502185953, 32.1951448046],
+            [4.654361e-5, 0.1038588798, 37.611770776],
+            [4.732434e-5, 4.09723977191, 79.2350166922],
+            [4.557247e-5, 1.09712669317, 38.6543004996],
+            [4.32255e-5, 2.37744780188, 38.084851528],
+            [4.315539e-5, 5.10473142056, 38.1812197476],
+            [4.089036e-5, 1.99429048244, 37.1698277913],
+            [4.249674e-5, 5.63324475823, 28.5718080822],
+            [3.920412e-5, 5.49263784865, 39.0962434843],
+            [3.951848e-5, 2.2999693411, 98.8999885246],
+            [3.322735e-5, 4.68798591938, 4.192785694],
+            [3.108292e-5, 1.84434543409, 47.6942631934],
+            [3.260095e-5, 1.81839652878, 145.1097790097],
+        ],
+        &[
+            [0.00255840261, 2.01935686795, 36.6485629295],
+            [0.00243125299, 5.46214902873, 39.6175083461],
+            [0.00118398168, 2.88251845061, 76.2660712756],
+            [0.00037965449, PI, 0.0],
+        ],
+    ],
+    y: &[
+        &[
+            [30.06056351665, 3.74086294714, 38.1330356378],
+            [0.30205857683, PI, 0.0],
+            [0.13506391797, 1.92953034883, 76.2660712756],
+            [0.15706589373, 4.82539970129, 36.6485629295],
+            [0.14936165806, 5.79694900665, 39.6175083461],
+            [0.02584250749, 0.42549700754, 1.4844727083],
+            [0.01073739772, 3.81371728533, 74.7815985673],
+            [0.00815187583, 5.49429775826, 2.9689454166],
+            [0.00582199295, 6.19633718936, 35.1640902212],
+            [0.00565576412, 4.41843009015, 41.1019810544],
+            [0.00495581047, 5.31205825784, 529.6909650946],
+            [0.00304525203, 5.11048113661, 73.297125859],
+            [0.00272640298, 5.58603690785, 213.299095438],
+            [0.00135897385, 3.97553750964, 77.7505439839],
+            [0.00090970871, 0.11783619888, 114.3991069134],
+            [0.00068790261, 4.26391997151, 4.4534181249],
+            [0.00028893355, 3.21848975032, 42.5864537627],
+            [0.00020081559, 1.19787916085, 33.6796175129],
+            [0.00012613583, 1.99777332934, 112.9146342051],
+            [0.00012828708, 1.16740053443, 111.4301614968],
+            [0.00012012961, 5.66157563804, 1059.3819301892],
+            [8.76858e-5, 3.2348715695, 108.4612160802],
+            [7.581788e-5, 4.81169168396, 426.598190876],
+            [6.439265e-5, 3.05453259951, 5.9378908332],
+            [5.297978e-5, 0.7900231399, 71.8126531507],
+            [4.650708e-5, 4.81540983294, 37.611770776],
+            [4.733483e-5, 2.52620194642, 79.2350166922],
+            [4.557247e-5, 5.80951552318, 38.6543004996],
+            [4.32255e-5, 0.80665145881, 38.084851528],
+            [4.315539e-5, 3.53393506841, 38.1812197476],
+            [4.089036e-5, 0.42349446479, 37.1698277913],
+            [4.247643e-5, 4.06355336504, 28.5718080822],
+            [3.932515e-5, 3.91607592815, 39.0962434843],
+            [3.930135e-5, 3.86614178174, 98.8999885246],
+            [3.323991e-5, 3.11674274385, 4.192785694],
+            [3.112636e-5, 0.27319642944, 47.6942631934],
+            [3.373281e-5, 3.3961625565, 145.1097790097],
+        ],
+        &[
+            [0.00352947493, PI, 0.0],
+            [0.00256125493, 0.44757496817, 36.6485629295],
+            [0.00243147725, 3.89099798696, 39.6175083461],
+            [0.00118427205, 1.31128027037, 76.2660712756],
+        ],
+    ],
+    z: &[
+        &[
+            [0.92866054405, 1.44103930278, 38.1330356378],
+            [0.01245978462, 0.0, 0.0],
+            [0.00474333567, 2.52218774238, 36.6485629295],
+            [0.00451987936, 3.50949720541, 39.6175083461],
+            [0.00417558068, 5.91310695421, 76.2660712756],
+            [0.00084104329, 4.38928900096, 1.4844727083],
+            [0.00032704958, 1.52048692001, 74.7815985673],
+            [0.00030873335, 3.29017611456, 35.1640902212],
+            [0.00025812584, 3.19303128782, 2.9689454166],
+            [0.00016865319, 2.13251104425, 41.1019810544],
+            [0.00011789909, 3.60001877675, 213.299095438],
+            [9.770125e-5, 2.80133971586, 73.297125859],
+            [0.0001127968, 3.55816676334, 529.6909650946],
+            [4.119873e-5, 1.67934316836, 77.7505439839],
+        ],
+        &[[0.00154885971, 2.14239039664, 38.1330356378]],
+    ],
+};
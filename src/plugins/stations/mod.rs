@@ -0,0 +1,175 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+pub mod passes;
+
+use crate::config::*;
+use crate::plugins::clock::SimulationClock;
+use crate::plugins::earth::uv::LatLon;
+use crate::plugins::satellites::Satellite;
+use passes::{Pass, Topocentric, predict_passes};
+
+pub struct StationPlugin;
+
+impl Plugin for StationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PassTable>()
+            .add_systems(Startup, spawn_stations)
+            .add_systems(
+                Update,
+                (update_pass_table, draw_visibility_cones, draw_pass_tracks),
+            );
+    }
+}
+
+/// A ground station that tracks satellites above its elevation mask
+#[derive(Component)]
+pub struct GroundStation {
+    pub location: LatLon,
+    // km above the surface
+    pub altitude: f32,
+    // radians
+    pub min_elevation: f32,
+}
+
+impl GroundStation {
+    pub fn topocentric(&self) -> Topocentric {
+        Topocentric::new(&self.location, self.altitude)
+    }
+}
+
+/// Predicted passes of every satellite over every ground station, sorted by AOS
+#[derive(Resource, Default)]
+pub struct PassTable {
+    pub passes: Vec<Pass>,
+    // julian dates (UTC) covered by the prediction
+    pub start: f64,
+    pub end: f64,
+}
+
+impl PassTable {
+    /// Passes that have not ended yet at `julian_date`
+    pub fn upcoming(&self, julian_date: f64) -> impl Iterator<Item = &Pass> {
+        self.passes
+            .iter()
+            .filter(move |pass| pass.los >= julian_date)
+    }
+}
+
+fn spawn_stations(mut commands: Commands) {
+    for &(name, latitude, longitude, altitude) in GROUND_STATIONS {
+        let location = LatLon::from_degrees(latitude, longitude);
+        commands.spawn((
+            GroundStation {
+                location,
+                altitude,
+                min_elevation: STATION_MIN_ELEVATION.to_radians(),
+            },
+            Name::new(name),
+            Transform::from_translation(location.to_unit_vector() * (EARTH_RADIUS + altitude)),
+        ));
+    }
+}
+
+fn update_pass_table(
+    clock: Res<SimulationClock>,
+    mut table: ResMut<PassTable>,
+    satellites: Query<(Entity, &Satellite)>,
+    stations: Query<(Entity, &GroundStation)>,
+    added_satellites: Query<(), Added<Satellite>>,
+    added_stations: Query<(), Added<GroundStation>>,
+) {
+    let now = clock.julian_date;
+
+    // predict a full window ahead, and start over once half of it has passed
+    let window = PASS_PREDICTION_WINDOW;
+    let stale = now < table.start || now > table.start + window * 0.5;
+    if !stale && added_satellites.is_empty() && added_stations.is_empty() {
+        return;
+    }
+
+    let end = now + window;
+    let mut passes = Vec::new();
+    for (station_entity, station) in &stations {
+        let topocentric = station.topocentric();
+        for (satellite_entity, satellite) in &satellites {
+            passes.extend(predict_passes(
+                (satellite_entity, satellite),
+                (station_entity, &topocentric),
+                station.min_elevation,
+                now,
+                end,
+                PASS_SEARCH_STEP,
+            ));
+        }
+    }
+    passes.sort_by(|a, b| a.aos.total_cmp(&b.aos));
+
+    table.passes = passes;
+    table.start = now;
+    table.end = end;
+}
+
+/// Draws the part of the sky each station can see, out to `STATION_CONE_ALTITUDE`,
+/// and the ring on the ground below it where satellites at that altitude are in view
+fn draw_visibility_cones(mut gizmos: Gizmos, stations: Query<&GroundStation>) {
+    const SEGMENTS: usize = 64;
+    let color = Color::srgba(0.3, 0.9, 1.0, 0.6);
+
+    for station in &stations {
+        let topocentric = station.topocentric();
+        let shell_radius = EARTH_RADIUS + STATION_CONE_ALTITUDE;
+
+        // earth central angle between the station and the edge of its view at the shell
+        let cos_elevation = station.min_elevation.cos();
+        let central_angle = ((EARTH_RADIUS + station.altitude) * cos_elevation / shell_radius)
+            .clamp(-1.0, 1.0)
+            .acos()
+            - station.min_elevation;
+
+        let edge = |azimuth: f32, radius: f32| {
+            let horizontal = topocentric.north * azimuth.cos() + topocentric.east * azimuth.sin();
+            (topocentric.up * central_angle.cos() + horizontal * central_angle.sin()) * radius
+        };
+
+        let ring = |radius: f32| {
+            (0..=SEGMENTS).map(move |i| edge(i as f32 / SEGMENTS as f32 * TAU, radius))
+        };
+        gizmos.linestrip(ring(shell_radius), color);
+        gizmos.linestrip(
+            ring(EARTH_RADIUS + DISPLACEMENT_SCALE),
+            color.with_alpha(0.3),
+        );
+
+        for i in 0..8 {
+            let azimuth = i as f32 / 8.0 * TAU;
+            gizmos.line(topocentric.position, edge(azimuth, shell_radius), color);
+        }
+    }
+}
+
+/// Draws the sampled path of every satellite while it is passing over a station
+fn draw_pass_tracks(
+    mut gizmos: Gizmos,
+    clock: Res<SimulationClock>,
+    table: Res<PassTable>,
+    stations: Query<&GroundStation>,
+) {
+    let color = Color::srgba(1.0, 0.8, 0.3, 0.8);
+
+    for pass in table
+        .upcoming(clock.julian_date)
+        .filter(|pass| pass.aos <= clock.julian_date)
+    {
+        let Ok(station) = stations.get(pass.station) else {
+            continue;
+        };
+        let topocentric = station.topocentric();
+        gizmos.linestrip(
+            pass.track
+                .iter()
+                .map(|angles| topocentric.position_of(angles)),
+            color,
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use bevy::prelude::*;
+
+use crate::config::EARTH_RADIUS;
+use crate::plugins::earth::uv::LatLon;
+use crate::plugins::satellites::Satellite;
+
+// Satellite pass prediction for ground stations
+// the earth is treated as the same sphere the globe is drawn with,
+// which is within a few tenths of a degree of elevation of the WGS84 result
+
+const MINUTES_PER_DAY: f64 = 1440.0;
+
+/// Direction and distance from a ground station to a satellite
+#[derive(Clone, Copy, Debug)]
+pub struct LookAngles {
+    // julian date (UTC)
+    pub time: f64,
+    // radians, clockwise from north
+    pub azimuth: f32,
+    // radians above the horizon
+    pub elevation: f32,
+    // km
+    pub range: f32,
+}
+
+/// A single pass of a satellite over a ground station
+#[derive(Clone, Debug)]
+pub struct Pass {
+    pub satellite: Entity,
+    pub station: Entity,
+    // julian dates (UTC) of acquisition and loss of signal
+    pub aos: f64,
+    pub los: f64,
+    pub max_elevation: LookAngles,
+    // sampled from AOS to LOS
+    pub track: Vec<LookAngles>,
+}
+
+impl Pass {
+    /// Duration in minutes
+    pub fn duration(&self) -> f64 {
+        (self.los - self.aos) * MINUTES_PER_DAY
+    }
+}
+
+/// Local horizon frame of a ground station, in the scene's earth-fixed frame
+#[derive(Clone, Copy, Debug)]
+pub struct Topocentric {
+    pub position: Vec3,
+    pub up: Vec3,
+    pub east: Vec3,
+    pub north: Vec3,
+}
+
+impl Topocentric {
+    /// `altitude` in km above the globe's surface
+    pub fn new(location: &LatLon, altitude: f32) -> Self {
+        let up = location.to_unit_vector();
+        // the east direction is undefined at the poles, any horizontal direction will do there
+        let east = Vec3::Y.cross(up).try_normalize().unwrap_or(Vec3::X);
+        let north = up.cross(east);
+
+        Topocentric {
+            position: up * (EARTH_RADIUS + altitude),
+            up,
+            east,
+            north,
+        }
+    }
+
+    pub fn look_angles(&self, target: Vec3, time: f64) -> LookAngles {
+        let offset = target - self.position;
+        let range = offset.length();
+        let east = offset.dot(self.east);
+        let north = offset.dot(self.north);
+        let up = offset.dot(self.up);
+
+        LookAngles {
+            time,
+            azimuth: east.atan2(north).rem_euclid(std::f32::consts::TAU),
+            elevation: (up / range).clamp(-1.0, 1.0).asin(),
+            range,
+        }
+    }
+
+    /// Inverse of `look_angles`, the point the angles and range point at
+    pub fn position_of(&self, angles: &LookAngles) -> Vec3 {
+        let (sin_azimuth, cos_azimuth) = angles.azimuth.sin_cos();
+        let (sin_elevation, cos_elevation) = angles.elevation.sin_cos();
+        let direction = (self.north * cos_azimuth + self.east * sin_azimuth) * cos_elevation
+            + self.up * sin_elevation;
+        self.position + direction * angles.range
+    }
+}
+
+/// Finds every pass of `satellite` above `min_elevation` (radians) between `start` and `end`
+/// `step` (minutes) must be shorter than the shortest pass of interest
+pub fn predict_passes(
+    satellite: (Entity, &Satellite),
+    station: (Entity, &Topocentric),
+    min_elevation: f32,
+    start: f64,
+    end: f64,
+    step: f64,
+) -> Vec<Pass> {
+    let (_, orbit) = satellite;
+    let (_, topocentric) = station;
+
+    // elevation above the mask, negative when the satellite is not visible
+    let elevation = |time: f64| -> Option<f32> {
+        let position = orbit.position_at(time).ok()?;
+        Some(topocentric.look_angles(position, time).elevation - min_elevation)
+    };
+
+    visibility_windows(&elevation, start, end, step)
+        .into_iter()
+        .map(|(aos, los)| build_pass(satellite, station, aos, los))
+        .collect()
+}
+
+/// AOS and LOS of every interval between `start` and `end` where `elevation` is at or above zero
+/// `elevation` is None where the satellite can't be propagated
+fn visibility_windows(
+    elevation: &impl Fn(f64) -> Option<f32>,
+    start: f64,
+    end: f64,
+    step: f64,
+) -> Vec<(f64, f64)> {
+    let step_days = step / MINUTES_PER_DAY;
+    let mut windows = Vec::new();
+    let mut aos: Option<f64> = None;
+
+    let mut previous_time = start;
+    let mut previous = elevation(start);
+
+    // a pass already in progress at the start of the window begins at the window
+    if previous.is_some_and(|elevation| elevation >= 0.0) {
+        aos = Some(start);
+    }
+
+    let mut time = start;
+    while time < end {
+        time = (time + step_days).min(end);
+        let current = elevation(time);
+
+        match (previous, current, aos) {
+            // after a failed step too, the pass then begins where propagation recovers
+            (before, Some(after), None)
+                if after >= 0.0 && before.is_none_or(|before| before < 0.0) =>
+            {
+                aos = Some(find_crossing(elevation, previous_time, time));
+            }
+            (Some(before), Some(after), Some(rise)) if before >= 0.0 && after < 0.0 => {
+                windows.push((rise, find_crossing(elevation, previous_time, time)));
+                aos = None;
+            }
+            // propagation failed, drop any pass in progress
+            (_, None, _) => aos = None,
+            _ => {}
+        }
+
+        previous_time = time;
+        previous = current;
+    }
+
+    // a pass still in progress at the end of the window ends at the window
+    if let Some(rise) = aos {
+        windows.push((rise, end));
+    }
+
+    windows
+}
+
+/// Bisects for the time between `a` and `b` where the elevation crosses the mask
+fn find_crossing(elevation: &impl Fn(f64) -> Option<f32>, mut a: f64, mut b: f64) -> f64 {
+    let rising = elevation(a).unwrap_or(-1.0) < 0.0;
+
+    // 20 halvings of a 30 second step is well below a millisecond
+    for _ in 0..20 {
+        let mid = 0.5 * (a + b);
+        let visible = elevation(mid).unwrap_or(-1.0) >= 0.0;
+        if visible == rising {
+            b = mid;
+        } else {
+            a = mid;
+        }
+    }
+    0.5 * (a + b)
+}
+
+fn build_pass(
+    satellite: (Entity, &Satellite),
+    station: (Entity, &Topocentric),
+    aos: f64,
+    los: f64,
+) -> Pass {
+    let (satellite, orbit) = satellite;
+    let (station, topocentric) = station;
+
+    let look = |time: f64| {
+        orbit
+            .position_at(time)
+            .ok()
+            .map(|position| topocentric.look_angles(position, time))
+    };
+
+    // one sample every 10 seconds, at least a handful for very short passes
+    let samples = (((los - aos) * MINUTES_PER_DAY * 6.0).ceil() as usize).max(8);
+    let track: Vec<LookAngles> = (0..=samples)
+        .filter_map(|i| look(aos + (los - aos) * i as f64 / samples as f64))
+        .collect();
+
+    // refine the highest sample with a golden section search between its neighbours
+    let highest = track
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.elevation.total_cmp(&b.elevation))
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    let mut a = track.get(highest.saturating_sub(1)).map_or(aos, |s| s.time);
+    let mut b = track.get(highest + 1).map_or(los, |s| s.time);
+    let ratio = 0.5 * (5f64.sqrt() - 1.0);
+    for _ in 0..30 {
+        let c = b - ratio * (b - a);
+        let d = a + ratio * (b - a);
+        let elevation_c = look(c).map_or(f32::MIN, |angles| angles.elevation);
+        let elevation_d = look(d).map_or(f32::MIN, |angles| angles.elevation);
+        if elevation_c > elevation_d {
+            b = d;
+        } else {
+            a = c;
+        }
+    }
+    let max_elevation = look(0.5 * (a + b))
+        .or_else(|| track.get(highest).copied())
+        .unwrap_or(LookAngles {
+            time: aos,
+            azimuth: 0.0,
+            elevation: 0.0,
+            range: 0.0,
+        });
+
+    Pass {
+        satellite,
+        station,
+        aos,
+        los,
+        max_elevation,
+        track,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::satellites::sgp4::Sgp4;
+    use crate::plugins::satellites::tle::Tle;
+    use std::f32::consts::{FRAC_PI_2, TAU};
+
+    const SECOND: f64 = 1.0 / 86400.0;
+
+    #[test]
+    fn look_angles_from_the_equator() {
+        let station = Topocentric::new(&LatLon::from_degrees(0.0, 0.0), 0.0);
+
+        let overhead = station.look_angles(station.up * (EARTH_RADIUS + 500.0), 0.0);
+        assert!((overhead.elevation - FRAC_PI_2).abs() < 1e-3);
+        assert!((overhead.range - 500.0).abs() < 1e-2);
+
+        let north = station.look_angles(station.position + station.north * 1000.0, 0.0);
+        assert!(north.azimuth.abs() < 1e-4 || (north.azimuth - TAU).abs() < 1e-4);
+        assert!(north.elevation.abs() < 1e-4);
+
+        let east = station.look_angles(station.position + station.east * 1000.0, 0.0);
+        assert!((east.azimuth - FRAC_PI_2).abs() < 1e-4);
+
+        let target = station.position + Vec3::new(300.0, 400.0, -200.0);
+        let angles = station.look_angles(target, 0.0);
+        assert!(station.position_of(&angles).distance(target) < 1e-2);
+    }
+
+    #[test]
+    fn predicts_an_overhead_pass() {
+        let tle = Tle::parse(
+            None,
+            "1 28057U 03049A   06177.78615833  .00000060  00000-0  35940-4 0  1836",
+            "2 28057  98.4283 247.6961 0000884  88.1964 271.9322 14.35478080140550",
+        )
+        .unwrap();
+        let satellite = Satellite {
+            model: Sgp4::new(&tle).unwrap(),
+            color: Color::WHITE,
+            period: tle.period(),
+        };
+
+        // a station right under the satellite half an hour after the epoch
+        let overhead = tle.epoch + 30.0 / MINUTES_PER_DAY;
+        let location = LatLon::from(satellite.position_at(overhead).unwrap());
+        let station = Topocentric::new(&location, 0.0);
+        let min_elevation = 10f32.to_radians();
+
+        let passes = predict_passes(
+            (Entity::PLACEHOLDER, &satellite),
+            (Entity::PLACEHOLDER, &station),
+            min_elevation,
+            overhead - 20.0 / MINUTES_PER_DAY,
+            overhead + 20.0 / MINUTES_PER_DAY,
+            0.5,
+        );
+
+        assert_eq!(passes.len(), 1);
+        let pass = &passes[0];
+        assert!(pass.aos < overhead && overhead < pass.los);
+        // about 12 minutes above 10 degrees from 800 km
+        assert!(
+            (10.0..14.0).contains(&pass.duration()),
+            "{}",
+            pass.duration()
+        );
+        assert!(pass.max_elevation.elevation > 89f32.to_radians());
+        assert!((pass.max_elevation.time - overhead).abs() < 2.0 * SECOND);
+
+        let aos = station.look_angles(satellite.position_at(pass.aos).unwrap(), pass.aos);
+        assert!((aos.elevation - min_elevation).abs() < 1e-3);
+        assert_eq!(pass.track.first().map(|angles| angles.time), Some(pass.aos));
+        assert_eq!(pass.track.last().map(|angles| angles.time), Some(pass.los));
+    }
+
+    // days, the mask is crossed at 0.2 and 0.8
+    fn elevation(time: f64) -> Option<f32> {
+        Some((0.3 - (time - 0.5).abs()) as f32)
+    }
+
+    #[test]
+    fn windows_are_bounded_by_the_mask_crossings() {
+        let windows = visibility_windows(&elevation, 0.0, 1.0, 14.4);
+        assert_eq!(windows.len(), 1);
+        let (aos, los) = windows[0];
+        assert!((aos - 0.2).abs() < SECOND && (los - 0.8).abs() < SECOND);
+
+        // passes in progress at either end of the window are cut there
+        let windows = visibility_windows(&elevation, 0.3, 0.6, 14.4);
+        assert_eq!(windows, vec![(0.3, 0.6)]);
+    }
+
+    #[test]
+    fn a_pass_resumes_after_a_propagation_failure() {
+        let gap = |time: f64| {
+            (!(0.4..0.5).contains(&time))
+                .then(|| elevation(time))
+                .flatten()
+        };
+        let windows = visibility_windows(&gap, 0.0, 1.0, 14.4);
+
+        // the part before the gap is dropped, the rest is kept
+        assert_eq!(windows.len(), 1);
+        let (aos, los) = windows[0];
+        assert!((aos - 0.5).abs() < SECOND && (los - 0.8).abs() < SECOND);
+    }
+}
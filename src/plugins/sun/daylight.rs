@@ -0,0 +1,162 @@
+use crate::plugins::clock::julian::start_of_day;
+use crate::plugins::earth::uv::LatLon;
+use crate::plugins::sun::ephemeris::solar_position;
+
+// Sunrise, sunset and solar noon
+// based on the NOAA solar calculator
+// https://gml.noaa.gov/grad/solcalc/calcdetails.html
+
+/// Altitude of the sun's center at sunrise and sunset (degrees)
+/// accounts for atmospheric refraction and the radius of the solar disc
+pub const SUNRISE_ALTITUDE: f64 = -0.833;
+
+const MINUTES_PER_DAY: f64 = 1440.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SunEvents {
+    // julian dates (UTC)
+    RiseSet { sunrise: f64, sunset: f64 },
+    // sun never sets on this day
+    PolarDay,
+    // sun never rises on this day
+    PolarNight,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Daylight {
+    // julian date (UTC)
+    pub solar_noon: f64,
+    pub events: SunEvents,
+    // hours
+    pub day_length: f64,
+}
+
+/// Computes sunrise, sunset, solar noon and day length for a location
+/// `julian_date` can be any time during the UTC calendar day of interest
+pub fn daylight(location: &LatLon, julian_date: f64) -> Daylight {
+    let day = start_of_day(julian_date);
+    let longitude = (location.longitude as f64).to_degrees();
+    let latitude = location.latitude as f64;
+
+    let solar_noon = solar_noon(day, longitude);
+
+    let events = match hour_angle(latitude, solar_noon) {
+        HourAngle::AlwaysUp => SunEvents::PolarDay,
+        HourAngle::AlwaysDown => SunEvents::PolarNight,
+        HourAngle::Angle(_) => {
+            // refine each event with the sun's position at the time of the event itself
+            // the declination changes enough during a day to shift events at high latitudes
+            let sunrise = refine_event(latitude, longitude, day, solar_noon, -1.0);
+            let sunset = refine_event(latitude, longitude, day, solar_noon, 1.0);
+            SunEvents::RiseSet { sunrise, sunset }
+        }
+    };
+
+    let day_length = match events {
+        SunEvents::RiseSet { sunrise, sunset } => (sunset - sunrise) * 24.0,
+        SunEvents::PolarDay => 24.0,
+        SunEvents::PolarNight => 0.0,
+    };
+
+    Daylight {
+        solar_noon,
+        events,
+        day_length,
+    }
+}
+
+enum HourAngle {
+    // radians
+    Angle(f64),
+    AlwaysUp,
+    AlwaysDown,
+}
+
+/// Hour angle of the sun when it crosses `SUNRISE_ALTITUDE`
+fn hour_angle(latitude: f64, julian_date: f64) -> HourAngle {
+    let declination = solar_position(julian_date).declination;
+
+    // cos(latitude) is zero at the poles, keep the division finite
+    let denominator = (latitude.cos() * declination.cos()).max(1e-12);
+    let cos_hour_angle =
+        (SUNRISE_ALTITUDE.to_radians().sin() - latitude.sin() * declination.sin()) / denominator;
+
+    if cos_hour_angle < -1.0 {
+        HourAngle::AlwaysUp
+    } else if cos_hour_angle > 1.0 {
+        HourAngle::AlwaysDown
+    } else {
+        HourAngle::Angle(cos_hour_angle.acos())
+    }
+}
+
+/// Solar noon for the day starting at `day`, longitude in degrees east
+fn solar_noon(day: f64, longitude: f64) -> f64 {
+    // first guess uses the equation of time at mean noon, second uses it at the estimate
+    let mut noon = day + 0.5 - longitude / 360.0;
+    for _ in 0..2 {
+        let equation_of_time = solar_position(noon).equation_of_time;
+        noon = day + (720.0 - 4.0 * longitude - equation_of_time) / MINUTES_PER_DAY;
+    }
+    noon
+}
+
+/// `direction` is -1 for sunrise and 1 for sunset
+fn refine_event(latitude: f64, longitude: f64, day: f64, solar_noon: f64, direction: f64) -> f64 {
+    let mut event = solar_noon;
+    let mut transit = solar_noon;
+
+    for _ in 0..3 {
+        // near polar day/night the sun may just graze the horizon, keep the last good estimate
+        let HourAngle::Angle(angle) = hour_angle(latitude, event) else {
+            break;
+        };
+        event = transit + direction * angle.to_degrees() / 360.0;
+
+        let equation_of_time = solar_position(event).equation_of_time;
+        transit = day + (720.0 - 4.0 * longitude - equation_of_time) / MINUTES_PER_DAY;
+    }
+
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::clock::julian::CalendarDate;
+
+    fn minutes_apart(a: f64, b: f64) -> f64 {
+        (a - b).abs() * MINUTES_PER_DAY
+    }
+
+    #[test]
+    fn reykjavik_on_midsummer_matches_noaa() {
+        let reykjavik = LatLon::from_degrees(64.1466, -21.9426);
+        let info = daylight(&reykjavik, CalendarDate::new(2024, 6, 21).to_julian_date());
+
+        // NOAA solar calculator: sunrise 02:55, solar noon 13:30, sunset 00:04 the next day
+        let SunEvents::RiseSet { sunrise, sunset } = info.events else {
+            panic!("expected a sunrise and sunset, got {:?}", info.events);
+        };
+        let expected_sunrise = CalendarDate::new(2024, 6, 21).with_time(2, 55, 21.0);
+        let expected_noon = CalendarDate::new(2024, 6, 21).with_time(13, 29, 42.0);
+        let expected_sunset = CalendarDate::new(2024, 6, 22).with_time(0, 4, 2.0);
+        assert!(minutes_apart(sunrise, expected_sunrise.to_julian_date()) < 2.0);
+        assert!(minutes_apart(info.solar_noon, expected_noon.to_julian_date()) < 1.0);
+        assert!(minutes_apart(sunset, expected_sunset.to_julian_date()) < 2.0);
+        assert!((info.day_length - (sunset - sunrise) * 24.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn polar_day_and_night_at_80_north() {
+        let svalbard = LatLon::from_degrees(80.0, 15.0);
+
+        let june = daylight(&svalbard, CalendarDate::new(2024, 6, 21).to_julian_date());
+        assert_eq!(june.events, SunEvents::PolarDay);
+        assert_eq!(june.day_length, 24.0);
+
+        let december = daylight(&svalbard, CalendarDate::new(2024, 12, 21).to_julian_date());
+        assert_eq!(december.events, SunEvents::PolarNight);
+        assert_eq!(december.day_length, 0.0);
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use std::f64::consts::TAU;
+
+use crate::plugins::clock::julian::J2000;
+use crate::plugins::earth::uv::LatLon;
+
+// Low precision solar ephemeris
+// based on the Astronomical Almanac's approximate solar coordinates
+// https://aa.usno.navy.mil/faq/sun_approx
+// accurate to about 0.01 degrees between 1950 and 2050
+
+/// Apparent position of the sun as seen from the earth's center
+#[derive(Clone, Copy, Debug)]
+pub struct SolarPosition {
+    // radians
+    pub right_ascension: f64,
+    pub declination: f64,
+    // apparent minus mean solar time, in minutes
+    pub equation_of_time: f64,
+}
+
+pub fn solar_position(julian_date: f64) -> SolarPosition {
+    let n = julian_date - J2000;
+
+    // mean longitude and mean anomaly (degrees)
+    let mean_longitude = (280.460 + 0.9856474 * n).rem_euclid(360.0);
+    let mean_anomaly = (357.528 + 0.9856003 * n).rem_euclid(360.0).to_radians();
+
+    let ecliptic_longitude =
+        (mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin())
+            .to_radians();
+    let obliquity = (23.439 - 0.0000004 * n).to_radians();
+
+    let right_ascension = (obliquity.cos() * ecliptic_longitude.sin())
+        .atan2(ecliptic_longitude.cos())
+        .rem_euclid(TAU);
+    let declination = (obliquity.sin() * ecliptic_longitude.sin()).asin();
+
+    // difference between the mean sun and the true sun, wrapped to [-180, 180)
+    let equation_of_time =
+        ((mean_longitude - right_ascension.to_degrees() + 180.0).rem_euclid(360.0) - 180.0) * 4.0;
+
+    SolarPosition {
+        right_ascension,
+        declination,
+        equation_of_time,
+    }
+}
+
+/// Greenwich mean sidereal time in radians
+/// this is the rotation angle between the inertial frame and the earth-fixed frame
+pub fn greenwich_mean_sidereal_time(julian_date: f64) -> f64 {
+    let n = julian_date - J2000;
+    (280.46061837 + 360.98564736629 * n)
+        .rem_euclid(360.0)
+        .to_radians()
+}
+
+/// Point on the earth where the sun is directly overhead
+pub fn subsolar_point(julian_date: f64) -> LatLon {
+    let sun = solar_position(julian_date);
+    let longitude = (sun.right_ascension - greenwich_mean_sidereal_time(julian_date) + TAU / 2.0)
+        .rem_euclid(TAU)
+        - TAU / 2.0;
+
+    LatLon {
+        latitude: sun.declination as f32,
+        longitude: longitude as f32,
+    }
+}
+
+/// Unit vector from the earth's center towards the sun, in the earth-fixed frame used by `LatLon`
+pub fn sun_direction(julian_date: f64) -> Vec3 {
+    subsolar_point(julian_date).to_unit_vector()
+}
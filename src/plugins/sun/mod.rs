@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+pub mod daylight;
+pub mod ephemeris;
+
+use crate::Sun;
+use crate::plugins::clock::SimulationClock;
+use ephemeris::sun_direction;
+
+pub struct SunPlugin;
+
+impl Plugin for SunPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, orient_sun);
+    }
+}
+
+/// Points every `Sun` light along the current sun direction
+fn orient_sun(clock: Res<SimulationClock>, mut suns: Query<&mut Transform, With<Sun>>) {
+    let direction = sun_direction(clock.julian_date);
+
+    for mut transform in &mut suns {
+        // directional lights shine along their local -z
+        *transform = Transform::from_translation(direction).looking_at(Vec3::ZERO, Vec3::Y);
+    }
+}
@@ -0,0 +1,322 @@
+use bevy::prelude::*;
+
+use crate::plugins::clock::{
+    SimulationClock,
+    julian::{CalendarDate, start_of_day},
+};
+use crate::plugins::earth::{
+    Earth, Relief,
+    analysis::{AnalysisMode, AnalysisRamp, TerrainAnalysis},
+    land_cover::LandCover,
+    shading::EarthShadingSettings,
+    uv::LatLon,
+};
+use crate::plugins::satellites::Coverage;
+use crate::plugins::stations::PassTable;
+use crate::plugins::sun::daylight::{SunEvents, daylight};
+
+// number of upcoming passes listed in the pass panel
+const PASS_PANEL_ROWS: usize = 8;
+// width of the terrain analysis legend's colour bar
+const LEGEND_WIDTH: f32 = 256.0;
+
+pub struct UiPlugin;
+
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedLocation>()
+            .add_observer(select_location)
+            .add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (update_location_panel, update_pass_panel, update_legend),
+            );
+    }
+}
+
+/// Point on the globe last clicked by the user
+#[derive(Resource, Default)]
+pub struct SelectedLocation(pub Option<LatLon>);
+
+/// Text panel showing details of the selected location
+#[derive(Component)]
+struct LocationPanel;
+
+/// Text panel listing the next satellite passes over the ground stations
+#[derive(Component)]
+struct PassPanel;
+
+/// Colour ramp of the terrain analysis view, hidden on the photographic globe
+#[derive(Component)]
+struct Legend;
+
+#[derive(Component)]
+struct LegendTitle;
+
+/// Holds the legend's labels, each placed at its value along the ramp
+#[derive(Component)]
+struct LegendLabels;
+
+fn setup(mut commands: Commands, ramp: Res<AnalysisRamp>) {
+    commands.spawn((
+        Text::new("Click on the globe to select a location"),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12),
+            left: px(12),
+            ..default()
+        },
+        LocationPanel,
+    ));
+
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12),
+            right: px(12),
+            ..default()
+        },
+        PassPanel,
+    ));
+
+    // the ramp image is the one the globe is coloured with, rebaked when the mode changes
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(12),
+            left: px(12),
+            flex_direction: FlexDirection::Column,
+            row_gap: px(4),
+            ..default()
+        },
+        Visibility::Hidden,
+        Legend,
+        children![
+            (
+                Text::default(),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                LegendTitle,
+            ),
+            (
+                ImageNode::new(ramp.0.clone()),
+                Node {
+                    width: px(LEGEND_WIDTH),
+                    height: px(12),
+                    ..default()
+                },
+            ),
+            (
+                Node {
+                    width: px(LEGEND_WIDTH),
+                    height: px(16),
+                    ..default()
+                },
+                LegendLabels,
+            ),
+        ],
+    ));
+}
+
+fn select_location(
+    mut click: On<Pointer<Click>>,
+    earths: Query<&GlobalTransform, With<Earth>>,
+    cameras: Query<&GlobalTransform, With<Camera>>,
+    relief: Res<Relief>,
+    shading: Res<EarthShadingSettings>,
+    images: Res<Assets<Image>>,
+    mut selected: ResMut<SelectedLocation>,
+) {
+    // clicks on earth meshes propagate up to the earth entity
+    let Ok(transform) = earths.get(click.entity) else {
+        return;
+    };
+    let Some(position) = click.hit.position else {
+        return;
+    };
+    click.propagate(false);
+
+    // hit position is in world space, LatLon expects the earth-fixed frame
+    let to_local = transform.affine().inverse();
+    let mut local_position = to_local.transform_point3(position);
+
+    // the meshes are flat, the ray is followed on to the raised terrain the shader draws
+    if let Ok(camera) = cameras.get(click.hit.camera) {
+        let origin = to_local.transform_point3(camera.translation());
+        if let Some(hit) = relief.hit(
+            &images,
+            shading.displacement_scale,
+            origin,
+            local_position - origin,
+        ) {
+            local_position = hit;
+        }
+    }
+    selected.0 = Some(LatLon::from(local_position));
+}
+
+fn update_location_panel(
+    selected: Res<SelectedLocation>,
+    clock: Res<SimulationClock>,
+    coverage: Res<Coverage>,
+    land_cover: Res<LandCover>,
+    images: Res<Assets<Image>>,
+    mut panels: Query<&mut Text, With<LocationPanel>>,
+) {
+    let Some(location) = selected.0 else {
+        return;
+    };
+
+    let day = start_of_day(clock.julian_date);
+    let date = clock.calendar_date();
+    let info = daylight(&location, day);
+    let (latitude, longitude) = location.as_degrees();
+
+    let mut lines = vec![
+        format!(
+            "{:.2}° {}, {:.2}° {}",
+            latitude.abs(),
+            if latitude >= 0.0 { "N" } else { "S" },
+            longitude.abs(),
+            if longitude >= 0.0 { "E" } else { "W" },
+        ),
+        format!("{:04}-{:02}-{:02}", date.year, date.month, date.day),
+        format!("Solar noon: {}", format_time(info.solar_noon, day)),
+    ];
+
+    match info.events {
+        SunEvents::RiseSet { sunrise, sunset } => {
+            lines.push(format!("Sunrise: {}", format_time(sunrise, day)));
+            lines.push(format!("Sunset: {}", format_time(sunset, day)));
+        }
+        SunEvents::PolarDay => lines.push("Polar day, the sun does not set".to_string()),
+        SunEvents::PolarNight => lines.push("Polar night, the sun does not rise".to_string()),
+    }
+
+    let minutes = (info.day_length * 60.0).round() as u32;
+    lines.push(format!(
+        "Day length: {}h {:02}m",
+        minutes / 60,
+        minutes % 60
+    ));
+
+    if let Some(class) = land_cover.land_cover_at(&images, &location) {
+        lines.push(format!("Land cover: {}", class.name));
+    }
+
+    if coverage.window.is_some() {
+        lines.push(format!(
+            "Sensor coverage: {:.1} min",
+            coverage.raster.value_at(&location)
+        ));
+    }
+
+    for mut text in &mut panels {
+        text.0 = lines.join("\n");
+    }
+}
+
+fn update_pass_panel(
+    table: Res<PassTable>,
+    clock: Res<SimulationClock>,
+    names: Query<&Name>,
+    mut panels: Query<&mut Text, With<PassPanel>>,
+) {
+    let name = |entity: Entity| {
+        names
+            .get(entity)
+            .map_or_else(|_| "?".to_string(), |name| name.to_string())
+    };
+
+    let day = start_of_day(clock.julian_date);
+    let mut lines = vec!["Upcoming passes".to_string()];
+    lines.extend(
+        table
+            .upcoming(clock.julian_date)
+            .take(PASS_PANEL_ROWS)
+            .map(|pass| {
+                format!(
+                    "{} over {}: {} - {} ({:.0} min), max {:.0}°",
+                    name(pass.satellite),
+                    name(pass.station),
+                    format_time(pass.aos, day),
+                    format_time(pass.los, day),
+                    pass.duration(),
+                    pass.max_elevation.elevation.to_degrees(),
+                )
+            }),
+    );
+    if lines.len() == 1 {
+        lines.push("none in the next prediction window".to_string());
+    }
+
+    for mut text in &mut panels {
+        text.0 = lines.join("\n");
+    }
+}
+
+/// Formats a julian date as UTC time of day, noting when it falls on another day than `day`
+fn format_time(julian_date: f64, day: f64) -> String {
+    let date = CalendarDate::from_julian_date(julian_date);
+    let day_offset = (start_of_day(julian_date) - day).round() as i32;
+
+    let mut time = format!("{:02}:{:02} UTC", date.hour, date.minute);
+    if day_offset != 0 {
+        time.push_str(&format!(" ({:+}d)", day_offset));
+    }
+    time
+}
+
+fn update_legend(
+    mut commands: Commands,
+    analysis: Res<TerrainAnalysis>,
+    mut legends: Query<&mut Visibility, With<Legend>>,
+    mut titles: Query<&mut Text, With<LegendTitle>>,
+    labels: Query<Entity, With<LegendLabels>>,
+) {
+    if !analysis.is_changed() {
+        return;
+    }
+
+    for mut visibility in &mut legends {
+        *visibility = match analysis.mode {
+            AnalysisMode::Off => Visibility::Hidden,
+            _ => Visibility::Inherited,
+        };
+    }
+    for mut title in &mut titles {
+        title.0 = analysis.mode.label().to_string();
+    }
+    for container in &labels {
+        commands
+            .entity(container)
+            .despawn_children()
+            .with_children(|parent| {
+                for (position, text) in analysis.legend_labels() {
+                    parent.spawn((
+                        Text::new(text),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: percent(position * 100.0),
+                            ..default()
+                        },
+                    ));
+                }
+            });
+    }
+}
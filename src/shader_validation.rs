@@ -0,0 +1,605 @@
+use bevy::{
+    asset::io::AssetSourceId,
+    pbr::{MATERIAL_BIND_GROUP_INDEX, Material},
+    reflect::{TypeInfo, Typed},
+    render::{
+        render_resource::{
+            BindGroupLayoutEntry, BindingType, BufferBindingType, ShaderType, TextureViewDimension,
+            encase::private::StructMetadata,
+        },
+        renderer::RenderDevice,
+    },
+    shader::ShaderRef,
+};
+use naga::{
+    AddressSpace, ImageDimension, Module, Scalar, ShaderStage, TypeInner, VectorSize,
+    proc::Layouter,
+    valid::{Capabilities, ValidationFlags, Validator},
+};
+use naga_oil::compose::{
+    ComposableModuleDescriptor, Composer, NagaModuleDescriptor, ShaderDefValue,
+};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::plugins::aurora::{AuroraMaterial, AuroraUniform};
+use crate::plugins::earth::{
+    constants::{CONSTANTS_IMPORT_PATH, constants_wgsl},
+    materials::{
+        AnalysisUniform, AtmosphereMaterial, AtmosphereUniform, CloudMaterial, DetailUniform,
+        EarthMaterial, OceanUniform, RasterFramesUniform, ShadingUniform, SurfaceUniform,
+    },
+};
+
+// Checks the shaders in assets/shaders without a gpu, so mistakes show up in `cargo test`
+// instead of as a pink globe: every shader is composed and validated with naga, the materials'
+// shaders must exist and have their entry points, and their bindings must match the AsBindGroup derives
+// down to the offset and type of every field of the uniform structs, as encase lays them out
+// bevy's shader modules are read from its crate sources, found through `cargo metadata`,
+// and the derives' layouts come from wgpu's noop device
+
+const ASSETS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets");
+
+// the vertex attributes and outputs of the globe's chunks in the main pass and the prepass
+const MAIN_PASS_DEFS: &[&str] = &[
+    "VERTEX_POSITIONS",
+    "VERTEX_NORMALS",
+    "VERTEX_UVS",
+    "VERTEX_UVS_A",
+    "VERTEX_TANGENTS",
+    "VERTEX_OUTPUT_INSTANCE_INDEX",
+];
+const PREPASS_DEFS: &[&str] = &[
+    "PREPASS_PIPELINE",
+    "VERTEX_POSITIONS",
+    "VERTEX_UVS",
+    "VERTEX_UVS_A",
+    "VERTEX_TANGENTS",
+];
+
+/// Every import module the shaders can use, composed for one set of shader defs
+struct Shaders {
+    composer: Composer,
+    defs: HashMap<String, ShaderDefValue>,
+    // our own modules that failed to compose, with the error
+    broken_modules: Vec<String>,
+}
+
+impl Shaders {
+    fn new(variant: &[&str]) -> Self {
+        let mut defs = HashMap::from([
+            (
+                "MATERIAL_BIND_GROUP".to_string(),
+                ShaderDefValue::UInt(MATERIAL_BIND_GROUP_INDEX as u32),
+            ),
+            (
+                "MAX_CASCADES_PER_LIGHT".to_string(),
+                ShaderDefValue::UInt(4),
+            ),
+            (
+                "MAX_DIRECTIONAL_LIGHTS".to_string(),
+                ShaderDefValue::UInt(10),
+            ),
+            (
+                "PER_OBJECT_BUFFER_BATCH_SIZE".to_string(),
+                ShaderDefValue::UInt(1),
+            ),
+            (
+                "AVAILABLE_STORAGE_BUFFER_BINDINGS".to_string(),
+                ShaderDefValue::UInt(8),
+            ),
+        ]);
+        for def in variant {
+            defs.insert(def.to_string(), ShaderDefValue::Bool(true));
+        }
+
+        // (source, file path, name ours are imported by, None for `#define_import_path` modules)
+        let mut pending: Vec<(String, String, Option<String>)> = bevy_shader_modules()
+            .into_iter()
+            .map(|(source, path)| (source, path, None))
+            .collect();
+        pending.push((constants_wgsl(), CONSTANTS_IMPORT_PATH.to_string(), None));
+        for path in shader_files() {
+            let source = fs::read_to_string(&path).unwrap();
+            if !has_entry_point(&source) {
+                let name = asset_path(&path);
+                pending.push((source, name.clone(), Some(format!("\"{name}\""))));
+            }
+        }
+
+        // modules can only be added after their imports, so keep going round until nothing changes
+        let mut composer = Composer::default().with_capabilities(Capabilities::all());
+        let mut errors = HashMap::new();
+        loop {
+            let before = pending.len();
+            pending.retain(|(source, path, name)| {
+                let added = composer.add_composable_module(ComposableModuleDescriptor {
+                    source,
+                    file_path: path,
+                    as_name: name.clone(),
+                    shader_defs: defs.clone(),
+                    ..Default::default()
+                });
+                match added {
+                    Ok(_) => false,
+                    Err(error) => {
+                        errors.insert(path.clone(), error.emit_to_string(&composer));
+                        true
+                    }
+                }
+            });
+            if pending.len() == before {
+                break;
+            }
+        }
+
+        // bevy has modules for features this app doesn't use, only ours have to compose
+        let broken_modules = pending
+            .iter()
+            .filter(|(_, _, name)| name.is_some())
+            .map(|(_, path, _)| format!("{path}: {}", errors[path]))
+            .collect();
+
+        Shaders {
+            composer,
+            defs,
+            broken_modules,
+        }
+    }
+
+    /// Composes and validates a shader with entry points
+    fn module(&mut self, path: &Path) -> Result<Module, String> {
+        let name = asset_path(path);
+        let source =
+            fs::read_to_string(path).map_err(|error| format!("{name} can't be read: {error}"))?;
+        let module = self
+            .composer
+            .make_naga_module(NagaModuleDescriptor {
+                source: &source,
+                file_path: &name,
+                shader_defs: self.defs.clone(),
+                ..Default::default()
+            })
+            .map_err(|error| error.emit_to_string(&self.composer))?;
+        Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(&module)
+            .map_err(|error| format!("{name}: {}", error.emit_to_string(&source)))?;
+        Ok(module)
+    }
+}
+
+/// A uniform struct's fields, with the offset encase writes each one at
+struct UniformLayout {
+    name: &'static str,
+    // field name, offset and type name
+    fields: Vec<(&'static str, u64, &'static str)>,
+}
+
+impl UniformLayout {
+    fn of<T, const N: usize>() -> Self
+    where
+        T: ShaderType<ExtraMetadata = StructMetadata<N>> + Typed,
+    {
+        let TypeInfo::Struct(info) = T::type_info() else {
+            panic!("uniforms are structs");
+        };
+        let fields = info
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let type_name = field.type_path_table().ident().unwrap_or("?");
+                (field.name(), T::METADATA.offset(index), type_name)
+            })
+            .collect();
+        UniformLayout {
+            name: info.type_path_table().ident().unwrap_or("?"),
+            fields,
+        }
+    }
+}
+
+// the naga type a field of a uniform struct becomes in wgsl
+fn shader_type(type_name: &str) -> Option<TypeInner> {
+    let scalar = match type_name {
+        "f32" | "Vec2" | "Vec3" | "Vec4" => Scalar::F32,
+        "u32" | "UVec2" | "UVec3" | "UVec4" => Scalar::U32,
+        "i32" | "IVec2" | "IVec3" | "IVec4" => Scalar::I32,
+        _ => return None,
+    };
+    let size = match type_name.chars().last() {
+        Some('2') if type_name.len() > 3 => VectorSize::Bi,
+        Some('3') if type_name.len() > 3 => VectorSize::Tri,
+        Some('4') if type_name.len() > 3 => VectorSize::Quad,
+        _ => return Some(TypeInner::Scalar(scalar)),
+    };
+    Some(TypeInner::Vector { size, scalar })
+}
+
+/// Sources of bevy's `#define_import_path` shader modules
+fn bevy_shader_modules() -> Vec<(String, String)> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(["metadata", "--format-version", "1", "--offline"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("cargo metadata should run");
+    assert!(
+        output.status.success(),
+        "cargo metadata failed with {}:\n{}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let metadata: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("cargo metadata should print json");
+
+    let mut files = Vec::new();
+    for package in metadata["packages"].as_array().unwrap() {
+        let name = package["name"].as_str().unwrap_or_default();
+        if !name.starts_with("bevy_") {
+            continue;
+        }
+        let manifest = Path::new(package["manifest_path"].as_str().unwrap());
+        collect_wgsl(&manifest.with_file_name("src"), &mut files);
+    }
+
+    files
+        .into_iter()
+        .filter_map(|path| {
+            let source = fs::read_to_string(&path).ok()?;
+            source
+                .contains("#define_import_path")
+                .then(|| (source, path.display().to_string()))
+        })
+        .collect()
+}
+
+fn shader_files() -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_wgsl(&Path::new(ASSETS_DIR).join("shaders"), &mut files);
+    files.sort();
+    files
+}
+
+fn collect_wgsl(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_wgsl(&path, files);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "wgsl")
+        {
+            files.push(path);
+        }
+    }
+}
+
+fn has_entry_point(source: &str) -> bool {
+    ["@vertex", "@fragment", "@compute"]
+        .iter()
+        .any(|attribute| source.contains(attribute))
+}
+
+// path relative to assets/, as a ShaderRef or an import names it
+fn asset_path(path: &Path) -> String {
+    path.strip_prefix(ASSETS_DIR)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn render_device() -> RenderDevice {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    RenderDevice::from(device)
+}
+
+/// Everything wrong between a material and its shaders
+/// `uniforms` are the material's uniform structs by binding
+fn material_errors<M: Material>(name: &str, uniforms: &[(u32, UniformLayout)]) -> Vec<String> {
+    let layout = M::bind_group_layout_entries(&render_device(), true);
+    let stages = [
+        (
+            M::vertex_shader(),
+            ShaderStage::Vertex,
+            "vertex",
+            MAIN_PASS_DEFS,
+        ),
+        (
+            M::fragment_shader(),
+            ShaderStage::Fragment,
+            "fragment",
+            MAIN_PASS_DEFS,
+        ),
+        (
+            M::prepass_vertex_shader(),
+            ShaderStage::Vertex,
+            "vertex",
+            PREPASS_DEFS,
+        ),
+        (
+            M::prepass_fragment_shader(),
+            ShaderStage::Fragment,
+            "fragment",
+            PREPASS_DEFS,
+        ),
+    ];
+
+    let mut errors = Vec::new();
+    for (shader, stage, entry_point, defs) in stages {
+        // bevy's own shaders, the defaults and embedded ones, are not ours to check
+        let ShaderRef::Path(path) = shader else {
+            continue;
+        };
+        if path.source() != &AssetSourceId::Default {
+            continue;
+        }
+        let path = Path::new(ASSETS_DIR).join(path.path());
+        if !path.exists() {
+            errors.push(format!(
+                "{name} uses {}, which doesn't exist",
+                path.display()
+            ));
+            continue;
+        }
+
+        let module = match Shaders::new(defs).module(&path) {
+            Ok(module) => module,
+            Err(error) => {
+                errors.push(error);
+                continue;
+            }
+        };
+        let shader = asset_path(&path);
+        if !module
+            .entry_points
+            .iter()
+            .any(|entry| entry.name == entry_point && entry.stage == stage)
+        {
+            errors.push(format!(
+                "{shader} has no {stage:?} entry point `{entry_point}`"
+            ));
+        }
+        errors.extend(
+            binding_errors(&module, &layout, uniforms)
+                .into_iter()
+                .map(|error| format!("{name}, {shader}: {error}")),
+        );
+    }
+    errors
+}
+
+/// Material bindings the shader declares differently from the bind group layout
+fn binding_errors(
+    module: &Module,
+    layout: &[BindGroupLayoutEntry],
+    uniforms: &[(u32, UniformLayout)],
+) -> Vec<String> {
+    let mut layouter = Layouter::default();
+    layouter.update(module.to_ctx()).unwrap();
+
+    let mut errors = Vec::new();
+    for (_, variable) in module.global_variables.iter() {
+        let Some(binding) = &variable.binding else {
+            continue;
+        };
+        if binding.group != MATERIAL_BIND_GROUP_INDEX as u32 {
+            continue;
+        }
+        let name = variable.name.as_deref().unwrap_or("?");
+        let Some(entry) = layout.iter().find(|entry| entry.binding == binding.binding) else {
+            errors.push(format!(
+                "`{name}` is at binding {}, the material has nothing there",
+                binding.binding
+            ));
+            continue;
+        };
+
+        let inner = &module.types[variable.ty].inner;
+        match (inner, entry.ty) {
+            (
+                TypeInner::Image { dim, arrayed, .. },
+                BindingType::Texture { view_dimension, .. },
+            ) => {
+                let declared = match (dim, arrayed) {
+                    (ImageDimension::D1, _) => TextureViewDimension::D1,
+                    (ImageDimension::D2, false) => TextureViewDimension::D2,
+                    (ImageDimension::D2, true) => TextureViewDimension::D2Array,
+                    (ImageDimension::D3, _) => TextureViewDimension::D3,
+                    (ImageDimension::Cube, false) => TextureViewDimension::Cube,
+                    (ImageDimension::Cube, true) => TextureViewDimension::CubeArray,
+                };
+                if declared != view_dimension {
+                    errors.push(format!(
+                        "`{name}` is a {declared:?} texture, the material binds a {view_dimension:?} one"
+                    ));
+                }
+            }
+            (TypeInner::Sampler { .. }, BindingType::Sampler(_)) => {}
+            (
+                _,
+                BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    min_binding_size,
+                    ..
+                },
+            ) if variable.space == AddressSpace::Uniform => {
+                let size = layouter[variable.ty].size as u64;
+                if let Some(expected) = min_binding_size
+                    && expected.get() != size
+                {
+                    errors.push(format!(
+                        "`{name}` is {size} bytes, the material's uniform is {expected}"
+                    ));
+                }
+                if let Some((_, uniform)) = uniforms.iter().find(|(at, _)| *at == binding.binding) {
+                    errors.extend(member_errors(module, name, inner, uniform));
+                }
+            }
+            _ => errors.push(format!(
+                "`{name}` at binding {} doesn't match the material's {:?}",
+                binding.binding, entry.ty
+            )),
+        }
+    }
+    errors
+}
+
+/// Fields of a uniform struct the shader declares at a different offset or with a different type
+fn member_errors(
+    module: &Module,
+    name: &str,
+    inner: &TypeInner,
+    uniform: &UniformLayout,
+) -> Vec<String> {
+    let TypeInner::Struct { members, .. } = inner else {
+        return vec![format!(
+            "`{name}` is not a struct, the material binds {}",
+            uniform.name
+        )];
+    };
+    let mut errors = Vec::new();
+    if members.len() != uniform.fields.len() {
+        errors.push(format!(
+            "`{name}` has {} members, {} has {} fields",
+            members.len(),
+            uniform.name,
+            uniform.fields.len()
+        ));
+    }
+    for (member, &(field, offset, type_name)) in members.iter().zip(&uniform.fields) {
+        let member_name = member.name.as_deref().unwrap_or("?");
+        if member.offset as u64 != offset {
+            errors.push(format!(
+                "`{name}.{member_name}` is at offset {}, {}::{field} at {offset}",
+                member.offset, uniform.name
+            ));
+        }
+        let declared = &module.types[member.ty].inner;
+        match shader_type(type_name) {
+            Some(expected) if expected == *declared => {}
+            Some(_) => errors.push(format!(
+                "`{name}.{member_name}` is a {declared:?}, {}::{field} is a {type_name}",
+                uniform.name
+            )),
+            None => errors.push(format!(
+                "{}::{field} is a {type_name}, which has no wgsl type here",
+                uniform.name
+            )),
+        }
+    }
+    errors
+}
+
+fn assert_no_errors(errors: Vec<String>) {
+    assert!(errors.is_empty(), "\n{}", errors.join("\n\n"));
+}
+
+#[test]
+fn shaders_compose_and_validate() {
+    for defs in [MAIN_PASS_DEFS, PREPASS_DEFS] {
+        let mut shaders = Shaders::new(defs);
+        let mut errors = std::mem::take(&mut shaders.broken_modules);
+        for path in shader_files() {
+            let source = fs::read_to_string(&path).unwrap();
+            if !has_entry_point(&source) {
+                continue;
+            }
+            // only the globe's vertex shader runs in the prepass
+            if defs == PREPASS_DEFS && !source.contains("PREPASS_PIPELINE") {
+                continue;
+            }
+            if let Err(error) = shaders.module(&path) {
+                errors.push(error);
+            }
+        }
+        assert_no_errors(errors);
+    }
+}
+
+#[test]
+fn earth_material_matches_shaders() {
+    let uniforms = [
+        (111, UniformLayout::of::<AtmosphereUniform, _>()),
+        (124, UniformLayout::of::<RasterFramesUniform, _>()),
+        (125, UniformLayout::of::<OceanUniform, _>()),
+        (132, UniformLayout::of::<SurfaceUniform, _>()),
+        (136, UniformLayout::of::<ShadingUniform, _>()),
+        (137, UniformLayout::of::<AnalysisUniform, _>()),
+        (148, UniformLayout::of::<DetailUniform, _>()),
+    ];
+    assert_no_errors(material_errors::<EarthMaterial>("EarthMaterial", &uniforms));
+}
+
+#[test]
+fn atmosphere_material_matches_shaders() {
+    let uniforms = [(0, UniformLayout::of::<AtmosphereUniform, _>())];
+    assert_no_errors(material_errors::<AtmosphereMaterial>(
+        "AtmosphereMaterial",
+        &uniforms,
+    ));
+}
+
+#[test]
+fn cloud_material_matches_shaders() {
+    let uniforms = [(7, UniformLayout::of::<RasterFramesUniform, _>())];
+    assert_no_errors(material_errors::<CloudMaterial>("CloudMaterial", &uniforms));
+}
+
+#[test]
+fn aurora_material_matches_shaders() {
+    let uniforms = [(0, UniformLayout::of::<AuroraUniform, _>())];
+    assert_no_errors(material_errors::<AuroraMaterial>(
+        "AuroraMaterial",
+        &uniforms,
+    ));
+}
+
+#[test]
+fn finds_fields_out_of_place() {
+    // a shader that swapped two of SurfaceUniform's members and made a third a vector
+    let source = "
+struct SurfaceUniform {
+    cloud_opacity: f32,
+    cloud_drift: f32,
+    day_blend: vec2<f32>,
+    debug_mode: u32,
+    show_land_cover: u32,
+    _padding: vec2<f32>,
+}
+@group(2) @binding(132) var<uniform> surface: SurfaceUniform;
+@fragment
+fn fragment() -> @location(0) vec4<f32> {
+    return vec4<f32>(surface.cloud_drift);
+}
+";
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let inner = module
+        .global_variables
+        .iter()
+        .find_map(|(_, variable)| (variable.binding.is_some()).then_some(variable.ty))
+        .map(|ty| &module.types[ty].inner)
+        .unwrap();
+    let uniform = UniformLayout::of::<SurfaceUniform, _>();
+    let errors = member_errors(&module, "surface", inner, &uniform);
+
+    assert!(errors[0].contains("has 6 members"), "{errors:?}");
+    assert!(
+        errors
+            .iter()
+            .any(|error| error.contains("`surface.day_blend` is a Vector")),
+        "{errors:?}"
+    );
+    assert!(
+        errors
+            .iter()
+            .any(|error| error.contains("`surface.debug_mode` is at offset 16")),
+        "{errors:?}"
+    );
+}